@@ -1,7 +1,7 @@
 //! This example demonstrates how to implement a custom Radiotap field parser by
-//! implementing the `field::Field` trait.
+//! implementing the `field::Field` and `field::PresentField` traits.
 
-use radiotap::{field, Error, RadiotapIterator};
+use radiotap::{field, Error, Radiotap, RadiotapIterator};
 
 /// Our custom Antenna Signal struct
 #[derive(Debug)]
@@ -17,6 +17,10 @@ impl field::Field for MyAntennaSignal {
     }
 }
 
+impl field::PresentField for MyAntennaSignal {
+    const KIND: field::Kind = field::Kind::AntennaSignal;
+}
+
 fn main() {
     let capture = [
         0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213, 166,
@@ -27,7 +31,11 @@ fn main() {
     for element in RadiotapIterator::from_bytes(&capture).unwrap() {
         if let Ok((field::Kind::AntennaSignal, data)) = element {
             let signal: MyAntennaSignal = field::from_bytes(data).unwrap();
-            println!("{:?}", signal);
+            println!("{} dBm", signal.value);
         }
     }
+
+    // A field with a kind can be located directly
+    let signal = Radiotap::get_one::<MyAntennaSignal>(&capture).unwrap();
+    println!("{:?}", signal);
 }
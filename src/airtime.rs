@@ -0,0 +1,245 @@
+//! Estimation of the time a frame occupies the medium, for channel utilization
+//! tooling.
+//!
+//! The estimate is derived from the PHY fields of the capture: the HE, VHT,
+//! and MCS fields, or the legacy rate. It includes the preamble and the data
+//! symbols, but not the HE-SIG-B field of HE MU PPDUs, the packet extension,
+//! or the interframe spacing and acknowledgement.
+
+use core::time::Duration;
+
+use crate::{
+    field::{ext::*, HE, MCS, VHT},
+    summary::is_dsss_rate,
+    util::channel::Band,
+    Radiotap,
+};
+
+/// The SERVICE and tail bits added to the PSDU by the OFDM PHYs.
+const SERVICE_TAIL_BITS: u64 = 16 + 6;
+
+/// The duration of the legacy preamble and L-SIG field in ns.
+const LEGACY_PREAMBLE: u64 = 20_000;
+
+/// Returns the estimated on-air time of a frame with a PSDU (the MPDU or
+/// A-MPDU, including the FCS) of `psdu_len` bytes, sent with the PHY
+/// parameters of the capture.
+///
+/// `None` is returned if the data rate cannot be determined, see
+/// [Radiotap::airtime](../struct.Radiotap.html#method.airtime).
+pub fn estimate(radiotap: &Radiotap, psdu_len: usize) -> Option<Duration> {
+    let bits = 8 * psdu_len as u64 + SERVICE_TAIL_BITS;
+
+    let ns = if let Some(he) = &radiotap.he {
+        he_airtime(he, bits)?
+    } else if let Some(vht) = &radiotap.vht {
+        vht_airtime(vht, bits)?
+    } else if let Some(mcs) = &radiotap.mcs {
+        ht_airtime(mcs, bits)?
+    } else {
+        let mbps = radiotap.rate?.as_mbps();
+        let rate = units(mbps)?;
+        if is_dsss_rate(mbps) {
+            // The long preamble is always used at 1 Mbps
+            let short = radiotap.flags.is_some_and(|flags| flags.preamble) && rate > 10;
+            let preamble = if short { 96_000 } else { 192_000 };
+            // The length of the data is given in whole microseconds
+            preamble + (8 * psdu_len as u64 * 10).div_ceil(rate) * 1000
+        } else {
+            let band = radiotap
+                .channel
+                .and_then(|channel| Band::from_freq(channel.freq));
+            // OFDM in the 2.4 GHz band is followed by a signal extension
+            let extension = if band == Some(Band::Band2GHz) {
+                6_000
+            } else {
+                0
+            };
+            LEGACY_PREAMBLE + data(bits, rate, 4_000)? + extension
+        }
+    };
+
+    Some(Duration::from_nanos(ns))
+}
+
+fn he_airtime(he: &HE, bits: u64) -> Option<u64> {
+    let rate = units(he.datarate()?)?;
+    let gi = match he.gi? {
+        HEGuardInterval::GI800 => 800,
+        HEGuardInterval::GI1600 => 1_600,
+        HEGuardInterval::GI3200 => 3_200,
+    };
+    // The 2x LTF is the most common when the size is unknown
+    let ltf = match he.ltf_size.unwrap_or(LTFSize::X2) {
+        LTFSize::X1 => 3_200,
+        LTFSize::X2 => 6_400,
+        LTFSize::X4 => 12_800,
+    } + gi;
+    let ltfs = he
+        .ltf_symbols
+        .unwrap_or_else(|| ltf_symbols(he.nsts.unwrap_or(1)));
+
+    // RL-SIG, HE-SIG-A, and HE-STF
+    let preamble = match he.format {
+        Some(HEFormat::ExtSU) => 4_000 + 16_000 + 4_000,
+        Some(HEFormat::Trig) => 4_000 + 8_000 + 8_000,
+        _ => 4_000 + 8_000 + 4_000,
+    };
+
+    Some(LEGACY_PREAMBLE + preamble + u64::from(ltfs) * ltf + data(bits, rate, 12_800 + gi)?)
+}
+
+fn vht_airtime(vht: &VHT, bits: u64) -> Option<u64> {
+    let user = vht.users.iter().flatten().next()?;
+    let rate = units(user.datarate?)?;
+
+    // VHT-SIG-A, VHT-STF, VHT-LTFs, and VHT-SIG-B
+    let preamble = 8_000 + 4_000 + u64::from(ltf_symbols(user.nsts)) * 4_000 + 4_000;
+
+    Some(LEGACY_PREAMBLE + preamble + data(bits, rate, symbol(vht.gi?))?)
+}
+
+fn ht_airtime(mcs: &MCS, bits: u64) -> Option<u64> {
+    let index = mcs.index?;
+    let rate = units(mcs.datarate?)?;
+    let nss = match index {
+        0..=31 => index / 8 + 1,
+        32 => 1,
+        33..=38 => 2,
+        39..=52 => 3,
+        _ => 4,
+    };
+    let ltfs = u64::from(ltf_symbols(nss + mcs.stbc.unwrap_or(0)) + mcs.ness.unwrap_or(0));
+
+    let preamble = match mcs.format {
+        // HT-GF-STF, HT-LTF1, HT-SIG, and the remaining HT-LTFs
+        Some(HTFormat::Greenfield) => 8_000 + 8_000 + 8_000 + (ltfs - 1) * 4_000,
+        // The legacy preamble, HT-SIG, HT-STF, and HT-LTFs
+        _ => LEGACY_PREAMBLE + 8_000 + 4_000 + ltfs * 4_000,
+    };
+
+    Some(preamble + data(bits, rate, symbol(mcs.gi?))?)
+}
+
+/// Returns the duration in ns of the OFDM symbols carrying `bits` at a rate
+/// in units of 100 kbps, with symbols of `symbol` ns.
+fn data(bits: u64, rate: u64, symbol: u64) -> Option<u64> {
+    // The data bits per symbol, the rates are rounded to 0.1 Mbps
+    let per_symbol = (rate * symbol + 5_000) / 10_000;
+    if per_symbol == 0 {
+        return None;
+    }
+    Some(bits.div_ceil(per_symbol) * symbol)
+}
+
+/// Returns the duration in ns of an HT or VHT OFDM symbol.
+fn symbol(gi: GuardInterval) -> u64 {
+    match gi {
+        GuardInterval::Long => 4_000,
+        GuardInterval::Short => 3_600,
+    }
+}
+
+/// Returns the number of long training field symbols for the number of
+/// space-time streams.
+fn ltf_symbols(nsts: u8) -> u8 {
+    match nsts {
+        0 | 1 => 1,
+        2 => 2,
+        3 | 4 => 4,
+        5 | 6 => 6,
+        _ => 8,
+    }
+}
+
+/// Returns a rate in Mbps in units of 100 kbps, or `None` if it is not
+/// positive.
+fn units(rate: f32) -> Option<u64> {
+    let units = (rate * 10.0 + 0.5) as u64;
+    Some(units).filter(|&units| units > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::{Channel, Field, Flags, Rate};
+
+    fn micros(radiotap: &Radiotap, psdu_len: usize) -> f64 {
+        estimate(radiotap, psdu_len).unwrap().as_nanos() as f64 / 1000.0
+    }
+
+    #[test]
+    fn legacy() {
+        let mut radiotap = Radiotap {
+            rate: Some(Rate::from_mbps(1.0).unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(micros(&radiotap, 100), 992.0);
+
+        radiotap.rate = Some(Rate::from_mbps(11.0).unwrap());
+        radiotap.flags = Some(Flags::from_bytes(&[0x02]).unwrap());
+        assert_eq!(micros(&radiotap, 100), 169.0);
+
+        radiotap.rate = Some(Rate::from_mbps(54.0).unwrap());
+        assert_eq!(micros(&radiotap, 1500), 244.0);
+
+        radiotap.channel = Some(Channel::from_bytes(&[0x85, 0x09, 0xc0, 0]).unwrap());
+        assert_eq!(micros(&radiotap, 1500), 250.0);
+
+        radiotap.rate = None;
+        assert_eq!(estimate(&radiotap, 1500), None);
+    }
+
+    #[test]
+    fn ht() {
+        let radiotap = Radiotap {
+            mcs: Some(MCS {
+                bw: Some(Bandwidth::new(0).unwrap()),
+                index: Some(7),
+                gi: Some(GuardInterval::Long),
+                datarate: Some(65.0),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(micros(&radiotap, 1500), 224.0);
+    }
+
+    #[test]
+    fn vht() {
+        let user = VHTUser {
+            index: 9,
+            fec: FEC::LDPC,
+            nss: 2,
+            nsts: 2,
+            datarate: Some(866.7),
+        };
+        let radiotap = Radiotap {
+            vht: Some(VHT {
+                gi: Some(GuardInterval::Short),
+                bw: Some(Bandwidth::new(4).unwrap()),
+                users: [Some(user), None, None, None],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(micros(&radiotap, 1500), 58.4);
+    }
+
+    #[test]
+    fn he() {
+        let radiotap = Radiotap {
+            he: Some(HE {
+                format: Some(HEFormat::SU),
+                index: Some(11),
+                bw: Some(HEBandwidth::Bandwidth(Bandwidth::new(4).unwrap())),
+                gi: Some(HEGuardInterval::GI800),
+                ltf_size: Some(LTFSize::X2),
+                nsts: Some(2),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(micros(&radiotap, 1500), 64.0);
+    }
+}
@@ -0,0 +1,27 @@
+//! Parsing of many captures in parallel with
+//! [rayon](https://docs.rs/rayon/1/rayon/), for offline processing of large
+//! capture files.
+//!
+//! Enabled with the `rayon` feature.
+
+use rayon::prelude::*;
+
+use crate::{Radiotap, Result};
+
+/// Parses each capture on the rayon thread pool, returning the results in the
+/// same order as the captures.
+///
+/// # Examples
+///
+/// ```
+/// let captures: [&[u8]; 2] = [&[0, 0, 9, 0, 4, 0, 0, 0, 12], &[0, 0, 9, 0]];
+/// let results = radiotap::parse_batch(&captures);
+/// assert_eq!(results[0].as_ref().unwrap().rate.unwrap().as_mbps(), 6.0);
+/// assert!(results[1].is_err());
+/// ```
+pub fn parse_batch(captures: &[&[u8]]) -> Vec<Result<Radiotap>> {
+    captures
+        .par_iter()
+        .map(|capture| Radiotap::from_bytes(capture))
+        .collect()
+}
@@ -0,0 +1,112 @@
+//! Writes the Radiotap header described by a TOML, YAML, or JSON
+//! specification.
+//!
+//! ```text
+//! radiotap-build [--raw | -o OUTPUT] [SPEC]
+//! ```
+//!
+//! Reads the specification from standard input if no file or `-` is given,
+//! see the `config` module for its format. It is JSON if the file has a
+//! `.json` extension or starts with `{`, YAML if the file has a `.yaml` or
+//! `.yml` extension, and TOML otherwise. The header is
+//! printed in hexadecimal, or written as raw bytes to standard output with
+//! `--raw` or to a file with `-o`.
+
+use std::{
+    env, fs,
+    io::{self, Read, Write},
+    process,
+};
+
+use radiotap::{config, Error};
+
+const USAGE: &str = "usage: radiotap-build [--raw | -o OUTPUT] [SPEC]";
+
+/// Returns the serialized Radiotap header described by the specification,
+/// read from a file with the given extension.
+fn build(spec: &str, extension: Option<&str>) -> Result<Vec<u8>, Error> {
+    let builder = match extension {
+        Some("json") => config::from_json_str(spec)?,
+        Some("yaml") | Some("yml") => config::from_yaml_str(spec)?,
+        _ if spec.trim_start().starts_with('{') => config::from_json_str(spec)?,
+        _ => config::from_str(spec)?,
+    };
+    builder.done().to_bytes()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn fail<T: std::fmt::Display>(message: T) -> ! {
+    eprintln!("radiotap-build: {}", message);
+    process::exit(1);
+}
+
+fn main() {
+    let mut raw = false;
+    let mut output = None;
+    let mut path = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--raw" => raw = true,
+            "-o" => match args.next() {
+                Some(arg) => output = Some(arg),
+                None => fail(USAGE),
+            },
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ if path.is_none() => path = Some(arg),
+            _ => fail(USAGE),
+        }
+    }
+
+    let mut spec = String::new();
+    let extension = match path.as_deref() {
+        None | Some("-") => {
+            if let Err(e) = io::stdin().read_to_string(&mut spec) {
+                fail(e);
+            }
+            None
+        }
+        Some(path) => {
+            spec = fs::read_to_string(path).unwrap_or_else(|e| fail(format!("{}: {}", path, e)));
+            path.rsplit_once('.').map(|(_, extension)| extension)
+        }
+    };
+    let bytes = build(&spec, extension).unwrap_or_else(|e| fail(e));
+
+    let result = match output {
+        Some(output) => fs::write(output, &bytes),
+        None if raw => io::stdout().write_all(&bytes),
+        None => writeln!(io::stdout(), "{}", hex(&bytes)),
+    };
+    if let Err(e) = result {
+        fail(e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn specs() {
+        let toml = "rate = 6.0\ntx_power = 10\n";
+        assert_eq!(hex(&build(toml, None).unwrap()), "00000a00040400000c0a");
+        let json = r#"{"rate": 6.0, "tx_power": 10}"#;
+        assert_eq!(build(json, None).unwrap(), build(toml, None).unwrap());
+        let yaml = "rate: 6.0\ntx_power: 10\n";
+        assert_eq!(
+            build(yaml, Some("yml")).unwrap(),
+            build(toml, None).unwrap()
+        );
+        assert!(matches!(
+            build("rate = 6.0", Some("json")),
+            Err(Error::InvalidConfig(_))
+        ));
+    }
+}
@@ -0,0 +1,138 @@
+//! Prints the Radiotap header of each record of a pcap or pcapng file.
+//!
+//! ```text
+//! radiotap-dump [--line] [FILE]
+//! ```
+//!
+//! Reads standard input if no file or `-` is given. Each header is printed
+//! with the `Display` implementation of `Radiotap`, or on a single line like
+//! tcpdump with `--line`.
+
+use std::{
+    env,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    process,
+};
+
+use radiotap::{pcap::Reader, Error};
+
+const USAGE: &str = "usage: radiotap-dump [--line] [FILE]";
+
+/// Prints the records read from the input, returning the number of records
+/// whose Radiotap header could not be parsed.
+fn dump<R: Read, W: Write>(input: R, out: &mut W, line: bool) -> Result<usize, Error> {
+    let mut errors = 0;
+    for (index, record) in Reader::new(input)?.enumerate() {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) if e.is_recoverable() => {
+                errors += 1;
+                writeln!(out, "#{} error: {}", index + 1, e)?;
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        let timestamp = record.timestamp;
+        let radiotap = &record.radiotap;
+        if line {
+            writeln!(
+                out,
+                "{}.{:06} {}",
+                timestamp.as_secs(),
+                timestamp.subsec_micros(),
+                radiotap.to_line()
+            )?;
+        } else {
+            writeln!(
+                out,
+                "#{} {}.{:06}, {} bytes\n{}",
+                index + 1,
+                timestamp.as_secs(),
+                timestamp.subsec_micros(),
+                record.original_length,
+                radiotap
+            )?;
+        }
+    }
+    Ok(errors)
+}
+
+fn main() {
+    let mut line = false;
+    let mut path = None;
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--line" => line = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ if path.is_none() => path = Some(arg),
+            _ => {
+                eprintln!("{}", USAGE);
+                process::exit(2);
+            }
+        }
+    }
+
+    let input: Box<dyn Read> = match path.as_deref() {
+        None | Some("-") => Box::new(io::stdin().lock()),
+        Some(path) => match File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(e) => {
+                eprintln!("radiotap-dump: {}: {}", path, e);
+                process::exit(1);
+            }
+        },
+    };
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let result = dump(input, &mut out, line);
+    let _ = out.flush();
+    match result {
+        Ok(0) => {}
+        Ok(_) => process::exit(1),
+        Err(e) => {
+            eprintln!("radiotap-dump: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records() {
+        let mut file = vec![0xd4, 0xc3, 0xb2, 0xa1, 2, 0, 4, 0];
+        file.extend_from_slice(&[0; 8]);
+        file.extend_from_slice(&65535u32.to_le_bytes());
+        file.extend_from_slice(&127u32.to_le_bytes());
+        let frames: [&[u8]; 2] = [
+            &[0, 0, 10, 0, 0x24, 0, 0, 0, 12, 0xd6, 0x80, 0],
+            &[0, 0, 99, 0],
+        ];
+        for (seconds, frame) in frames.iter().enumerate() {
+            file.extend_from_slice(&(seconds as u32 + 1).to_le_bytes());
+            file.extend_from_slice(&5u32.to_le_bytes());
+            file.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+            file.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+            file.extend_from_slice(frame);
+        }
+
+        let mut out = Vec::new();
+        assert_eq!(dump(&file[..], &mut out, true).unwrap(), 1);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "1.000005 6.0 Mb/s -42dBm signal\n#2 error: invalid radiotap length\n"
+        );
+
+        let mut out = Vec::new();
+        dump(&file[..], &mut out, false).unwrap();
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .starts_with("#1 1.000005, 12 bytes\nRadiotap v0, 10 bytes, present 0x00000024\n"));
+    }
+}
@@ -0,0 +1,34 @@
+//! Bit tests on the integers fields are decoded from.
+
+/// Tests bits and flags of an integer.
+pub(crate) trait BitOps: Copy {
+    /// Returns whether any of the bits of the flag are set.
+    fn is_flag_set(self, flag: Self) -> bool;
+
+    /// Returns whether the bit at the given index is set.
+    fn is_bit_set(self, bit: u8) -> bool;
+
+    /// Returns `count` bits starting at the given index, shifted down to the
+    /// lowest bits.
+    fn bits_as_int(self, bit: u8, count: u8) -> Self;
+}
+
+macro_rules! impl_bit_ops {
+    ($($ty:ty),*) => {$(
+        impl BitOps for $ty {
+            fn is_flag_set(self, flag: $ty) -> bool {
+                self & flag != 0
+            }
+
+            fn is_bit_set(self, bit: u8) -> bool {
+                self.is_flag_set(1 << bit)
+            }
+
+            fn bits_as_int(self, bit: u8, count: u8) -> $ty {
+                (self >> bit) & ((1 << count) - 1)
+            }
+        }
+    )*};
+}
+
+impl_bit_ops!(u8, u16, u32, u64);
@@ -0,0 +1,102 @@
+//! Parsing from any [Buf](https://docs.rs/bytes/1/bytes/trait.Buf.html) and
+//! writing into any [BufMut](https://docs.rs/bytes/1/bytes/trait.BufMut.html),
+//! for pipelines holding frames in the types of the bytes crate.
+//!
+//! Enabled with the `bytes` feature.
+
+use bytes::{Buf, BufMut};
+
+use crate::{Error, Radiotap, Result};
+
+impl Radiotap {
+    /// Parses the Radiotap capture at the start of the buffer, advancing it
+    /// to the payload.
+    ///
+    /// A capture in a single chunk is parsed in place, one spread over several
+    /// chunks is first copied, without copying the payload. If an error is
+    /// returned the buffer may have been advanced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Buf;
+    /// use radiotap::Radiotap;
+    ///
+    /// let header: &[u8] = &[0, 0, 9, 0];
+    /// let rest: &[u8] = &[4, 0, 0, 0, 12, 0x80, 0];
+    /// let mut buf = header.chain(rest);
+    ///
+    /// let radiotap = Radiotap::parse_buf(&mut buf).unwrap();
+    /// assert_eq!(radiotap.rate.unwrap().as_mbps(), 6.0);
+    /// assert_eq!(buf.chunk(), &[0x80, 0]);
+    /// ```
+    pub fn parse_buf<B: Buf>(buf: &mut B) -> Result<Radiotap> {
+        let chunk = buf.chunk();
+        if chunk.len() >= 4 {
+            let length = usize::from(u16::from_le_bytes([chunk[2], chunk[3]]));
+            if chunk.len() >= length {
+                let (radiotap, rest) = Radiotap::parse(chunk)?;
+                let consumed = chunk.len() - rest.len();
+                buf.advance(consumed);
+                return Ok(radiotap);
+            }
+        }
+
+        if buf.remaining() < 4 {
+            return Err(Error::IncompleteError);
+        }
+        let mut head = [0; 4];
+        buf.copy_to_slice(&mut head);
+        let length = usize::from(u16::from_le_bytes([head[2], head[3]])).max(4);
+        if buf.remaining() < length - 4 {
+            return Err(Error::IncompleteError);
+        }
+
+        let mut data = Vec::new();
+        data.try_reserve_exact(length)?;
+        data.extend_from_slice(&head);
+        data.resize(length, 0);
+        buf.copy_to_slice(&mut data[4..]);
+        Radiotap::from_bytes(&data)
+    }
+
+    /// Writes the Radiotap capture into the buffer, see
+    /// [unparse](struct.Radiotap.html#method.unparse).
+    pub fn unparse_buf<B: BufMut>(&self, buf: &mut B) -> Result<usize> {
+        self.unparse(&mut buf.writer())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::BytesMut;
+
+    #[test]
+    fn round_trip() {
+        let frame = [
+            0, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+        let expected = Radiotap::from_bytes(&frame).unwrap();
+
+        // Split in the middle of the present words and of the fields
+        for split in [2, 6, 20, 38].iter() {
+            let mut buf = frame[..*split].chain(&frame[*split..]);
+            assert_eq!(Radiotap::parse_buf(&mut buf).unwrap(), expected);
+            assert!(!buf.has_remaining());
+        }
+
+        let mut buf = BytesMut::new();
+        let written = expected.unparse_buf(&mut buf).unwrap();
+        assert_eq!(buf.len(), written);
+        let bytes = buf.freeze();
+        assert_eq!(
+            Radiotap::parse_buf(&mut bytes.clone()).unwrap(),
+            Radiotap::from_bytes(&bytes).unwrap()
+        );
+
+        let mut buf = &frame[..20];
+        assert!(Radiotap::parse_buf(&mut buf).is_err());
+    }
+}
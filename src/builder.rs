@@ -0,0 +1,307 @@
+//! A builder for constructing Radiotap captures, for example to inject frames.
+
+use alloc::vec::Vec;
+
+use crate::{field::*, validate, validate::Violation, Error, Radiotap};
+
+/// The error returned by
+/// [RadiotapBuilder::try_done](struct.RadiotapBuilder.html#method.try_done).
+#[derive(Debug, thiserror::Error)]
+pub enum BuildError {
+    /// The fields contradict each other or do not follow the specification.
+    #[error("radiotap header violates the specification: {}", validate::ViolationList(.0))]
+    Violations(Vec<Violation>),
+
+    /// The capture could not be written, see
+    /// [Radiotap::unparse](struct.Radiotap.html#method.unparse), or read back
+    /// to be validated.
+    #[error(transparent)]
+    Unparse(#[from] Error),
+}
+
+impl From<BuildError> for Error {
+    fn from(error: BuildError) -> Error {
+        match error {
+            BuildError::Violations(violations) => Error::Violations(violations),
+            BuildError::Unparse(error) => error,
+        }
+    }
+}
+
+/// Builds a [Radiotap](struct.Radiotap.html) capture field by field.
+///
+/// ```
+/// use radiotap::{field::*, RadiotapBuilder};
+///
+/// let radiotap = RadiotapBuilder::new()
+///     .rate(Rate::from_mbps(6.0).unwrap())
+///     .tx_power(TxPower { value: 10 })
+///     .done();
+///
+/// let mut bytes = Vec::new();
+/// radiotap.unparse(&mut bytes).unwrap();
+/// assert_eq!(bytes, [0, 0, 10, 0, 4, 4, 0, 0, 12, 10]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RadiotapBuilder {
+    radiotap: Radiotap,
+}
+
+impl RadiotapBuilder {
+    /// Returns a new builder with no fields set.
+    pub fn new() -> RadiotapBuilder {
+        RadiotapBuilder::default()
+    }
+
+    /// Returns a builder starting from the fields of an existing capture, see
+    /// [Radiotap::to_builder](struct.Radiotap.html#method.to_builder).
+    ///
+    /// The [raw](struct.Radiotap.html#structfield.raw) bytes of the fields are
+    /// dropped, they would not reflect the changes.
+    pub fn from_radiotap(mut radiotap: Radiotap) -> RadiotapBuilder {
+        radiotap.raw.clear();
+        RadiotapBuilder { radiotap }
+    }
+
+    /// Sets the [TSFT](field/struct.TSFT.html) field.
+    pub fn tsft(mut self, tsft: TSFT) -> RadiotapBuilder {
+        self.radiotap.tsft = Some(tsft);
+        self
+    }
+
+    /// Sets the [Flags](field/struct.Flags.html) field.
+    pub fn flags(mut self, flags: Flags) -> RadiotapBuilder {
+        self.radiotap.flags = Some(flags);
+        self
+    }
+
+    /// Sets the [Rate](field/struct.Rate.html) field.
+    pub fn rate(mut self, rate: Rate) -> RadiotapBuilder {
+        self.radiotap.rate = Some(rate);
+        self
+    }
+
+    /// Sets the [Channel](field/struct.Channel.html) field.
+    pub fn channel(mut self, channel: Channel) -> RadiotapBuilder {
+        self.radiotap.channel = Some(channel);
+        self
+    }
+
+    /// Sets the [FHSS](field/struct.FHSS.html) field.
+    pub fn fhss(mut self, fhss: FHSS) -> RadiotapBuilder {
+        self.radiotap.fhss = Some(fhss);
+        self
+    }
+
+    /// Sets the [AntennaSignal](field/struct.AntennaSignal.html) field.
+    pub fn antenna_signal(mut self, antenna_signal: AntennaSignal) -> RadiotapBuilder {
+        self.radiotap.antenna_signal = Some(antenna_signal);
+        self
+    }
+
+    /// Sets the [AntennaNoise](field/struct.AntennaNoise.html) field.
+    pub fn antenna_noise(mut self, antenna_noise: AntennaNoise) -> RadiotapBuilder {
+        self.radiotap.antenna_noise = Some(antenna_noise);
+        self
+    }
+
+    /// Sets the [LockQuality](field/struct.LockQuality.html) field.
+    pub fn lock_quality(mut self, lock_quality: LockQuality) -> RadiotapBuilder {
+        self.radiotap.lock_quality = Some(lock_quality);
+        self
+    }
+
+    /// Sets the [TxAttenuation](field/struct.TxAttenuation.html) field.
+    pub fn tx_attenuation(mut self, tx_attenuation: TxAttenuation) -> RadiotapBuilder {
+        self.radiotap.tx_attenuation = Some(tx_attenuation);
+        self
+    }
+
+    /// Sets the [TxAttenuationDb](field/struct.TxAttenuationDb.html) field.
+    pub fn tx_attenuation_db(mut self, tx_attenuation_db: TxAttenuationDb) -> RadiotapBuilder {
+        self.radiotap.tx_attenuation_db = Some(tx_attenuation_db);
+        self
+    }
+
+    /// Sets the [TxPower](field/struct.TxPower.html) field.
+    pub fn tx_power(mut self, tx_power: TxPower) -> RadiotapBuilder {
+        self.radiotap.tx_power = Some(tx_power);
+        self
+    }
+
+    /// Sets the [Antenna](field/struct.Antenna.html) field.
+    pub fn antenna(mut self, antenna: Antenna) -> RadiotapBuilder {
+        self.radiotap.antenna = Some(antenna);
+        self
+    }
+
+    /// Sets the [AntennaSignalDb](field/struct.AntennaSignalDb.html) field.
+    pub fn antenna_signal_db(mut self, antenna_signal_db: AntennaSignalDb) -> RadiotapBuilder {
+        self.radiotap.antenna_signal_db = Some(antenna_signal_db);
+        self
+    }
+
+    /// Sets the [AntennaNoiseDb](field/struct.AntennaNoiseDb.html) field.
+    pub fn antenna_noise_db(mut self, antenna_noise_db: AntennaNoiseDb) -> RadiotapBuilder {
+        self.radiotap.antenna_noise_db = Some(antenna_noise_db);
+        self
+    }
+
+    /// Sets the [RxFlags](field/struct.RxFlags.html) field.
+    pub fn rx_flags(mut self, rx_flags: RxFlags) -> RadiotapBuilder {
+        self.radiotap.rx_flags = Some(rx_flags);
+        self
+    }
+
+    /// Sets the [TxFlags](field/struct.TxFlags.html) field.
+    pub fn tx_flags(mut self, tx_flags: TxFlags) -> RadiotapBuilder {
+        self.radiotap.tx_flags = Some(tx_flags);
+        self
+    }
+
+    /// Sets the [RTSRetries](field/struct.RTSRetries.html) field.
+    pub fn rts_retries(mut self, rts_retries: RTSRetries) -> RadiotapBuilder {
+        self.radiotap.rts_retries = Some(rts_retries);
+        self
+    }
+
+    /// Sets the [DataRetries](field/struct.DataRetries.html) field.
+    pub fn data_retries(mut self, data_retries: DataRetries) -> RadiotapBuilder {
+        self.radiotap.data_retries = Some(data_retries);
+        self
+    }
+
+    /// Sets the [XChannel](field/struct.XChannel.html) field.
+    pub fn xchannel(mut self, xchannel: XChannel) -> RadiotapBuilder {
+        self.radiotap.xchannel = Some(xchannel);
+        self
+    }
+
+    /// Sets the [MCS](field/struct.MCS.html) field.
+    pub fn mcs(mut self, mcs: MCS) -> RadiotapBuilder {
+        self.radiotap.mcs = Some(mcs);
+        self
+    }
+
+    /// Sets the [AMPDUStatus](field/struct.AMPDUStatus.html) field.
+    pub fn ampdu_status(mut self, ampdu_status: AMPDUStatus) -> RadiotapBuilder {
+        self.radiotap.ampdu_status = Some(ampdu_status);
+        self
+    }
+
+    /// Sets the [VHT](field/struct.VHT.html) field.
+    pub fn vht(mut self, vht: VHT) -> RadiotapBuilder {
+        self.radiotap.vht = Some(vht);
+        self
+    }
+
+    /// Sets the [Timestamp](field/struct.Timestamp.html) field.
+    pub fn timestamp(mut self, timestamp: Timestamp) -> RadiotapBuilder {
+        self.radiotap.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Sets the [HE](field/struct.HE.html) field.
+    pub fn he(mut self, he: HE) -> RadiotapBuilder {
+        self.radiotap.he = Some(he);
+        self
+    }
+
+    /// Sets the [HEMU](field/struct.HEMU.html) field.
+    pub fn he_mu(mut self, he_mu: HEMU) -> RadiotapBuilder {
+        self.radiotap.he_mu = Some(he_mu);
+        self
+    }
+
+    /// Sets the [HEMUOtherUser](field/struct.HEMUOtherUser.html) field.
+    pub fn he_mu_other_user(mut self, he_mu_other_user: HEMUOtherUser) -> RadiotapBuilder {
+        self.radiotap.he_mu_other_user = Some(he_mu_other_user);
+        self
+    }
+
+    /// Sets the [LSig](field/struct.LSig.html) field.
+    pub fn l_sig(mut self, l_sig: LSig) -> RadiotapBuilder {
+        self.radiotap.l_sig = Some(l_sig);
+        self
+    }
+
+    /// Sets the [USig](field/struct.USig.html) field.
+    pub fn u_sig(mut self, u_sig: USig) -> RadiotapBuilder {
+        self.radiotap.u_sig = Some(u_sig);
+        self
+    }
+
+    /// Sets the [EHT](field/struct.EHT.html) field.
+    pub fn eht(mut self, eht: EHT) -> RadiotapBuilder {
+        self.radiotap.eht = Some(eht);
+        self
+    }
+
+    /// Adds a vendor namespace with the given OUI, sub namespace, and data.
+    /// Vendor namespaces are written in the order they are added.
+    pub fn vendor_namespace(mut self, oui: Oui, sub_namespace: u8, data: &[u8]) -> RadiotapBuilder {
+        self.radiotap.vendor_namespaces.push(VendorData {
+            oui,
+            sub_namespace,
+            present: Vec::new(),
+            data: data.to_vec(),
+        });
+        self
+    }
+
+    /// Returns the built [Radiotap](struct.Radiotap.html), with a header
+    /// describing the fields that were set.
+    pub fn done(self) -> Radiotap {
+        let mut radiotap = self.radiotap;
+        radiotap.header = radiotap.unparsed_header();
+        radiotap
+    }
+
+    /// Returns the built [Radiotap](struct.Radiotap.html) like
+    /// [done](#method.done), after checking that it follows the
+    /// specification and that its fields do not contradict each other.
+    ///
+    /// Kernels reject injected frames whose header is not consistent, for
+    /// example with both the MCS and VHT fields, a VHT field without users, or
+    /// channel flags for both the 2 GHz and 5 GHz bands. These are returned as
+    /// [BuildError::Violations](enum.BuildError.html#variant.Violations), see
+    /// [validate](validate/fn.validate.html).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::{field::*, validate::Violation, BuildError, RadiotapBuilder};
+    ///
+    /// let builder = RadiotapBuilder::new().mcs(MCS::default()).vht(VHT::default());
+    /// match builder.try_done() {
+    ///     Err(BuildError::Violations(violations)) => assert_eq!(
+    ///         violations[0],
+    ///         Violation::InvalidField {
+    ///             kind: Kind::VHT,
+    ///             reason: "set together with the MCS field".to_string(),
+    ///         }
+    ///     ),
+    ///     result => panic!("not rejected: {:?}", result),
+    /// }
+    /// ```
+    pub fn try_done(self) -> Result<Radiotap, BuildError> {
+        let radiotap = self.done();
+        let mut violations = validate::validate(&radiotap.to_bytes()?)?;
+        let mut conflicts = validate::conflicts(&radiotap)?;
+        violations
+            .try_reserve(conflicts.len())
+            .map_err(Error::from)?;
+        violations.append(&mut conflicts);
+        if violations.is_empty() {
+            Ok(radiotap)
+        } else {
+            Err(BuildError::Violations(violations))
+        }
+    }
+}
+
+impl From<Radiotap> for RadiotapBuilder {
+    fn from(radiotap: Radiotap) -> RadiotapBuilder {
+        RadiotapBuilder::from_radiotap(radiotap)
+    }
+}
@@ -0,0 +1,154 @@
+//! Caching of field offsets across captures with the same header.
+//!
+//! In a live capture, consecutive frames from the same interface usually share
+//! the same present bitmap and length, so the fields are at the same offsets.
+
+use alloc::vec::Vec;
+
+use crate::{
+    field::{Header, Kind},
+    Radiotap, RadiotapIterator, RadiotapParser, Result,
+};
+
+/// The size of the header of a capture with a single present word.
+const KEY_SIZE: usize = 8;
+
+/// Memoizes the offsets of the fields of the last capture, keyed by its
+/// version, length, and present word, so that the following captures with the
+/// same header are decoded without walking the present bitmap.
+///
+/// Only captures with a single present word and no TLV records are cached,
+/// others are parsed like [Radiotap::parse](../struct.Radiotap.html#method.parse).
+///
+/// # Examples
+///
+/// ```
+/// use radiotap::cache::HeaderCache;
+///
+/// let mut cache = HeaderCache::new();
+/// for capture in [[0, 0, 10, 0, 0x24, 0, 0, 0, 12, 0xd6], [0, 0, 10, 0, 0x24, 0, 0, 0, 22, 0xd0]].iter() {
+///     let (radiotap, _) = cache.parse(capture).unwrap();
+///     assert!(radiotap.rate.is_some());
+/// }
+/// assert_eq!(cache.hits(), 1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct HeaderCache {
+    key: Option<[u8; KEY_SIZE]>,
+    header: Header,
+    fields: Vec<(Kind, usize, usize)>,
+    hits: u64,
+}
+
+impl HeaderCache {
+    /// Returns an empty cache.
+    pub fn new() -> HeaderCache {
+        HeaderCache::default()
+    }
+
+    /// Returns the number of captures decoded from the cached offsets.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Returns the parsed [Radiotap](../struct.Radiotap.html) and the
+    /// remaining data from an input byte array.
+    pub fn parse<'a>(&mut self, input: &'a [u8]) -> Result<(Radiotap, &'a [u8])> {
+        let mut radiotap = Radiotap::default();
+        let rest = self.parse_into(&mut radiotap, input)?;
+        Ok((radiotap, rest))
+    }
+
+    /// Parses the input into an existing [Radiotap](../struct.Radiotap.html),
+    /// see [Radiotap::parse_into](../struct.Radiotap.html#method.parse_into),
+    /// returning the remaining data.
+    pub fn parse_into<'a>(&mut self, radiotap: &mut Radiotap, input: &'a [u8]) -> Result<&'a [u8]> {
+        let key = input.get(..KEY_SIZE);
+        if key.is_some() && key == self.key.as_ref().map(|key| &key[..]) {
+            if let Some(rest) = self.parse_cached(radiotap, input)? {
+                self.hits += 1;
+                return Ok(rest);
+            }
+        }
+
+        let (_, rest) = RadiotapParser::new().parse_into(radiotap, input)?;
+        self.update(input)?;
+        Ok(rest)
+    }
+
+    /// Decodes the fields at the cached offsets, or returns `None` if the
+    /// input is too short.
+    fn parse_cached<'a>(
+        &self,
+        radiotap: &mut Radiotap,
+        input: &'a [u8],
+    ) -> Result<Option<&'a [u8]>> {
+        let length = self.header.length;
+        if input.len() < length {
+            return Ok(None);
+        }
+        radiotap.clear();
+        for &(kind, start, end) in &self.fields {
+            radiotap.parse_field(kind, &input[start..end], &[], true)?;
+        }
+        radiotap.header = self.header.clone();
+        Ok(Some(&input[length..]))
+    }
+
+    /// Records the offsets of the fields of a successfully parsed capture, if
+    /// it can be cached.
+    fn update(&mut self, input: &[u8]) -> Result<()> {
+        self.key = None;
+        self.fields.clear();
+
+        let (iterator, _) = RadiotapIterator::parse(input)?;
+        if iterator.header.size != KEY_SIZE {
+            return Ok(());
+        }
+        for result in &iterator {
+            let (kind, data) = match result {
+                Ok(field) => field,
+                Err(_) => return Ok(()),
+            };
+            if kind.bit() >= 28 {
+                self.fields.clear();
+                return Ok(());
+            }
+            let start = data.as_ptr() as usize - input.as_ptr() as usize;
+            self.fields.try_reserve(1)?;
+            self.fields.push((kind, start, start + data.len()));
+        }
+
+        let mut key = [0; KEY_SIZE];
+        key.copy_from_slice(&input[..KEY_SIZE]);
+        self.key = Some(key);
+        self.header = iterator.header;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_parse() {
+        let frames: [&[u8]; 4] = [
+            &[0, 0, 10, 0, 0x24, 0, 0, 0, 12, 0xd6],
+            &[0, 0, 10, 0, 0x24, 0, 0, 0, 22, 0xd0, 1, 2],
+            // A different length is not a hit
+            &[0, 0, 11, 0, 0x24, 0, 0, 0, 22, 0xd0, 0],
+            // A TLV record is never cached
+            &[0, 0, 16, 0, 0, 0, 0, 0x10, 27, 0, 4, 0, 0x0b, 0, 0, 0],
+        ];
+
+        let mut cache = HeaderCache::new();
+        for frame in frames.iter().chain(frames.iter()) {
+            assert_eq!(cache.parse(frame).unwrap(), Radiotap::parse(frame).unwrap());
+        }
+        assert_eq!(cache.hits(), 2);
+
+        // A truncated capture matching the key still fails
+        assert!(cache.parse(&[0, 0, 10, 0, 0x24, 0, 0, 0, 12]).is_err());
+    }
+}
@@ -0,0 +1,629 @@
+//! Loading of Radiotap header specifications from TOML, YAML, or JSON.
+//!
+//! Enabled with the `config` feature. A specification lists the fields to set
+//! and their values, for example:
+//!
+//! ```toml
+//! flags = ["preamble", "fcs"]
+//! rate = 6.0
+//! tx_power = 10
+//!
+//! [channel]
+//! freq = 2412
+//! flags = ["ofdm", "ghz2"]
+//!
+//! [mcs]
+//! bw = 1
+//! index = 7
+//! gi = "short"
+//! ```
+//!
+//! Bandwidths are given using their Radiotap encoding, see
+//! [Bandwidth::new](../field/ext/struct.Bandwidth.html#method.new).
+//!
+//! The same specification can be written in YAML or JSON, for example
+//! `{"rate": 6.0, "channel": {"freq": 2412, "flags": ["ofdm", "ghz2"]}}`.
+//!
+//! The [Channel](../field/struct.Channel.html) field only has the flags up to
+//! `gfsk`, the other flags are only accepted for the
+//! [XChannel](../field/struct.XChannel.html) field.
+
+use std::{fs, path::Path};
+
+use serde::Deserialize;
+
+use crate::{
+    field::{ext::*, *},
+    Error, RadiotapBuilder, Result,
+};
+
+/// Returns a [RadiotapBuilder](../struct.RadiotapBuilder.html) with the fields
+/// described in the given TOML specification set.
+pub fn from_str(spec: &str) -> Result<RadiotapBuilder> {
+    let spec: Spec = toml::from_str(spec).map_err(|e| Error::InvalidConfig(e.to_string()))?;
+    spec.builder()
+}
+
+/// Returns a [RadiotapBuilder](../struct.RadiotapBuilder.html) with the fields
+/// described in the given YAML specification set.
+pub fn from_yaml_str(spec: &str) -> Result<RadiotapBuilder> {
+    let spec: Spec = serde_yaml::from_str(spec).map_err(|e| Error::InvalidConfig(e.to_string()))?;
+    spec.builder()
+}
+
+/// Returns a [RadiotapBuilder](../struct.RadiotapBuilder.html) with the fields
+/// described in the given JSON specification set.
+pub fn from_json_str(spec: &str) -> Result<RadiotapBuilder> {
+    let spec: Spec = serde_json::from_str(spec).map_err(|e| Error::InvalidConfig(e.to_string()))?;
+    spec.builder()
+}
+
+/// Returns a [RadiotapBuilder](../struct.RadiotapBuilder.html) with the fields
+/// described in the specification at the given path set. The specification
+/// is JSON if the path has a `.json` extension, YAML if it has a `.yaml` or
+/// `.yml` extension, and TOML otherwise.
+pub fn from_file<P: AsRef<Path>>(path: P) -> Result<RadiotapBuilder> {
+    let path = path.as_ref();
+    let spec = fs::read_to_string(path)?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => from_json_str(&spec),
+        Some("yaml") | Some("yml") => from_yaml_str(&spec),
+        _ => from_str(&spec),
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Spec {
+    tsft: Option<u64>,
+    flags: Option<Vec<FlagSpec>>,
+    rate: Option<f32>,
+    channel: Option<ChannelSpec>,
+    fhss: Option<FHSSSpec>,
+    antenna_signal: Option<i8>,
+    antenna_noise: Option<i8>,
+    lock_quality: Option<u16>,
+    tx_attenuation: Option<u16>,
+    tx_attenuation_db: Option<u16>,
+    tx_power: Option<i8>,
+    antenna: Option<u8>,
+    antenna_signal_db: Option<u8>,
+    antenna_noise_db: Option<u8>,
+    rx_flags: Option<Vec<RxFlagSpec>>,
+    tx_flags: Option<Vec<TxFlagSpec>>,
+    rts_retries: Option<u8>,
+    data_retries: Option<u8>,
+    xchannel: Option<XChannelSpec>,
+    mcs: Option<MCSSpec>,
+    ampdu_status: Option<AMPDUStatusSpec>,
+    vht: Option<VHTSpec>,
+    timestamp: Option<TimestampSpec>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum FlagSpec {
+    Cfp,
+    Preamble,
+    Wep,
+    Fragmentation,
+    Fcs,
+    DataPad,
+    BadFcs,
+    Sgi,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum RxFlagSpec {
+    BadPlcp,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum TxFlagSpec {
+    Fail,
+    Cts,
+    Rts,
+    NoAck,
+    NoSeq,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum ChannelFlagSpec {
+    Turbo,
+    Cck,
+    Ofdm,
+    Ghz2,
+    Ghz5,
+    Passive,
+    Dynamic,
+    Gfsk,
+    Gsm,
+    Sturbo,
+    Half,
+    Quarter,
+    Ht20,
+    Ht40u,
+    Ht40d,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ChannelSpec {
+    freq: u16,
+    #[serde(default)]
+    flags: Vec<ChannelFlagSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FHSSSpec {
+    hopset: u8,
+    pattern: u8,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct XChannelSpec {
+    freq: u16,
+    channel: u8,
+    max_power: u8,
+    #[serde(default)]
+    flags: Vec<ChannelFlagSpec>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum GuardIntervalSpec {
+    Long,
+    Short,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum FECSpec {
+    Bcc,
+    Ldpc,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum HTFormatSpec {
+    Mixed,
+    Greenfield,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MCSSpec {
+    bw: Option<u8>,
+    index: Option<u8>,
+    gi: Option<GuardIntervalSpec>,
+    format: Option<HTFormatSpec>,
+    fec: Option<FECSpec>,
+    stbc: Option<u8>,
+    ness: Option<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AMPDUStatusSpec {
+    reference: u32,
+    zero_length: Option<bool>,
+    last: Option<bool>,
+    delimiter_crc: Option<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct VHTUserSpec {
+    index: u8,
+    nss: u8,
+    #[serde(default = "default_fec")]
+    fec: FECSpec,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct VHTSpec {
+    stbc: Option<bool>,
+    txop_ps: Option<bool>,
+    gi: Option<GuardIntervalSpec>,
+    sgi_nsym_da: Option<bool>,
+    ldpc_extra: Option<bool>,
+    beamformed: Option<bool>,
+    bw: Option<u8>,
+    group_id: Option<u8>,
+    partial_aid: Option<u16>,
+    #[serde(default)]
+    users: Vec<VHTUserSpec>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum TimeUnitSpec {
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum SamplingPositionSpec {
+    StartMpdu,
+    StartPlcp,
+    EndPpdu,
+    EndMpdu,
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TimestampSpec {
+    timestamp: u64,
+    unit: TimeUnitSpec,
+    position: SamplingPositionSpec,
+    accuracy: Option<u16>,
+}
+
+fn default_fec() -> FECSpec {
+    FECSpec::Bcc
+}
+
+impl From<GuardIntervalSpec> for GuardInterval {
+    fn from(gi: GuardIntervalSpec) -> GuardInterval {
+        match gi {
+            GuardIntervalSpec::Long => GuardInterval::Long,
+            GuardIntervalSpec::Short => GuardInterval::Short,
+        }
+    }
+}
+
+impl From<FECSpec> for FEC {
+    fn from(fec: FECSpec) -> FEC {
+        match fec {
+            FECSpec::Bcc => FEC::BCC,
+            FECSpec::Ldpc => FEC::LDPC,
+        }
+    }
+}
+
+impl ChannelSpec {
+    fn channel(&self) -> Result<Channel> {
+        // The extended flags have no bit in the channel field
+        if let Some(flag) = self.flags.iter().find(|&flag| {
+            matches!(
+                flag,
+                ChannelFlagSpec::Gsm
+                    | ChannelFlagSpec::Sturbo
+                    | ChannelFlagSpec::Half
+                    | ChannelFlagSpec::Quarter
+                    | ChannelFlagSpec::Ht20
+                    | ChannelFlagSpec::Ht40u
+                    | ChannelFlagSpec::Ht40d
+            )
+        }) {
+            return Err(Error::InvalidConfig(format!(
+                "channel flag {:?} is only supported by xchannel",
+                flag
+            )));
+        }
+        let has = |flag| self.flags.contains(&flag);
+        Ok(Channel {
+            freq: self.freq,
+            flags: ChannelFlags {
+                turbo: has(ChannelFlagSpec::Turbo),
+                cck: has(ChannelFlagSpec::Cck),
+                ofdm: has(ChannelFlagSpec::Ofdm),
+                ghz2: has(ChannelFlagSpec::Ghz2),
+                ghz5: has(ChannelFlagSpec::Ghz5),
+                passive: has(ChannelFlagSpec::Passive),
+                dynamic: has(ChannelFlagSpec::Dynamic),
+                gfsk: has(ChannelFlagSpec::Gfsk),
+            },
+        })
+    }
+}
+
+impl XChannelSpec {
+    fn xchannel(&self) -> XChannel {
+        let has = |flag| self.flags.contains(&flag);
+        XChannel {
+            flags: XChannelFlags {
+                turbo: has(ChannelFlagSpec::Turbo),
+                cck: has(ChannelFlagSpec::Cck),
+                ofdm: has(ChannelFlagSpec::Ofdm),
+                ghz2: has(ChannelFlagSpec::Ghz2),
+                ghz5: has(ChannelFlagSpec::Ghz5),
+                passive: has(ChannelFlagSpec::Passive),
+                dynamic: has(ChannelFlagSpec::Dynamic),
+                gfsk: has(ChannelFlagSpec::Gfsk),
+                gsm: has(ChannelFlagSpec::Gsm),
+                sturbo: has(ChannelFlagSpec::Sturbo),
+                half: has(ChannelFlagSpec::Half),
+                quarter: has(ChannelFlagSpec::Quarter),
+                ht20: has(ChannelFlagSpec::Ht20),
+                ht40u: has(ChannelFlagSpec::Ht40u),
+                ht40d: has(ChannelFlagSpec::Ht40d),
+            },
+            freq: self.freq,
+            channel: self.channel,
+            max_power: self.max_power,
+        }
+    }
+}
+
+impl MCSSpec {
+    fn mcs(&self) -> Result<MCS> {
+        let bw = self.bw.map(Bandwidth::new).transpose()?;
+        let gi = self.gi.map(GuardInterval::from);
+        let datarate = match (self.index, bw, gi) {
+            (Some(index), Some(bw), Some(gi)) => Some(ht_rate(index, bw, gi)?),
+            _ => None,
+        };
+        Ok(MCS {
+            bw,
+            index: self.index,
+            gi,
+            format: self.format.map(|format| match format {
+                HTFormatSpec::Mixed => HTFormat::Mixed,
+                HTFormatSpec::Greenfield => HTFormat::Greenfield,
+            }),
+            fec: self.fec.map(FEC::from),
+            stbc: self.stbc,
+            ness: self.ness,
+            datarate,
+        })
+    }
+}
+
+impl VHTSpec {
+    fn vht(&self) -> Result<VHT> {
+        if self.users.len() > 4 {
+            return Err(Error::InvalidConfig("at most 4 VHT users".to_string()));
+        }
+
+        let bw = self.bw.map(Bandwidth::new).transpose()?;
+        let gi = self.gi.map(GuardInterval::from);
+        let mut users = [None; 4];
+        for (slot, user) in users.iter_mut().zip(&self.users) {
+            let datarate = match (bw, gi) {
+                (Some(bw), Some(gi)) => Some(vht_rate(user.index, bw, gi, user.nss)?),
+                _ => None,
+            };
+            *slot = Some(VHTUser {
+                index: user.index,
+                fec: user.fec.into(),
+                nss: user.nss,
+                nsts: user.nss << u8::from(self.stbc == Some(true)),
+                datarate,
+            });
+        }
+
+        Ok(VHT {
+            stbc: self.stbc,
+            txop_ps: self.txop_ps,
+            gi,
+            sgi_nsym_da: self.sgi_nsym_da,
+            ldpc_extra: self.ldpc_extra,
+            beamformed: self.beamformed,
+            bw,
+            group_id: self.group_id,
+            partial_aid: self.partial_aid,
+            users,
+        })
+    }
+}
+
+impl TimestampSpec {
+    fn timestamp(&self) -> Timestamp {
+        Timestamp {
+            timestamp: self.timestamp,
+            unit: match self.unit {
+                TimeUnitSpec::Milliseconds => TimeUnit::Milliseconds,
+                TimeUnitSpec::Microseconds => TimeUnit::Microseconds,
+                TimeUnitSpec::Nanoseconds => TimeUnit::Nanoseconds,
+            },
+            position: match self.position {
+                SamplingPositionSpec::StartMpdu => SamplingPosition::StartMPDU,
+                SamplingPositionSpec::StartPlcp => SamplingPosition::StartPLCP,
+                SamplingPositionSpec::EndPpdu => SamplingPosition::EndPPDU,
+                SamplingPositionSpec::EndMpdu => SamplingPosition::EndMPDU,
+                SamplingPositionSpec::Unknown => SamplingPosition::Unknown,
+            },
+            accuracy: self.accuracy,
+        }
+    }
+}
+
+impl Spec {
+    fn builder(&self) -> Result<RadiotapBuilder> {
+        let mut builder = RadiotapBuilder::new();
+
+        if let Some(value) = self.tsft {
+            builder = builder.tsft(TSFT { value });
+        }
+        if let Some(flags) = &self.flags {
+            let has = |flag| flags.contains(&flag);
+            builder = builder.flags(Flags {
+                cfp: has(FlagSpec::Cfp),
+                preamble: has(FlagSpec::Preamble),
+                wep: has(FlagSpec::Wep),
+                fragmentation: has(FlagSpec::Fragmentation),
+                fcs: has(FlagSpec::Fcs),
+                data_pad: has(FlagSpec::DataPad),
+                bad_fcs: has(FlagSpec::BadFcs),
+                sgi: has(FlagSpec::Sgi),
+            });
+        }
+        if let Some(value) = self.rate {
+            builder = builder.rate(
+                Rate::from_mbps(value)
+                    .map_err(|_| Error::InvalidConfig(format!("invalid rate {}", value)))?,
+            );
+        }
+        if let Some(channel) = &self.channel {
+            builder = builder.channel(channel.channel()?);
+        }
+        if let Some(fhss) = &self.fhss {
+            builder = builder.fhss(FHSS {
+                hopset: fhss.hopset,
+                pattern: fhss.pattern,
+            });
+        }
+        if let Some(value) = self.antenna_signal {
+            builder = builder.antenna_signal(AntennaSignal { value });
+        }
+        if let Some(value) = self.antenna_noise {
+            builder = builder.antenna_noise(AntennaNoise { value });
+        }
+        if let Some(value) = self.lock_quality {
+            builder = builder.lock_quality(LockQuality { value });
+        }
+        if let Some(value) = self.tx_attenuation {
+            builder = builder.tx_attenuation(TxAttenuation { value });
+        }
+        if let Some(value) = self.tx_attenuation_db {
+            builder = builder.tx_attenuation_db(TxAttenuationDb { value });
+        }
+        if let Some(value) = self.tx_power {
+            builder = builder.tx_power(TxPower { value });
+        }
+        if let Some(value) = self.antenna {
+            builder = builder.antenna(Antenna { value });
+        }
+        if let Some(value) = self.antenna_signal_db {
+            builder = builder.antenna_signal_db(AntennaSignalDb { value });
+        }
+        if let Some(value) = self.antenna_noise_db {
+            builder = builder.antenna_noise_db(AntennaNoiseDb { value });
+        }
+        if let Some(flags) = &self.rx_flags {
+            builder = builder.rx_flags(RxFlags {
+                bad_plcp: flags.contains(&RxFlagSpec::BadPlcp),
+            });
+        }
+        if let Some(flags) = &self.tx_flags {
+            let has = |flag| flags.contains(&flag);
+            builder = builder.tx_flags(TxFlags {
+                fail: has(TxFlagSpec::Fail),
+                cts: has(TxFlagSpec::Cts),
+                rts: has(TxFlagSpec::Rts),
+                no_ack: has(TxFlagSpec::NoAck),
+                no_seq: has(TxFlagSpec::NoSeq),
+            });
+        }
+        if let Some(value) = self.rts_retries {
+            builder = builder.rts_retries(RTSRetries { value });
+        }
+        if let Some(value) = self.data_retries {
+            builder = builder.data_retries(DataRetries { value });
+        }
+        if let Some(xchannel) = &self.xchannel {
+            builder = builder.xchannel(xchannel.xchannel());
+        }
+        if let Some(mcs) = &self.mcs {
+            builder = builder.mcs(mcs.mcs()?);
+        }
+        if let Some(ampdu) = &self.ampdu_status {
+            builder = builder.ampdu_status(AMPDUStatus {
+                reference: ampdu.reference,
+                zero_length: ampdu.zero_length,
+                last: ampdu.last,
+                delimiter_crc: ampdu.delimiter_crc,
+            });
+        }
+        if let Some(vht) = &self.vht {
+            builder = builder.vht(vht.vht()?);
+        }
+        if let Some(timestamp) = &self.timestamp {
+            builder = builder.timestamp(timestamp.timestamp());
+        }
+
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Radiotap;
+
+    #[test]
+    fn load() {
+        let radiotap = from_str(
+            r#"
+            flags = ["preamble", "fcs"]
+            rate = 6.0
+            tx_power = 10
+            tx_flags = ["no_ack"]
+
+            [channel]
+            freq = 2412
+            flags = ["ofdm", "ghz2"]
+
+            [vht]
+            bw = 4
+            gi = "short"
+            users = [{ index = 9, nss = 2, fec = "ldpc" }]
+            "#,
+        )
+        .unwrap()
+        .done();
+
+        let mut bytes = Vec::new();
+        radiotap.unparse(&mut bytes).unwrap();
+        assert_eq!(Radiotap::from_bytes(&bytes).unwrap(), radiotap);
+        assert_eq!(radiotap.channel.unwrap().freq, 2412);
+        assert!(radiotap.tx_flags.unwrap().no_ack);
+        assert_eq!(
+            radiotap.vht.unwrap().users[0].unwrap().datarate,
+            Some(866.7)
+        );
+    }
+
+    #[test]
+    fn json() {
+        let spec = r#"{
+            "flags": ["fcs"],
+            "channel": {"freq": 5180, "flags": ["ofdm", "ghz5"]},
+            "mcs": {"bw": 0, "index": 7, "gi": "short"}
+        }"#;
+        let radiotap = from_json_str(spec).unwrap().done();
+        assert!(radiotap.flags.unwrap().fcs);
+        assert_eq!(radiotap.channel.unwrap().freq, 5180);
+        assert_eq!(radiotap.mcs.unwrap().index, Some(7));
+        assert!(from_json_str(r#"{"unknown": 1}"#).is_err());
+    }
+
+    #[test]
+    fn yaml() {
+        let spec = "
+            flags: [fcs]
+            channel:
+              freq: 5180
+              flags: [ofdm, ghz5]
+            mcs: {bw: 0, index: 7, gi: short}
+        ";
+        let radiotap = from_yaml_str(spec).unwrap().done();
+        assert!(radiotap.flags.unwrap().fcs);
+        assert_eq!(radiotap.channel.unwrap().freq, 5180);
+        assert_eq!(radiotap.mcs.unwrap().index, Some(7));
+        assert!(from_yaml_str("unknown: 1").is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        match from_str("unknown = 1").unwrap_err() {
+            Error::InvalidConfig(_) => {}
+            e => panic!("Error not InvalidConfig: {:?}", e),
+        }
+        // The channel field cannot represent the extended flags
+        match from_str("channel = { freq = 2412, flags = [\"gsm\"] }").unwrap_err() {
+            Error::InvalidConfig(reason) => assert!(reason.contains("xchannel")),
+            e => panic!("Error not InvalidConfig: {:?}", e),
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! A push-based Radiotap decoder that performs no IO.
+//!
+//! A [Decoder](struct.Decoder.html) is fed bytes as they become available, in
+//! chunks of any size, and returns the events they complete. It never blocks
+//! and owns no transport, so it can be driven from any event loop.
+
+use alloc::{boxed::Box, vec::Vec};
+use core::mem;
+
+use crate::{Error, Radiotap, Result};
+
+/// An event produced by a [Decoder](struct.Decoder.html).
+#[derive(Debug)]
+pub enum Event {
+    /// The Radiotap header of the capture was decoded.
+    Radiotap(Box<Radiotap>),
+    /// Bytes following the Radiotap header, usually part of an 802.11 frame.
+    Payload(Vec<u8>),
+    /// The Radiotap header could not be decoded, any further input is ignored
+    /// until the decoder is [reset](struct.Decoder.html#method.reset).
+    Error(Error),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Buffering the Radiotap header.
+    Header,
+    /// The header has been decoded, passing through the payload.
+    Payload,
+    /// The header could not be decoded.
+    Failed,
+}
+
+/// Incrementally decodes a single Radiotap capture.
+///
+/// # Examples
+///
+/// ```
+/// use radiotap::decoder::{Decoder, Event};
+///
+/// let mut decoder = Decoder::new();
+/// assert!(decoder.feed(&[0, 0, 9, 0]).is_empty());
+/// assert_eq!(decoder.needed(), 5);
+///
+/// let events = decoder.feed(&[4, 0, 0, 0, 12, 0x80, 0]);
+/// match &events[..] {
+///     [Event::Radiotap(radiotap), Event::Payload(payload)] => {
+///         assert_eq!(radiotap.rate.unwrap().as_mbps(), 6.0);
+///         assert_eq!(payload, &[0x80, 0]);
+///     }
+///     events => panic!("unexpected events: {:?}", events),
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Decoder {
+    state: State,
+    buffer: Vec<u8>,
+}
+
+impl Default for Decoder {
+    fn default() -> Decoder {
+        Decoder::new()
+    }
+}
+
+impl Decoder {
+    /// Returns a new decoder waiting for the start of a capture.
+    pub fn new() -> Decoder {
+        Decoder {
+            state: State::Header,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Resets the decoder so that the next input is treated as the start of a
+    /// new capture. Any buffered input is discarded.
+    pub fn reset(&mut self) {
+        self.state = State::Header;
+        self.buffer.clear();
+    }
+
+    /// Returns the minimum number of bytes still needed before the Radiotap
+    /// header can be decoded, or 0 if it has already been decoded.
+    pub fn needed(&self) -> usize {
+        match self.state {
+            State::Header => self.header_length().unwrap_or(4) - self.buffer.len(),
+            State::Payload | State::Failed => 0,
+        }
+    }
+
+    /// Feeds the next chunk of the capture to the decoder, returning the events
+    /// it completed.
+    pub fn feed(&mut self, input: &[u8]) -> Vec<Event> {
+        let mut events = Vec::new();
+        match self.state {
+            State::Header => {
+                if let Err(e) = self.buffer.try_reserve(input.len()) {
+                    self.state = State::Failed;
+                    events.push(Event::Error(e.into()));
+                    return events;
+                }
+                self.buffer.extend_from_slice(input);
+
+                let length = match self.header_length() {
+                    Some(length) if length <= self.buffer.len() => length,
+                    _ => return events,
+                };
+
+                let mut buffer = mem::take(&mut self.buffer);
+                match Radiotap::from_bytes(&buffer[..length]) {
+                    Ok(radiotap) => {
+                        self.state = State::Payload;
+                        events.push(Event::Radiotap(Box::new(radiotap)));
+                        if buffer.len() > length {
+                            events.push(Event::Payload(buffer.split_off(length)));
+                        }
+                    }
+                    Err(e) => {
+                        self.state = State::Failed;
+                        events.push(Event::Error(e));
+                    }
+                }
+            }
+            State::Payload if !input.is_empty() => events.push(Event::Payload(input.to_vec())),
+            State::Payload | State::Failed => {}
+        }
+        events
+    }
+
+    /// Feeds a complete capture to a new decoder, returning the Radiotap header
+    /// and the payload.
+    pub fn decode(input: &[u8]) -> Result<(Radiotap, Vec<u8>)> {
+        let mut radiotap = None;
+        let mut payload = Vec::new();
+        for event in Decoder::new().feed(input) {
+            match event {
+                Event::Radiotap(r) => radiotap = Some(*r),
+                Event::Payload(p) => payload = p,
+                Event::Error(e) => return Err(e),
+            }
+        }
+        Ok((radiotap.ok_or(Error::IncompleteError)?, payload))
+    }
+
+    /// Returns the length of the Radiotap header if enough of it is buffered.
+    fn header_length(&self) -> Option<usize> {
+        let bytes = self.buffer.get(2..4)?;
+        // Never less than the fixed part of the header, so the decoder always
+        // makes progress
+        Some(usize::from(u16::from_le_bytes([bytes[0], bytes[1]])).max(8))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drip_feed() {
+        let capture = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0, 0x88, 0x41,
+        ];
+
+        let mut decoder = Decoder::new();
+        let mut events = Vec::new();
+        for byte in capture.iter() {
+            events.extend(decoder.feed(&[*byte]));
+        }
+
+        assert_eq!(events.len(), 3);
+        match &events[0] {
+            Event::Radiotap(radiotap) => {
+                assert_eq!(**radiotap, Radiotap::from_bytes(&capture).unwrap())
+            }
+            e => panic!("Event not Radiotap: {:?}", e),
+        }
+        match &events[1..] {
+            [Event::Payload(a), Event::Payload(b)] => assert_eq!([a[0], b[0]], [0x88, 0x41]),
+            e => panic!("Events not Payload: {:?}", e),
+        }
+
+        let (radiotap, payload) = Decoder::decode(&capture).unwrap();
+        assert_eq!(radiotap.header.length, 56);
+        assert_eq!(payload, [0x88, 0x41]);
+    }
+
+    #[test]
+    fn failed() {
+        let mut decoder = Decoder::new();
+        match &decoder.feed(&[1, 0, 8, 0, 0, 0, 0, 0])[..] {
+            [Event::Error(Error::UnsupportedVersion)] => {}
+            e => panic!("Events not UnsupportedVersion: {:?}", e),
+        }
+        assert!(decoder.feed(&[0, 0]).is_empty());
+
+        decoder.reset();
+        assert_eq!(decoder.feed(&[0, 0, 8, 0, 0, 0, 0, 0]).len(), 1);
+    }
+}
@@ -0,0 +1,599 @@
+//! Human readable renderings of a Radiotap capture.
+
+use alloc::string::{String, ToString};
+use core::fmt::{self, Display, Formatter};
+
+use crate::{
+    field::ext::{
+        ChannelFlags, GuardInterval, HEBandwidth, HEGuardInterval, HTFormat, TimeUnit,
+        XChannelFlags, FEC,
+    },
+    Radiotap,
+};
+
+/// Writes a capture as a header line followed by one line per present field,
+/// with units.
+///
+/// The fields of the additional radiotap namespaces and the vendor namespaces
+/// are listed after the fields of the first radiotap namespace.
+///
+/// # Examples
+///
+/// ```
+/// use radiotap::{field::*, RadiotapBuilder};
+///
+/// let radiotap = RadiotapBuilder::new()
+///     .rate(Rate::from_mbps(6.0).unwrap())
+///     .antenna_signal(AntennaSignal { value: -42 })
+///     .done();
+/// assert_eq!(
+///     radiotap.to_string(),
+///     "Radiotap v0, 10 bytes, present 0x00000024\n\
+///      \x20 Rate: 6 Mb/s\n\
+///      \x20 Antenna signal: -42 dBm\n"
+/// );
+/// ```
+impl Display for Radiotap {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let header = &self.header;
+        writeln!(
+            f,
+            "Radiotap v{}, {} bytes, present {}",
+            header.version, header.length, header.present
+        )?;
+
+        if let Some(tsft) = self.tsft {
+            writeln!(f, "  TSFT: {} us", tsft.value)?;
+        }
+        if let Some(flags) = self.flags {
+            writeln!(
+                f,
+                "  Flags: {}",
+                Names(&[
+                    (flags.cfp, "CFP"),
+                    (flags.preamble, "short preamble"),
+                    (flags.wep, "WEP"),
+                    (flags.fragmentation, "fragmentation"),
+                    (flags.fcs, "FCS"),
+                    (flags.data_pad, "data pad"),
+                    (flags.bad_fcs, "bad FCS"),
+                    (flags.sgi, "short GI"),
+                ])
+            )?;
+        }
+        if let Some(rate) = self.rate {
+            writeln!(f, "  Rate: {} Mb/s", rate.as_mbps())?;
+        }
+        if let Some(channel) = self.channel {
+            let flags = channel.flags;
+            writeln!(
+                f,
+                "  Channel: {} MHz ({})",
+                channel.freq,
+                Names(&[
+                    (flags.turbo, "turbo"),
+                    (flags.cck, "CCK"),
+                    (flags.ofdm, "OFDM"),
+                    (flags.ghz2, "2 GHz"),
+                    (flags.ghz5, "5 GHz"),
+                    (flags.passive, "passive"),
+                    (flags.dynamic, "dynamic CCK-OFDM"),
+                    (flags.gfsk, "GFSK"),
+                ])
+            )?;
+        }
+        if let Some(fhss) = self.fhss {
+            writeln!(
+                f,
+                "  FHSS: hop set {}, pattern {}",
+                fhss.hopset, fhss.pattern
+            )?;
+        }
+        if let Some(signal) = self.antenna_signal {
+            writeln!(f, "  Antenna signal: {} dBm", signal.value)?;
+        }
+        if let Some(noise) = self.antenna_noise {
+            writeln!(f, "  Antenna noise: {} dBm", noise.value)?;
+        }
+        if let Some(quality) = self.lock_quality {
+            writeln!(f, "  Lock quality: {}", quality.value)?;
+        }
+        if let Some(attenuation) = self.tx_attenuation {
+            writeln!(f, "  TX attenuation: {}", attenuation.value)?;
+        }
+        if let Some(attenuation) = self.tx_attenuation_db {
+            writeln!(f, "  TX attenuation: {} dB", attenuation.value)?;
+        }
+        if let Some(power) = self.tx_power {
+            writeln!(f, "  TX power: {} dBm", power.value)?;
+        }
+        if let Some(antenna) = self.antenna {
+            writeln!(f, "  Antenna: {}", antenna.value)?;
+        }
+        if let Some(signal) = self.antenna_signal_db {
+            writeln!(f, "  Antenna signal: {} dB", signal.value)?;
+        }
+        if let Some(noise) = self.antenna_noise_db {
+            writeln!(f, "  Antenna noise: {} dB", noise.value)?;
+        }
+        if let Some(rx_flags) = self.rx_flags {
+            writeln!(
+                f,
+                "  RX flags: {}",
+                Names(&[(rx_flags.bad_plcp, "bad PLCP")])
+            )?;
+        }
+        if let Some(tx_flags) = self.tx_flags {
+            writeln!(
+                f,
+                "  TX flags: {}",
+                Names(&[
+                    (tx_flags.fail, "failed"),
+                    (tx_flags.cts, "CTS"),
+                    (tx_flags.rts, "RTS"),
+                    (tx_flags.no_ack, "no ACK"),
+                    (tx_flags.no_seq, "no sequence"),
+                ])
+            )?;
+        }
+        if let Some(retries) = self.rts_retries {
+            writeln!(f, "  RTS retries: {}", retries.value)?;
+        }
+        if let Some(retries) = self.data_retries {
+            writeln!(f, "  Data retries: {}", retries.value)?;
+        }
+        if let Some(xchannel) = self.xchannel {
+            writeln!(
+                f,
+                "  XChannel: {} MHz, channel {}, max power {} dBm",
+                xchannel.freq, xchannel.channel, xchannel.max_power
+            )?;
+        }
+        if let Some(mcs) = self.mcs {
+            write!(f, "  MCS:")?;
+            Parts::new(f)
+                .part("index", mcs.index)?
+                .unit(mcs.bw.map(|bw| bw.bandwidth), "MHz")?
+                .unit(mcs.gi.map(gi_name), "GI")?
+                .unit(mcs.datarate, "Mb/s")?
+                .finish()?;
+        }
+        if let Some(ampdu) = self.ampdu_status {
+            writeln!(f, "  A-MPDU: reference {}", ampdu.reference)?;
+        }
+        if let Some(vht) = self.vht {
+            write!(f, "  VHT:")?;
+            Parts::new(f)
+                .unit(vht.bw.map(|bw| bw.bandwidth), "MHz")?
+                .unit(vht.gi.map(gi_name), "GI")?
+                .part("group", vht.group_id)?
+                .finish()?;
+            for (i, user) in vht.users.iter().enumerate() {
+                if let Some(user) = user {
+                    write!(
+                        f,
+                        "    User {}: MCS {}, {} streams",
+                        i, user.index, user.nss
+                    )?;
+                    match user.datarate {
+                        Some(datarate) => writeln!(f, ", {} Mb/s", datarate)?,
+                        None => writeln!(f)?,
+                    }
+                }
+            }
+        }
+        if let Some(timestamp) = self.timestamp {
+            let unit = match timestamp.unit {
+                TimeUnit::Milliseconds => "ms",
+                TimeUnit::Microseconds => "us",
+                TimeUnit::Nanoseconds => "ns",
+            };
+            writeln!(f, "  Timestamp: {} {}", timestamp.timestamp, unit)?;
+        }
+        if let Some(he) = self.he {
+            write!(f, "  HE:")?;
+            Parts::new(f)
+                .part("format", he.format.map(Name))?
+                .part("MCS", he.index)?
+                .unit(
+                    he.bw.and_then(|bw| match bw {
+                        HEBandwidth::Bandwidth(bw) => Some(bw.bandwidth),
+                        HEBandwidth::RU(_) => None,
+                    }),
+                    "MHz",
+                )?
+                .part(
+                    "size",
+                    he.bw.and_then(|bw| match bw {
+                        HEBandwidth::RU(ru) => Some(Name(ru)),
+                        HEBandwidth::Bandwidth(_) => None,
+                    }),
+                )?
+                .unit(he.gi.map(he_gi_name), "GI")?
+                .part("BSS color", he.bss_color)?
+                .finish()?;
+        }
+        if let Some(he_mu) = self.he_mu {
+            write!(f, "  HE-MU:")?;
+            Parts::new(f)
+                .part("SIG-B MCS", he_mu.sigb_mcs)?
+                .unit(he_mu.bw.map(|bw| bw.bandwidth), "MHz")?
+                .finish()?;
+        }
+        if let Some(user) = self.he_mu_other_user {
+            write!(f, "  HE-MU other user:")?;
+            Parts::new(f)
+                .part("STA ID", user.sta_id)?
+                .part("MCS", user.index)?
+                .unit(user.nsts, "streams")?
+                .finish()?;
+        }
+        if let Some(l_sig) = self.l_sig {
+            write!(f, "  L-SIG:")?;
+            Parts::new(f)
+                .part("rate", l_sig.rate)?
+                .part("length", l_sig.length)?
+                .finish()?;
+        }
+        if let Some(u_sig) = &self.u_sig {
+            write!(f, "  U-SIG:")?;
+            Parts::new(f)
+                .part("PHY version", u_sig.phy_version)?
+                .part("BSS color", u_sig.bss_color)?
+                .part("TXOP", u_sig.txop)?
+                .finish()?;
+        }
+        if let Some(eht) = &self.eht {
+            write!(f, "  EHT:")?;
+            Parts::new(f)
+                .unit(eht.gi.map(he_gi_name), "GI")?
+                .unit(eht.nss, "streams")?
+                .unit(Some(eht.users.len()), "users")?
+                .finish()?;
+        }
+
+        for (i, namespace) in self.namespaces.iter().enumerate() {
+            writeln!(
+                f,
+                "  Radiotap namespace {}: {} fields",
+                i + 1,
+                namespace.len()
+            )?;
+        }
+        for vendor in &self.vendor_namespaces {
+            writeln!(
+                f,
+                "  Vendor namespace {} {}: {} bytes",
+                vendor.oui,
+                vendor.sub_namespace,
+                vendor.data.len()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Radiotap {
+    /// Returns the capture as a single line in the style of tcpdump, such as
+    /// `30.0 Mb/s 5180 MHz 11a -38dBm signal antenna 1`.
+    ///
+    /// The fields are written in the order of their present bits, the HE and
+    /// EHT fields and the vendor namespaces are not written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::Radiotap;
+    ///
+    /// let capture = [0, 0, 16, 0, 46, 8, 0, 0, 0, 60, 60, 20, 64, 1, 218, 1];
+    /// let radiotap = Radiotap::from_bytes(&capture).unwrap();
+    /// assert_eq!(radiotap.to_line(), "30.0 Mb/s 5180 MHz 11a -38dBm signal antenna 1");
+    /// ```
+    pub fn to_line(&self) -> String {
+        Line(self).to_string()
+    }
+}
+
+/// Writes a capture on a single line, see
+/// [Radiotap::to_line](struct.Radiotap.html#method.to_line).
+struct Line<'a>(&'a Radiotap);
+
+impl Display for Line<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let radiotap = self.0;
+        let mut words = Words::new(f);
+
+        if let Some(tsft) = radiotap.tsft {
+            words.word(format_args!("{}us tsft", tsft.value))?;
+        }
+        if let Some(flags) = radiotap.flags {
+            words.flag(flags.cfp, "cfp")?;
+            words.flag(flags.preamble, "short preamble")?;
+            words.flag(flags.wep, "WEP Encrypted")?;
+            words.flag(flags.fragmentation, "fragmented")?;
+            words.flag(flags.bad_fcs, "bad-fcs")?;
+        }
+        if let Some(rate) = radiotap.rate {
+            words.word(format_args!("{:.1} Mb/s", rate.as_mbps()))?;
+        }
+        if let Some(channel) = radiotap.channel {
+            words.word(format_args!("{} MHz", channel.freq))?;
+            words.channel(&channel.flags)?;
+        } else if let Some(xchannel) = radiotap.xchannel {
+            words.word(format_args!("{} MHz", xchannel.freq))?;
+            words.xchannel(&xchannel.flags)?;
+        }
+        if let Some(fhss) = radiotap.fhss {
+            words.word(format_args!("fhset {} fhpat {}", fhss.hopset, fhss.pattern))?;
+        }
+        if let Some(signal) = radiotap.antenna_signal {
+            words.word(format_args!("{}dBm signal", signal.value))?;
+        }
+        if let Some(noise) = radiotap.antenna_noise {
+            words.word(format_args!("{}dBm noise", noise.value))?;
+        }
+        if let Some(quality) = radiotap.lock_quality {
+            words.word(format_args!("{} sq", quality.value))?;
+        }
+        if let Some(attenuation) = radiotap.tx_attenuation {
+            words.word(format_args!("{} tx power", -i32::from(attenuation.value)))?;
+        }
+        if let Some(attenuation) = radiotap.tx_attenuation_db {
+            words.word(format_args!("{}dB tx attenuation", attenuation.value))?;
+        }
+        if let Some(power) = radiotap.tx_power {
+            words.word(format_args!("{}dBm tx power", power.value))?;
+        }
+        if let Some(antenna) = radiotap.antenna {
+            words.word(format_args!("antenna {}", antenna.value))?;
+        }
+        if let Some(signal) = radiotap.antenna_signal_db {
+            words.word(format_args!("{}dB signal", signal.value))?;
+        }
+        if let Some(noise) = radiotap.antenna_noise_db {
+            words.word(format_args!("{}dB noise", noise.value))?;
+        }
+        if let Some(rx_flags) = radiotap.rx_flags {
+            words.flag(rx_flags.bad_plcp, "bad-plcp")?;
+        }
+        if let Some(mcs) = radiotap.mcs {
+            if let Some(index) = mcs.index {
+                match mcs.datarate {
+                    Some(datarate) => {
+                        words.word(format_args!("{:.1} Mb/s MCS {}", datarate, index))?
+                    }
+                    None => words.word(format_args!("MCS {}", index))?,
+                }
+            }
+            if let Some(bw) = mcs.bw {
+                match bw.sideband_index {
+                    Some(0) => words.word("20 MHz (L)")?,
+                    Some(_) => words.word("20 MHz (U)")?,
+                    None => words.word(format_args!("{} MHz", bw.bandwidth))?,
+                }
+            }
+            match mcs.gi {
+                Some(GuardInterval::Short) => words.word("sgi")?,
+                Some(GuardInterval::Long) => words.word("lgi")?,
+                None => {}
+            }
+            match mcs.format {
+                Some(HTFormat::Greenfield) => words.word("greenfield")?,
+                Some(HTFormat::Mixed) => words.word("mixed")?,
+                None => {}
+            }
+            match mcs.fec {
+                Some(FEC::BCC) => words.word("BCC FEC")?,
+                Some(FEC::LDPC) => words.word("LDPC FEC")?,
+                None => {}
+            }
+            if let Some(stbc) = mcs.stbc.filter(|&stbc| stbc > 0) {
+                words.word(format_args!("RX-STBC{}", stbc))?;
+            }
+        }
+        if let Some(vht) = radiotap.vht {
+            for user in vht.users.iter().flatten() {
+                match user.datarate {
+                    Some(datarate) => words.word(format_args!(
+                        "{:.1} Mb/s MCS {} NSS {}",
+                        datarate, user.index, user.nss
+                    ))?,
+                    None => words.word(format_args!("MCS {} NSS {}", user.index, user.nss))?,
+                }
+            }
+            if let Some(bw) = vht.bw {
+                words.word(format_args!("{} MHz", bw.bandwidth))?;
+            }
+            match vht.gi {
+                Some(GuardInterval::Short) => words.word("sgi")?,
+                Some(GuardInterval::Long) => words.word("lgi")?,
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Words written separated by spaces.
+struct Words<'a, 'b> {
+    f: &'a mut Formatter<'b>,
+    empty: bool,
+}
+
+impl<'a, 'b> Words<'a, 'b> {
+    fn new(f: &'a mut Formatter<'b>) -> Words<'a, 'b> {
+        Words { f, empty: true }
+    }
+
+    fn word<T: Display>(&mut self, word: T) -> fmt::Result {
+        if !self.empty {
+            self.f.write_str(" ")?;
+        }
+        self.empty = false;
+        write!(self.f, "{}", word)
+    }
+
+    fn flag(&mut self, set: bool, word: &str) -> fmt::Result {
+        if set {
+            self.word(word)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the PHY of a channel like tcpdump.
+    fn channel(&mut self, flags: &ChannelFlags) -> fmt::Result {
+        self.flag(flags.ghz2 && flags.gfsk, "FHSS")?;
+        self.flag(flags.ghz5 && flags.ofdm, "11a")?;
+        self.flag(flags.ghz2 && (flags.ofdm || flags.dynamic), "11g")?;
+        self.flag(flags.ghz2 && flags.cck, "11b")?;
+        self.flag(flags.turbo, "Turbo")
+    }
+
+    /// Writes the PHY of an extended channel like tcpdump.
+    fn xchannel(&mut self, flags: &XChannelFlags) -> fmt::Result {
+        self.flag(flags.ghz2 && flags.gfsk, "FHSS")?;
+        if flags.ghz5 && flags.ofdm {
+            if flags.half {
+                self.word("11a/10Mhz")?;
+            } else if flags.quarter {
+                self.word("11a/5Mhz")?;
+            } else {
+                self.word("11a")?;
+            }
+        }
+        if flags.ghz2 && (flags.ofdm || flags.dynamic) {
+            if flags.half {
+                self.word("11g/10Mhz")?;
+            } else if flags.quarter {
+                self.word("11g/5Mhz")?;
+            } else {
+                self.word("11g")?;
+            }
+        }
+        self.flag(flags.ghz2 && flags.cck, "11b")?;
+        self.flag(flags.turbo, "Turbo")?;
+        self.flag(flags.ht20, "ht/20")?;
+        self.flag(flags.ht40d, "ht/40-")?;
+        self.flag(flags.ht40u, "ht/40+")
+    }
+}
+
+/// The names of the set flags, separated by commas, or `none`.
+struct Names<'a>(&'a [(bool, &'static str)]);
+
+impl Display for Names<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut names = self.0.iter().filter(|(set, _)| *set).map(|(_, name)| name);
+        match names.next() {
+            Some(name) => f.write_str(name)?,
+            None => return f.write_str("none"),
+        }
+        for name in names {
+            write!(f, ", {}", name)?;
+        }
+        Ok(())
+    }
+}
+
+/// The known parts of a field, written as a comma separated line.
+struct Parts<'a, 'b> {
+    f: &'a mut Formatter<'b>,
+    empty: bool,
+}
+
+impl<'a, 'b> Parts<'a, 'b> {
+    fn new(f: &'a mut Formatter<'b>) -> Parts<'a, 'b> {
+        Parts { f, empty: true }
+    }
+
+    fn separator(&mut self) -> &'static str {
+        let separator = if self.empty { " " } else { ", " };
+        self.empty = false;
+        separator
+    }
+
+    /// Writes the value after its label, if it is known.
+    fn part<T: Display>(mut self, label: &str, value: Option<T>) -> Result<Self, fmt::Error> {
+        if let Some(value) = value {
+            let separator = self.separator();
+            write!(self.f, "{}{} {}", separator, label, value)?;
+        }
+        Ok(self)
+    }
+
+    /// Writes the value before its unit, if it is known.
+    fn unit<T: Display>(mut self, value: Option<T>, unit: &str) -> Result<Self, fmt::Error> {
+        if let Some(value) = value {
+            let separator = self.separator();
+            write!(self.f, "{}{} {}", separator, value, unit)?;
+        }
+        Ok(self)
+    }
+
+    fn finish(self) -> fmt::Result {
+        if self.empty {
+            writeln!(self.f, " unknown")
+        } else {
+            writeln!(self.f)
+        }
+    }
+}
+
+/// Writes a value with its `Debug` implementation, for plain enumerations.
+struct Name<T>(T);
+
+impl<T: fmt::Debug> Display for Name<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+fn gi_name(gi: GuardInterval) -> &'static str {
+    match gi {
+        GuardInterval::Long => "800 ns",
+        GuardInterval::Short => "400 ns",
+    }
+}
+
+fn he_gi_name(gi: HEGuardInterval) -> &'static str {
+    match gi {
+        HEGuardInterval::GI800 => "800 ns",
+        HEGuardInterval::GI1600 => "1600 ns",
+        HEGuardInterval::GI3200 => "3200 ns",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vht() {
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(
+            radiotap.to_string(),
+            "Radiotap v0, 56 bytes, present 0x0034086b
+  TSFT: 2593857465 us
+  Flags: WEP, FCS
+  Channel: 5500 MHz (OFDM, 5 GHz)
+  Antenna signal: -43 dBm
+  Antenna noise: -90 dBm
+  Antenna: 1
+  XChannel: 5500 MHz, channel 100, max power 34 dBm
+  A-MPDU: reference 505
+  VHT: 80 MHz, 800 ns GI, group 63
+    User 0: MCS 7, 3 streams, 877.5 Mb/s
+"
+        );
+        assert_eq!(
+            radiotap.to_line(),
+            "2593857465us tsft WEP Encrypted 5500 MHz 11a -43dBm signal -90dBm noise antenna 1 \
+             877.5 Mb/s MCS 7 NSS 3 80 MHz lgi"
+        );
+    }
+}
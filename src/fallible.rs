@@ -0,0 +1,118 @@
+//! Adapters implementing
+//! [FallibleIterator](https://docs.rs/fallible-iterator/0.3/fallible_iterator/trait.FallibleIterator.html)
+//! over Radiotap fields and streams of captures.
+//!
+//! Enabled with the `fallible-iterator` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use fallible_iterator::FallibleIterator;
+//! use radiotap::fallible::Captures;
+//!
+//! let captures: [&[u8]; 2] = [&[0, 0, 9, 0, 4, 0, 0, 0, 12], &[0, 0, 8, 0, 0, 0, 0, 0]];
+//! let rates: Vec<_> = Captures::new(captures.iter())
+//!     .map(|radiotap| Ok(radiotap.rate.map(|rate| rate.as_mbps())))
+//!     .collect()
+//!     .unwrap();
+//! assert_eq!(rates, [Some(6.0), None]);
+//! ```
+
+use fallible_iterator::FallibleIterator;
+
+#[cfg(feature = "alloc")]
+use crate::Radiotap;
+use crate::{field::Kind, Error, RadiotapIterator, RadiotapIteratorIntoIter};
+
+/// A fallible iterator over the fields of a
+/// [RadiotapIterator](../struct.RadiotapIterator.html).
+#[derive(Debug, Clone)]
+pub struct Fields<'a> {
+    inner: RadiotapIteratorIntoIter<'a>,
+}
+
+impl<'a> Fields<'a> {
+    /// Returns a fallible iterator over the fields of the given capture.
+    pub fn new(iterator: &'a RadiotapIterator<'a>) -> Fields<'a> {
+        Fields {
+            inner: iterator.into_iter(),
+        }
+    }
+}
+
+impl<'a> FallibleIterator for Fields<'a> {
+    type Item = (Kind, &'a [u8]);
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>, Error> {
+        self.inner.next().transpose()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+/// A fallible iterator parsing each capture yielded by an iterator of byte
+/// buffers, stopping at the first capture that fails to parse.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct Captures<I> {
+    inner: I,
+}
+
+#[cfg(feature = "alloc")]
+impl<I> Captures<I>
+where
+    I: Iterator,
+    I::Item: AsRef<[u8]>,
+{
+    /// Returns a fallible iterator over the parsed captures.
+    pub fn new(inner: I) -> Captures<I> {
+        Captures { inner }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I> FallibleIterator for Captures<I>
+where
+    I: Iterator,
+    I::Item: AsRef<[u8]>,
+{
+    type Item = Radiotap;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<Radiotap>, Error> {
+        self.inner
+            .next()
+            .map(|capture| Radiotap::from_bytes(capture.as_ref()))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields() {
+        let capture = [0, 0, 12, 0, 6, 0, 0, 0, 12, 0, 108, 9];
+        let iterator = RadiotapIterator::from_bytes(&capture).unwrap();
+        let kinds: Vec<_> = Fields::new(&iterator)
+            .map(|(kind, _)| Ok(kind))
+            .collect()
+            .unwrap();
+        assert_eq!(kinds, [Kind::Flags, Kind::Rate]);
+
+        let capture = [0, 0, 10, 0, 8, 0, 0, 0, 0, 0];
+        let iterator = RadiotapIterator::from_bytes(&capture).unwrap();
+        match Fields::new(&iterator).count().unwrap_err() {
+            Error::IncompleteError => {}
+            e => panic!("Error not IncompleteError: {:?}", e),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Frame check sequence (FCS) computation.
+//!
+//! The 802.11 FCS is the standard IEEE 802.3 CRC-32 over the MAC header and
+//! frame body. By default a table-driven implementation is used, enabling the
+//! `crc32fast` feature switches to the SIMD accelerated implementation from the
+//! [crc32fast](https://docs.rs/crc32fast) crate.
+//!
+//! When the [Flags](../field/struct.Flags.html) field says the frame ends with
+//! an FCS, [verify](fn.verify.html) checks it, and
+//! [FcsStatus](enum.FcsStatus.html) combines the check with the bad FCS flag
+//! set by the driver.
+
+use crate::field::Flags;
+
+/// Whether an 802.11 frame was received intact, see
+/// [Payload::fcs_status](../payload/struct.Payload.html#method.fcs_status).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum FcsStatus {
+    /// The FCS at the end of the frame matches the frame.
+    Valid,
+    /// The FCS at the end of the frame does not match the frame, or the
+    /// driver flagged the frame as failing the FCS check.
+    Invalid,
+    /// The frame does not include an FCS and was not flagged, so it cannot be
+    /// checked.
+    Unknown,
+}
+
+impl FcsStatus {
+    /// Returns the status of the data following a Radiotap capture with the
+    /// given flags, the 802.11 frame including any FCS.
+    pub fn new(data: &[u8], flags: Option<Flags>) -> FcsStatus {
+        FcsStatus::check(
+            data,
+            flags.is_some_and(|flags| flags.fcs),
+            flags.is_some_and(|flags| flags.bad_fcs),
+        )
+    }
+
+    pub(crate) fn check(data: &[u8], fcs: bool, bad_fcs: bool) -> FcsStatus {
+        if bad_fcs || (fcs && !verify(data)) {
+            FcsStatus::Invalid
+        } else if fcs {
+            FcsStatus::Valid
+        } else {
+            FcsStatus::Unknown
+        }
+    }
+}
+
+/// Returns whether the FCS in the last 4 bytes of an 802.11 frame matches the
+/// rest of the frame, false if the frame is shorter than an FCS.
+///
+/// # Examples
+///
+/// ```
+/// use radiotap::fcs;
+///
+/// // An ACK frame with an FCS
+/// let frame = [0xd4, 0, 0, 0, 2, 4, 6, 8, 10, 12, 0x4b, 0xea, 0x19, 0xd5];
+/// assert!(fcs::verify(&frame));
+/// assert!(!fcs::verify(&frame[1..]));
+/// ```
+pub fn verify(frame: &[u8]) -> bool {
+    if frame.len() < 4 {
+        return false;
+    }
+    let (frame, fcs) = frame.split_at(frame.len() - 4);
+    crc32(frame) == u32::from_le_bytes([fcs[0], fcs[1], fcs[2], fcs[3]])
+}
+
+/// Returns the IEEE 802.3 CRC-32 of the given data.
+#[cfg(feature = "crc32fast")]
+pub fn crc32(data: &[u8]) -> u32 {
+    crc32fast::hash(data)
+}
+
+/// Returns the IEEE 802.3 CRC-32 of the given data.
+#[cfg(not(feature = "crc32fast"))]
+pub fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0, |crc, &byte| {
+        CRC32_TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+/// Lookup table for the reflected CRC-32 polynomial 0xedb88320.
+#[cfg(not(feature = "crc32fast"))]
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut j = 0;
+        while j < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+            j += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::Field;
+
+    #[test]
+    fn check_value() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn status() {
+        let frame = [0xd4, 0, 0, 0, 2, 4, 6, 8, 10, 12, 0x4b, 0xea, 0x19, 0xd5];
+        let fcs = Some(Flags::from_bytes(&[0x10]).unwrap());
+        let bad_fcs = Some(Flags::from_bytes(&[0x40]).unwrap());
+
+        assert_eq!(FcsStatus::new(&frame, fcs), FcsStatus::Valid);
+        assert_eq!(FcsStatus::new(&frame[..13], fcs), FcsStatus::Invalid);
+        assert_eq!(FcsStatus::new(&frame[..10], bad_fcs), FcsStatus::Invalid);
+        assert_eq!(FcsStatus::new(&frame[..10], None), FcsStatus::Unknown);
+        assert!(!verify(&frame[..3]));
+    }
+}
@@ -1,43 +1,94 @@
 //! Extended Radiotap field definitions and parsers.
 
-use crate::{Error, Result};
-
-const HT_RATE: [[f32; 4]; 32] = [
-    // 20 MHz LGI,20 MHz SGI,40 MHZ LGI,40 MHz SGI
-    [6.50, 7.20, 13.50, 15.00],
-    [13.00, 14.40, 27.00, 30.00],
-    [19.50, 21.70, 40.50, 45.00],
-    [26.00, 28.90, 54.00, 60.00],
-    [39.00, 43.30, 81.00, 90.00],
-    [52.00, 57.80, 108.00, 120.00],
-    [58.50, 65.00, 121.50, 135.00],
-    [65.00, 72.20, 135.00, 150.00],
-    [13.00, 14.40, 27.00, 30.00],
-    [26.00, 28.90, 54.00, 60.00],
-    [39.00, 43.30, 81.00, 90.00],
-    [52.00, 57.80, 108.00, 120.00],
-    [78.00, 86.70, 162.00, 180.00],
-    [104.00, 115.60, 216.00, 240.00],
-    [117.00, 130.00, 243.00, 270.00],
-    [130.00, 144.40, 270.00, 300.00],
-    [19.50, 21.70, 40.50, 45.00],
-    [39.00, 43.30, 81.00, 90.00],
-    [58.50, 65.00, 121.50, 135.00],
-    [78.00, 86.70, 162.00, 180.00],
-    [117.00, 130.00, 243.00, 270.00],
-    [156.00, 173.30, 324.00, 360.00],
-    [175.50, 195.00, 364.50, 405.00],
-    [195.00, 216.70, 405.00, 450.00],
-    [26.00, 28.80, 54.00, 60.00],
-    [52.00, 57.60, 108.00, 120.00],
-    [78.00, 86.80, 162.00, 180.00],
-    [104.00, 115.60, 216.00, 240.00],
-    [156.00, 173.20, 324.00, 360.00],
-    [208.00, 231.20, 432.00, 480.00],
-    [234.00, 260.00, 486.00, 540.00],
-    [260.00, 288.80, 540.00, 600.00],
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+use crate::{util::channel, Error, Result};
+
+/// The 802.11n data rates of MCS 0 to 76, MCS 32 is only defined for 40 MHz.
+const HT_RATE: [[f32; 4]; 77] = [
+    // 20 MHz LGI,20 MHz SGI,40 MHz LGI,40 MHz SGI
+    [6.5, 7.2, 13.5, 15.0],
+    [13.0, 14.4, 27.0, 30.0],
+    [19.5, 21.7, 40.5, 45.0],
+    [26.0, 28.9, 54.0, 60.0],
+    [39.0, 43.3, 81.0, 90.0],
+    [52.0, 57.8, 108.0, 120.0],
+    [58.5, 65.0, 121.5, 135.0],
+    [65.0, 72.2, 135.0, 150.0],
+    [13.0, 14.4, 27.0, 30.0],
+    [26.0, 28.9, 54.0, 60.0],
+    [39.0, 43.3, 81.0, 90.0],
+    [52.0, 57.8, 108.0, 120.0],
+    [78.0, 86.7, 162.0, 180.0],
+    [104.0, 115.6, 216.0, 240.0],
+    [117.0, 130.0, 243.0, 270.0],
+    [130.0, 144.4, 270.0, 300.0],
+    [19.5, 21.7, 40.5, 45.0],
+    [39.0, 43.3, 81.0, 90.0],
+    [58.5, 65.0, 121.5, 135.0],
+    [78.0, 86.7, 162.0, 180.0],
+    [117.0, 130.0, 243.0, 270.0],
+    [156.0, 173.3, 324.0, 360.0],
+    [175.5, 195.0, 364.5, 405.0],
+    [195.0, 216.7, 405.0, 450.0],
+    [26.0, 28.9, 54.0, 60.0],
+    [52.0, 57.8, 108.0, 120.0],
+    [78.0, 86.7, 162.0, 180.0],
+    [104.0, 115.6, 216.0, 240.0],
+    [156.0, 173.3, 324.0, 360.0],
+    [208.0, 231.1, 432.0, 480.0],
+    [234.0, 260.0, 486.0, 540.0],
+    [260.0, 288.9, 540.0, 600.0],
+    [-1.0, -1.0, 6.0, 6.7],
+    [39.0, 43.3, 81.0, 90.0],
+    [52.0, 57.8, 108.0, 120.0],
+    [65.0, 72.2, 135.0, 150.0],
+    [58.5, 65.0, 121.5, 135.0],
+    [78.0, 86.7, 162.0, 180.0],
+    [97.5, 108.3, 202.5, 225.0],
+    [52.0, 57.8, 108.0, 120.0],
+    [65.0, 72.2, 135.0, 150.0],
+    [65.0, 72.2, 135.0, 150.0],
+    [78.0, 86.7, 162.0, 180.0],
+    [91.0, 101.1, 189.0, 210.0],
+    [91.0, 101.1, 189.0, 210.0],
+    [104.0, 115.6, 216.0, 240.0],
+    [78.0, 86.7, 162.0, 180.0],
+    [97.5, 108.3, 202.5, 225.0],
+    [97.5, 108.3, 202.5, 225.0],
+    [117.0, 130.0, 243.0, 270.0],
+    [136.5, 151.7, 283.5, 315.0],
+    [136.5, 151.7, 283.5, 315.0],
+    [156.0, 173.3, 324.0, 360.0],
+    [65.0, 72.2, 135.0, 150.0],
+    [78.0, 86.7, 162.0, 180.0],
+    [91.0, 101.1, 189.0, 210.0],
+    [78.0, 86.7, 162.0, 180.0],
+    [91.0, 101.1, 189.0, 210.0],
+    [104.0, 115.6, 216.0, 240.0],
+    [117.0, 130.0, 243.0, 270.0],
+    [104.0, 115.6, 216.0, 240.0],
+    [117.0, 130.0, 243.0, 270.0],
+    [130.0, 144.4, 270.0, 300.0],
+    [130.0, 144.4, 270.0, 300.0],
+    [143.0, 158.9, 297.0, 330.0],
+    [97.5, 108.3, 202.5, 225.0],
+    [117.0, 130.0, 243.0, 270.0],
+    [136.5, 151.7, 283.5, 315.0],
+    [117.0, 130.0, 243.0, 270.0],
+    [136.5, 151.7, 283.5, 315.0],
+    [156.0, 173.3, 324.0, 360.0],
+    [175.5, 195.0, 364.5, 405.0],
+    [156.0, 173.3, 324.0, 360.0],
+    [175.5, 195.0, 364.5, 405.0],
+    [195.0, 216.7, 405.0, 450.0],
+    [195.0, 216.7, 405.0, 450.0],
+    [214.5, 238.3, 445.5, 495.0],
 ];
 
+/// The 802.11ac data rates of MCS 0 to 9 for 1 to 8 spatial streams, the
+/// combinations that are not defined are negative.
 const VHT_RATE: [[f32; 8]; 80] = [
     // 20 MHz LGI,20 MHz SGI,40 MHz LGI,40 MHz SGI,80 MHZ LGI,80 MHz SGI,160 MHZ LGI,160 MHz SGI
     [6.5, 7.2, 13.5, 15.0, 29.3, 32.5, 58.5, 65.0],
@@ -56,7 +107,7 @@ const VHT_RATE: [[f32; 8]; 80] = [
     [52.0, 57.8, 108.0, 120.0, 234.0, 260.0, 468.0, 520.0],
     [78.0, 86.7, 162.0, 180.0, 351.0, 390.0, 702.0, 780.0],
     [104.0, 115.6, 216.0, 240.0, 468.0, 520.0, 936.0, 1040.0],
-    [117.0, 130.3, 243.0, 270.0, 526.5, 585.0, 1053.0, 1170.0],
+    [117.0, 130.0, 243.0, 270.0, 526.5, 585.0, 1053.0, 1170.0],
     [130.0, 144.4, 270.0, 300.0, 585.0, 650.0, 1170.0, 1300.0],
     [156.0, 173.3, 324.0, 360.0, 702.0, 780.0, 1404.0, 1560.0],
     [-1.0, -1.0, 360.0, 400.0, 780.0, 866.7, 1560.0, 1733.3],
@@ -80,56 +131,72 @@ const VHT_RATE: [[f32; 8]; 80] = [
     [260.0, 288.9, 540.0, 600.0, 1170.0, 1300.0, 2340.0, 2600.0],
     [312.0, 346.7, 648.0, 720.0, 1404.0, 1560.0, 2808.0, 3120.0],
     [-1.0, -1.0, 720.0, 800.0, 1560.0, 1733.3, 3120.0, 3466.7],
-    [-1.0, -1.0, -1.0, -1.0, 146.3, 162.5, 292.5, 325.0],
-    [-1.0, -1.0, -1.0, -1.0, 292.5, 325.0, 585.0, 650.0],
-    [-1.0, -1.0, -1.0, -1.0, 438.8, 487.5, 877.5, 975.0],
-    [-1.0, -1.0, -1.0, -1.0, 585.0, 650.0, 1170.0, 1300.0],
-    [-1.0, -1.0, -1.0, -1.0, 877.5, 975.0, 1755.0, 1950.0],
-    [-1.0, -1.0, -1.0, -1.0, 1170.0, 1300.0, 2340.0, 2600.0],
-    [-1.0, -1.0, -1.0, -1.0, 1316.3, 1462.5, 2632.5, 2925.0],
-    [-1.0, -1.0, -1.0, -1.0, 1462.5, 1625.0, 2925.0, 3250.0],
-    [-1.0, -1.0, -1.0, -1.0, 1755.0, 1950.0, 3510.0, 3900.0],
-    [-1.0, -1.0, -1.0, -1.0, 1950.0, 2166.7, 3900.0, 4333.3],
-    [-1.0, -1.0, -1.0, -1.0, 175.5, 195.0, 351.0, 390.0],
-    [-1.0, -1.0, -1.0, -1.0, 351.0, 390.0, 702.0, 780.0],
-    [-1.0, -1.0, -1.0, -1.0, 526.5, 585.0, 1053.0, 1170.0],
-    [-1.0, -1.0, -1.0, -1.0, 702.0, 780.0, 1404.0, 1560.0],
-    [-1.0, -1.0, -1.0, -1.0, 1053.0, 1170.0, 2106.0, 2340.0],
-    [-1.0, -1.0, -1.0, -1.0, 1404.0, 1560.0, 2808.0, 3120.0],
-    [-1.0, -1.0, -1.0, -1.0, 1579.5, 1755.0, 3159.0, 3510.0],
-    [-1.0, -1.0, -1.0, -1.0, 1755.0, 1950.0, 3510.0, 3900.0],
-    [-1.0, -1.0, -1.0, -1.0, 2106.0, 2340.0, 4212.0, 4680.0],
-    [-1.0, -1.0, -1.0, -1.0, -1.0, -1.0, 4680.0, 5200.0],
-    [-1.0, -1.0, -1.0, -1.0, 204.8, 227.5, 409.5, 455.0],
-    [-1.0, -1.0, -1.0, -1.0, 409.5, 455.0, 819.0, 910.0],
-    [-1.0, -1.0, -1.0, -1.0, 614.3, 682.5, 1228.5, 1365.0],
-    [-1.0, -1.0, -1.0, -1.0, 819.0, 910.0, 1638.0, 1820.0],
-    [-1.0, -1.0, -1.0, -1.0, 1228.5, 1365.0, 2457.0, 2730.0],
-    [-1.0, -1.0, -1.0, -1.0, 1638.0, 1820.0, 3276.0, 3640.0],
-    [-1.0, -1.0, -1.0, -1.0, -1.0, -1.0, 3685.5, 4095.0],
-    [-1.0, -1.0, -1.0, -1.0, 2047.5, 2275.0, 4095.0, 4550.0],
-    [-1.0, -1.0, -1.0, -1.0, 2457.0, 2730.0, 4914.0, 5460.0],
-    [-1.0, -1.0, -1.0, -1.0, 2730.0, 3033.3, 5460.0, 6066.7],
-    [-1.0, -1.0, -1.0, -1.0, 234.0, 260.0, 468.0, 520.0],
-    [-1.0, -1.0, -1.0, -1.0, 468.0, 520.0, 936.0, 1040.0],
-    [-1.0, -1.0, -1.0, -1.0, 702.0, 780.0, 1404.0, 1560.0],
-    [-1.0, -1.0, -1.0, -1.0, 936.0, 1040.0, 1872.0, 2080.0],
-    [-1.0, -1.0, -1.0, -1.0, 1404.0, 1560.0, 2808.0, 3120.0],
-    [-1.0, -1.0, -1.0, -1.0, 1872.0, 2080.0, 3744.0, 4160.0],
-    [-1.0, -1.0, -1.0, -1.0, 2106.0, 2340.0, 4212.0, 4680.0],
-    [-1.0, -1.0, -1.0, -1.0, 2340.0, 2600.0, 4680.0, 5200.0],
-    [-1.0, -1.0, -1.0, -1.0, 2808.0, 3120.0, 5616.0, 6240.0],
-    [-1.0, -1.0, -1.0, -1.0, 3120.0, 3466.7, 6240.0, 6933.3],
+    [32.5, 36.1, 67.5, 75.0, 146.3, 162.5, 292.5, 325.0],
+    [65.0, 72.2, 135.0, 150.0, 292.5, 325.0, 585.0, 650.0],
+    [97.5, 108.3, 202.5, 225.0, 438.8, 487.5, 877.5, 975.0],
+    [130.0, 144.4, 270.0, 300.0, 585.0, 650.0, 1170.0, 1300.0],
+    [195.0, 216.7, 405.0, 450.0, 877.5, 975.0, 1755.0, 1950.0],
+    [260.0, 288.9, 540.0, 600.0, 1170.0, 1300.0, 2340.0, 2600.0],
+    [292.5, 325.0, 607.5, 675.0, 1316.3, 1462.5, 2632.5, 2925.0],
+    [325.0, 361.1, 675.0, 750.0, 1462.5, 1625.0, 2925.0, 3250.0],
+    [390.0, 433.3, 810.0, 900.0, 1755.0, 1950.0, 3510.0, 3900.0],
+    [-1.0, -1.0, 900.0, 1000.0, 1950.0, 2166.7, 3900.0, 4333.3],
+    [39.0, 43.3, 81.0, 90.0, 175.5, 195.0, 351.0, 390.0],
+    [78.0, 86.7, 162.0, 180.0, 351.0, 390.0, 702.0, 780.0],
+    [117.0, 130.0, 243.0, 270.0, 526.5, 585.0, 1053.0, 1170.0],
+    [156.0, 173.3, 324.0, 360.0, 702.0, 780.0, 1404.0, 1560.0],
+    [234.0, 260.0, 486.0, 540.0, 1053.0, 1170.0, 2106.0, 2340.0],
+    [312.0, 346.7, 648.0, 720.0, 1404.0, 1560.0, 2808.0, 3120.0],
+    [351.0, 390.0, 729.0, 810.0, 1579.5, 1755.0, 3159.0, 3510.0],
+    [390.0, 433.3, 810.0, 900.0, 1755.0, 1950.0, 3510.0, 3900.0],
+    [468.0, 520.0, 972.0, 1080.0, 2106.0, 2340.0, 4212.0, 4680.0],
+    [520.0, 577.8, 1080.0, 1200.0, -1.0, -1.0, 4680.0, 5200.0],
+    [45.5, 50.6, 94.5, 105.0, 204.8, 227.5, 409.5, 455.0],
+    [91.0, 101.1, 189.0, 210.0, 409.5, 455.0, 819.0, 910.0],
+    [136.5, 151.7, 283.5, 315.0, 614.3, 682.5, 1228.5, 1365.0],
+    [182.0, 202.2, 378.0, 420.0, 819.0, 910.0, 1638.0, 1820.0],
+    [273.0, 303.3, 567.0, 630.0, 1228.5, 1365.0, 2457.0, 2730.0],
+    [364.0, 404.4, 756.0, 840.0, 1638.0, 1820.0, 3276.0, 3640.0],
+    [409.5, 455.0, 850.5, 945.0, -1.0, -1.0, 3685.5, 4095.0],
+    [455.0, 505.6, 945.0, 1050.0, 2047.5, 2275.0, 4095.0, 4550.0],
+    [546.0, 606.7, 1134.0, 1260.0, 2457.0, 2730.0, 4914.0, 5460.0],
+    [-1.0, -1.0, 1260.0, 1400.0, 2730.0, 3033.3, 5460.0, 6066.7],
+    [52.0, 57.8, 108.0, 120.0, 234.0, 260.0, 468.0, 520.0],
+    [104.0, 115.6, 216.0, 240.0, 468.0, 520.0, 936.0, 1040.0],
+    [156.0, 173.3, 324.0, 360.0, 702.0, 780.0, 1404.0, 1560.0],
+    [208.0, 231.1, 432.0, 480.0, 936.0, 1040.0, 1872.0, 2080.0],
+    [312.0, 346.7, 648.0, 720.0, 1404.0, 1560.0, 2808.0, 3120.0],
+    [416.0, 462.2, 864.0, 960.0, 1872.0, 2080.0, 3744.0, 4160.0],
+    [468.0, 520.0, 972.0, 1080.0, 2106.0, 2340.0, 4212.0, 4680.0],
+    [520.0, 577.8, 1080.0, 1200.0, 2340.0, 2600.0, 4680.0, 5200.0],
+    [624.0, 693.3, 1296.0, 1440.0, 2808.0, 3120.0, 5616.0, 6240.0],
+    [-1.0, -1.0, 1440.0, 1600.0, 3120.0, 3466.7, 6240.0, 6933.3],
 ];
 
-/// Returns the 802.11n data rate based on the MCS index, bandwidth, and guard
-/// interval.
+/// Returns the 802.11n data rate in Mbps based on the MCS index, bandwidth,
+/// and guard interval.
+///
+/// All 77 MCS indices are supported, including MCS 32 and the unequal
+/// modulation MCS 33 to 76. A 20 MHz transmission in the lower or upper half
+/// of a 40 MHz channel has the rate of a 20 MHz channel. Combinations that
+/// are not defined, such as MCS 32 at 20 MHz, return an error.
+///
+/// # Examples
+///
+/// ```
+/// use radiotap::field::ext::{ht_rate, Bandwidth, GuardInterval};
+///
+/// let bw = Bandwidth::new(1).unwrap();
+/// assert_eq!(ht_rate(7, bw, GuardInterval::Short).unwrap(), 150.0);
+/// assert_eq!(ht_rate(76, bw, GuardInterval::Long).unwrap(), 445.5);
+/// assert!(ht_rate(32, Bandwidth::new(0).unwrap(), GuardInterval::Long).is_err());
+/// ```
 pub fn ht_rate(index: u8, bw: Bandwidth, gi: GuardInterval) -> Result<f32> {
-    if index > 31 {
+    if usize::from(index) >= HT_RATE.len() {
         return Err(Error::InvalidFormat);
     }
 
-    let b = match bw.bandwidth {
+    let b = match bw.width() {
         20 => 0,
         40 => 2,
         _ => return Err(Error::InvalidFormat),
@@ -137,17 +204,38 @@ pub fn ht_rate(index: u8, bw: Bandwidth, gi: GuardInterval) -> Result<f32> {
 
     let col = b + usize::from(gi == GuardInterval::Short);
 
-    Ok(HT_RATE[index as usize][col])
+    let rate = HT_RATE[index as usize][col];
+    if rate < 0.0 {
+        return Err(Error::InvalidFormat);
+    }
+
+    Ok(rate)
 }
 
-/// Returns the 802.11ac data rate based on the MCS index, bandwidth, guard
-/// interval, and number of spatial streams.
+/// Returns the 802.11ac data rate in Mbps based on the MCS index, bandwidth,
+/// guard interval, and number of spatial streams.
+///
+/// MCS 0 to 9 with 1 to 8 spatial streams are supported at 20, 40, 80, and
+/// 160 MHz. A transmission in a sideband of a wider channel has the rate of
+/// the sideband. The combinations that are not defined by the standard, such
+/// as MCS 9 with one spatial stream at 20 MHz, return an error.
+///
+/// # Examples
+///
+/// ```
+/// use radiotap::field::ext::{vht_rate, Bandwidth, GuardInterval};
+///
+/// let bw = Bandwidth::new(4).unwrap();
+/// assert_eq!(vht_rate(9, bw, GuardInterval::Short, 1).unwrap(), 433.3);
+/// assert_eq!(vht_rate(7, bw, GuardInterval::Long, 4).unwrap(), 1170.0);
+/// assert!(vht_rate(6, bw, GuardInterval::Long, 3).is_err());
+/// ```
 pub fn vht_rate(index: u8, bw: Bandwidth, gi: GuardInterval, nss: u8) -> Result<f32> {
-    if index > 9 || nss > 8 {
+    if index > 9 || !(1..=8).contains(&nss) {
         return Err(Error::InvalidFormat);
     }
 
-    let b = match bw.bandwidth {
+    let b = match bw.width() {
         20 => 0,
         40 => 2,
         80 => 4,
@@ -166,8 +254,97 @@ pub fn vht_rate(index: u8, bw: Bandwidth, gi: GuardInterval, nss: u8) -> Result<
     Ok(rate)
 }
 
+/// The number of coded bits per subcarrier and the coding rate of the 802.11ax
+/// MCS 0 to 11.
+const HE_MODULATION: [(u32, u32, u32); 12] = [
+    (1, 1, 2),
+    (2, 1, 2),
+    (2, 3, 4),
+    (4, 1, 2),
+    (4, 3, 4),
+    (6, 2, 3),
+    (6, 3, 4),
+    (6, 5, 6),
+    (8, 3, 4),
+    (8, 5, 6),
+    (10, 3, 4),
+    (10, 5, 6),
+];
+
+/// Returns the 802.11ax data rate in Mbps based on the MCS index, bandwidth
+/// or resource unit size, guard interval, number of spatial streams, and
+/// whether dual carrier modulation was used.
+///
+/// MCS 0 to 11 with 1 to 8 spatial streams are supported. The rate is rounded
+/// to 0.1 Mbps. The LTF size does not change the data rate. DCM halves the
+/// rate and is only defined for MCS 0, 1, 3, and 4 with 1 or 2 spatial
+/// streams, other combinations return an error.
+///
+/// # Examples
+///
+/// ```
+/// use radiotap::field::ext::{he_rate, Bandwidth, HEBandwidth, HEGuardInterval, RUSize};
+///
+/// let bw = HEBandwidth::Bandwidth(Bandwidth::new(0).unwrap());
+/// assert_eq!(he_rate(11, bw, HEGuardInterval::GI800, 1, false).unwrap(), 143.4);
+/// let ru = HEBandwidth::RU(RUSize::RU26);
+/// assert_eq!(he_rate(0, ru, HEGuardInterval::GI3200, 1, true).unwrap(), 0.4);
+/// assert!(he_rate(2, ru, HEGuardInterval::GI800, 1, true).is_err());
+/// ```
+pub fn he_rate(index: u8, bw: HEBandwidth, gi: HEGuardInterval, nss: u8, dcm: bool) -> Result<f32> {
+    if !(1..=8).contains(&nss) {
+        return Err(Error::InvalidFormat);
+    }
+
+    let &(bits, num, den) = HE_MODULATION
+        .get(usize::from(index))
+        .ok_or(Error::InvalidFormat)?;
+
+    if dcm && (!matches!(index, 0 | 1 | 3 | 4) || nss > 2) {
+        return Err(Error::InvalidFormat);
+    }
+
+    let size = match bw {
+        HEBandwidth::Bandwidth(bw) => match bw.bandwidth {
+            20 => RUSize::RU242,
+            40 => RUSize::RU484,
+            80 => RUSize::RU996,
+            160 => RUSize::RU2x996,
+            _ => return Err(Error::InvalidFormat),
+        },
+        HEBandwidth::RU(size) => size,
+    };
+
+    let subcarriers = match size {
+        RUSize::RU26 => 24,
+        RUSize::RU52 => 48,
+        RUSize::RU106 => 102,
+        RUSize::RU242 => 234,
+        RUSize::RU484 => 468,
+        RUSize::RU996 => 980,
+        RUSize::RU2x996 => 1960,
+    };
+
+    // The symbol duration in units of 100 ns, 12.8 µs plus the guard interval
+    let symbol = match gi {
+        HEGuardInterval::GI800 => 136,
+        HEGuardInterval::GI1600 => 144,
+        HEGuardInterval::GI3200 => 160,
+    };
+
+    // The rate in units of 100 kbps, rounded to the nearest
+    let bits = subcarriers * bits * num * u32::from(nss) * 100;
+    let time = den * symbol * if dcm { 2 } else { 1 };
+    let rate = (2 * bits + time) / (2 * time);
+
+    Ok(rate as f32 / 10.0)
+}
+
 /// Flags describing the channel.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ChannelFlags {
     /// Turbo channel.
     pub turbo: bool,
@@ -189,6 +366,9 @@ pub struct ChannelFlags {
 
 /// Extended flags describing the channel.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct XChannelFlags {
     /// Turbo channel.
     pub turbo: bool,
@@ -224,6 +404,9 @@ pub struct XChannelFlags {
 
 /// Struct containing the bandwidth, sideband, and sideband index.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Bandwidth {
     /// The bandwidth in MHz.
     pub bandwidth: u8,
@@ -272,11 +455,192 @@ impl Bandwidth {
             sideband_index,
         })
     }
+
+    /// Returns the width in MHz occupied by the data, the sideband if the
+    /// data only occupied part of the channel.
+    pub fn width(&self) -> u8 {
+        self.sideband.unwrap_or(self.bandwidth)
+    }
+
+    /// Returns the center frequency in MHz of the part of the channel the data
+    /// occupied, given the frequency of a 20 MHz channel within it, usually
+    /// from the [Channel](../struct.Channel.html) field.
+    ///
+    /// Returns `None` if the channel the frequency belongs to is ambiguous,
+    /// see [center_freq](../../util/channel/fn.center_freq.html).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::field::ext::Bandwidth;
+    ///
+    /// // 80 MHz channel 42, 20 MHz in the upper half of the 40 MHz channel 38
+    /// let bw = Bandwidth::new(8).unwrap();
+    /// assert_eq!(bw.width(), 20);
+    /// assert_eq!(bw.center_freq(5180), Some(5200));
+    /// ```
+    pub fn center_freq(&self, freq: u16) -> Option<u16> {
+        let bandwidth = u16::from(self.bandwidth);
+        let center = channel::center_freq(freq, bandwidth)?;
+        Some(match (self.sideband, self.sideband_index) {
+            (Some(sideband), Some(index)) => {
+                let sideband = u16::from(sideband);
+                center - bandwidth / 2 + u16::from(index) * sideband + sideband / 2
+            }
+            _ => center,
+        })
+    }
+
+    /// Returns the Radiotap encoding of the bandwidth, the inverse of
+    /// [new](#method.new).
+    pub fn value(&self) -> Result<u8> {
+        Ok(match (self.bandwidth, self.sideband, self.sideband_index) {
+            (20, None, None) => 0,
+            (40, None, None) => 1,
+            (40, Some(20), Some(0)) => 2,
+            (40, Some(20), Some(1)) => 3,
+            (80, None, None) => 4,
+            (80, Some(40), Some(0)) => 5,
+            (80, Some(40), Some(1)) => 6,
+            (80, Some(20), Some(0)) => 7,
+            (80, Some(20), Some(1)) => 8,
+            (80, Some(20), Some(2)) => 9,
+            (80, Some(20), Some(3)) => 10,
+            (160, None, None) => 11,
+            (160, Some(80), Some(0)) => 12,
+            (160, Some(80), Some(1)) => 13,
+            (160, Some(40), Some(0)) => 14,
+            (160, Some(40), Some(1)) => 15,
+            (160, Some(40), Some(2)) => 16,
+            (160, Some(40), Some(3)) => 17,
+            (160, Some(20), Some(0)) => 18,
+            (160, Some(20), Some(1)) => 19,
+            (160, Some(20), Some(2)) => 20,
+            (160, Some(20), Some(3)) => 21,
+            (160, Some(20), Some(4)) => 22,
+            (160, Some(20), Some(5)) => 23,
+            (160, Some(20), Some(6)) => 24,
+            (160, Some(20), Some(7)) => 25,
+            _ => return Err(Error::InvalidFormat),
+        })
+    }
+}
+
+/// The size of an 802.11ax resource unit, in tones.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum RUSize {
+    RU26,
+    RU52,
+    RU106,
+    RU242,
+    RU484,
+    RU996,
+    RU2x996,
+}
+
+/// A resource unit assigned within a 20 MHz subchannel.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ResourceUnit {
+    /// The size of the resource unit.
+    pub size: RUSize,
+    /// The number of users multiplexed on the resource unit, 0 if the resource
+    /// unit has no users in this content channel.
+    pub users: u8,
+}
+
+/// A decoded 802.11ax RU allocation subfield, describing how a 20 MHz
+/// subchannel is divided into resource units.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RUAllocation {
+    /// The resource units, ordered from the lowest frequency.
+    pub units: Vec<ResourceUnit>,
+}
+
+#[cfg(feature = "alloc")]
+impl RUAllocation {
+    pub fn new(value: u8) -> Result<RUAllocation> {
+        let ru = |size, users| ResourceUnit { size, users };
+        let ru106 = |users| ru(RUSize::RU106, users + 1);
+        // The possible divisions of each half of the subchannel into 26 and
+        // 52-tone resource units
+        let half = |value: u8| match value & 0x03 {
+            0 => vec![ru(RUSize::RU26, 1); 4],
+            1 => vec![
+                ru(RUSize::RU26, 1),
+                ru(RUSize::RU26, 1),
+                ru(RUSize::RU52, 1),
+            ],
+            2 => vec![
+                ru(RUSize::RU52, 1),
+                ru(RUSize::RU26, 1),
+                ru(RUSize::RU26, 1),
+            ],
+            _ => vec![ru(RUSize::RU52, 1); 2],
+        };
+        let center = ru(RUSize::RU26, 1);
+
+        let units = match value {
+            0..=15 => {
+                let mut units = half(value >> 2);
+                units.push(center);
+                units.extend(half(value));
+                units
+            }
+            16..=23 => {
+                let mut units = half(3);
+                units.push(ru106(value & 0x07));
+                units
+            }
+            24..=31 => {
+                let mut units = vec![ru106(value & 0x07)];
+                units.extend(half(3));
+                units
+            }
+            32..=63 => {
+                let mut units = half((value - 32) >> 3);
+                units.push(center);
+                units.push(ru106(value & 0x07));
+                units
+            }
+            64..=95 => {
+                let mut units = vec![ru106(value & 0x07), center];
+                units.extend(half((value - 64) >> 3));
+                units
+            }
+            96..=111 => vec![ru106((value & 0x0c) >> 2), ru106(value & 0x03)],
+            112 => vec![ru(RUSize::RU52, 1); 4],
+            113 => vec![ru(RUSize::RU242, 0)],
+            114 => vec![ru(RUSize::RU484, 0)],
+            115 => vec![ru(RUSize::RU996, 0)],
+            128..=191 => vec![ru106((value & 0x38) >> 3), center, ru106(value & 0x07)],
+            192..=199 => vec![ru(RUSize::RU242, (value & 0x07) + 1)],
+            200..=207 => vec![ru(RUSize::RU484, (value & 0x07) + 1)],
+            208..=215 => vec![ru(RUSize::RU996, (value & 0x07) + 1)],
+            224..=231 => vec![ru(RUSize::RU2x996, (value & 0x07) + 1)],
+            _ => {
+                return Err(Error::InvalidFormat);
+            }
+        };
+        Ok(RUAllocation { units })
+    }
 }
 
 /// Represents a [VHT](../struct.VHT.html) user, the [VHT](../struct.VHT.html)
 /// encodes the MCS and NSS for up to four users.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct VHTUser {
     /// The 802.11ac MCS index.
     pub index: u8,
@@ -284,14 +648,164 @@ pub struct VHTUser {
     pub fec: FEC,
     /// Number of spatial streams (range 1 - 8).
     pub nss: u8,
-    /// Number of space-time streams (range 1 - 16).
+    /// Number of space-time streams (range 1 - 16), twice the spatial
+    /// streams with STBC.
     pub nsts: u8,
-    /// The datarate in Mbps
+    /// The datarate in Mbps, see [vht_rate](fn.vht_rate.html). Only set when
+    /// the bandwidth and guard interval are known and the combination of MCS
+    /// index, bandwidth, and spatial streams is valid.
     pub datarate: Option<f32>,
 }
 
+/// A user field of the HE-SIG-B field, located from the RU allocation
+/// subfields of an [HEMU](../struct.HEMU.html) field.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct HEMUUser {
+    /// The content channel carrying the user field, 1 or 2.
+    pub channel: u8,
+    /// The position of the user field within the content channel, as in
+    /// [HEMUOtherUser](../struct.HEMUOtherUser.html).
+    pub position: u8,
+    /// The size of the resource unit assigned to the user.
+    pub size: RUSize,
+    /// Whether the resource unit is shared with other users using MU-MIMO.
+    pub mu_mimo: bool,
+}
+
+/// The antenna, signal, and noise of a single receive chain, reported in a
+/// radiotap namespace of its own.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct AntennaChain {
+    /// The antenna index of the chain.
+    pub antenna: Option<u8>,
+    /// The signal of the chain in dBm.
+    pub signal: Option<i8>,
+    /// The noise of the chain in dBm.
+    pub noise: Option<i8>,
+}
+
+/// Represents an 802.11be user, decoded from one of the user info words of
+/// the EHT field.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct EHTUser {
+    /// The STA-ID of the user.
+    pub sta_id: Option<u16>,
+    /// The 802.11be MCS index.
+    pub index: Option<u8>,
+    /// The FEC type.
+    pub fec: Option<FEC>,
+    /// Number of spatial streams, for non-MU-MIMO users.
+    pub nss: Option<u8>,
+    /// Whether the user was beamformed, for non-MU-MIMO users.
+    pub beamformed: Option<bool>,
+    /// The spatial configuration, for MU-MIMO users.
+    pub spatial_config: Option<u8>,
+    /// Whether the captured data belongs to this user.
+    pub data_captured: bool,
+}
+
+impl EHTUser {
+    pub fn new(value: u32) -> EHTUser {
+        let known = |flag: u32| value & flag != 0;
+        EHTUser {
+            sta_id: Some(((value >> 8) & 0x07ff) as u16).filter(|_| known(0x01)),
+            index: Some(((value >> 20) & 0x0f) as u8).filter(|_| known(0x02)),
+            fec: Some(if known(0x0008_0000) {
+                FEC::LDPC
+            } else {
+                FEC::BCC
+            })
+            .filter(|_| known(0x04)),
+            nss: Some(((value >> 24) & 0x0f) as u8).filter(|_| known(0x10)),
+            beamformed: Some(known(0x2000_0000)).filter(|_| known(0x20)),
+            spatial_config: Some(((value >> 24) & 0x3f) as u8).filter(|_| known(0x40)),
+            data_captured: known(0x80),
+        }
+    }
+
+    /// Returns the user info word, the inverse of [new](#method.new).
+    pub fn value(&self) -> u32 {
+        let mut value = 0;
+        if let Some(sta_id) = self.sta_id {
+            value |= 0x01 | u32::from(sta_id & 0x07ff) << 8;
+        }
+        if let Some(index) = self.index {
+            value |= 0x02 | u32::from(index & 0x0f) << 20;
+        }
+        if let Some(fec) = self.fec {
+            value |= 0x04;
+            if fec == FEC::LDPC {
+                value |= 0x0008_0000;
+            }
+        }
+        if let Some(nss) = self.nss {
+            value |= 0x10 | u32::from(nss & 0x0f) << 24;
+        }
+        if let Some(beamformed) = self.beamformed {
+            value |= 0x20;
+            if beamformed {
+                value |= 0x2000_0000;
+            }
+        }
+        if let Some(spatial_config) = self.spatial_config {
+            value |= 0x40 | u32::from(spatial_config & 0x3f) << 24;
+        }
+        if self.data_captured {
+            value |= 0x80;
+        }
+        value
+    }
+}
+
+/// An iterator over the little endian user info words of the EHT field,
+/// yielding an [EHTUser](struct.EHTUser.html) for each.
+#[derive(Clone, Debug)]
+pub struct EHTUsers<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> EHTUsers<'a> {
+    pub fn new(data: &'a [u8]) -> EHTUsers<'a> {
+        EHTUsers { data }
+    }
+}
+
+impl<'a> Iterator for EHTUsers<'a> {
+    type Item = EHTUser;
+
+    fn next(&mut self) -> Option<EHTUser> {
+        if self.data.len() < 4 {
+            return None;
+        }
+        let (word, rest) = self.data.split_at(4);
+        self.data = rest;
+        Some(EHTUser::new(u32::from_le_bytes([
+            word[0], word[1], word[2], word[3],
+        ])))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.data.len() / 4;
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for EHTUsers<'a> {}
+
 /// The guard interval.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum GuardInterval {
     /// 800 ns.
     Long,
@@ -301,6 +815,9 @@ pub enum GuardInterval {
 
 /// Forward error correction type.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum FEC {
     /// Binary convolutional coding.
     BCC,
@@ -310,13 +827,260 @@ pub enum FEC {
 
 /// The HT format.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum HTFormat {
     Mixed,
     Greenfield,
 }
 
+/// The HE PPDU format.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum HEFormat {
+    /// Single user.
+    SU,
+    /// Extended range single user.
+    ExtSU,
+    /// Multi user.
+    MU,
+    /// Trigger based.
+    Trig,
+}
+
+impl HEFormat {
+    pub fn new(value: u8) -> Result<HEFormat> {
+        Ok(match value {
+            0 => HEFormat::SU,
+            1 => HEFormat::ExtSU,
+            2 => HEFormat::MU,
+            3 => HEFormat::Trig,
+            _ => {
+                return Err(Error::InvalidFormat);
+            }
+        })
+    }
+
+    /// Returns the Radiotap encoding of the HE PPDU format.
+    pub fn value(self) -> u8 {
+        match self {
+            HEFormat::SU => 0,
+            HEFormat::ExtSU => 1,
+            HEFormat::MU => 2,
+            HEFormat::Trig => 3,
+        }
+    }
+}
+
+/// The 802.11ax guard interval.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum HEGuardInterval {
+    /// 800 ns.
+    GI800,
+    /// 1600 ns.
+    GI1600,
+    /// 3200 ns.
+    GI3200,
+}
+
+impl HEGuardInterval {
+    pub fn new(value: u8) -> Result<HEGuardInterval> {
+        Ok(match value {
+            0 => HEGuardInterval::GI800,
+            1 => HEGuardInterval::GI1600,
+            2 => HEGuardInterval::GI3200,
+            _ => {
+                return Err(Error::InvalidFormat);
+            }
+        })
+    }
+
+    /// Returns the Radiotap encoding of the guard interval.
+    pub fn value(self) -> u8 {
+        match self {
+            HEGuardInterval::GI800 => 0,
+            HEGuardInterval::GI1600 => 1,
+            HEGuardInterval::GI3200 => 2,
+        }
+    }
+}
+
+/// The size of the 802.11ax long training field symbols.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum LTFSize {
+    /// 1x LTF.
+    X1,
+    /// 2x LTF.
+    X2,
+    /// 4x LTF.
+    X4,
+}
+
+impl LTFSize {
+    /// Returns the LTF size, or `None` if it is unknown.
+    pub fn new(value: u8) -> Option<LTFSize> {
+        match value {
+            1 => Some(LTFSize::X1),
+            2 => Some(LTFSize::X2),
+            3 => Some(LTFSize::X4),
+            _ => None,
+        }
+    }
+
+    /// Returns the Radiotap encoding of the LTF size.
+    pub fn value(self) -> u8 {
+        match self {
+            LTFSize::X1 => 1,
+            LTFSize::X2 => 2,
+            LTFSize::X4 => 3,
+        }
+    }
+}
+
+/// The bandwidth of an 802.11ax PPDU, or the size of the resource unit the
+/// data was received on.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum HEBandwidth {
+    /// The data occupied the whole channel of this bandwidth, 160 MHz
+    /// includes 80+80 MHz.
+    Bandwidth(Bandwidth),
+    /// The data occupied a resource unit of this size.
+    RU(RUSize),
+}
+
+impl HEBandwidth {
+    pub fn new(value: u8) -> Result<HEBandwidth> {
+        Ok(match value {
+            0 => HEBandwidth::Bandwidth(Bandwidth::new(0)?),
+            1 => HEBandwidth::Bandwidth(Bandwidth::new(1)?),
+            2 => HEBandwidth::Bandwidth(Bandwidth::new(4)?),
+            3 => HEBandwidth::Bandwidth(Bandwidth::new(11)?),
+            4 => HEBandwidth::RU(RUSize::RU26),
+            5 => HEBandwidth::RU(RUSize::RU52),
+            6 => HEBandwidth::RU(RUSize::RU106),
+            7 => HEBandwidth::RU(RUSize::RU242),
+            8 => HEBandwidth::RU(RUSize::RU484),
+            9 => HEBandwidth::RU(RUSize::RU996),
+            10 => HEBandwidth::RU(RUSize::RU2x996),
+            _ => {
+                return Err(Error::InvalidFormat);
+            }
+        })
+    }
+
+    /// Returns the width in MHz occupied by the data, or `None` for a resource
+    /// unit, whose position is given by the RU offset.
+    pub fn width(&self) -> Option<u8> {
+        match self {
+            HEBandwidth::Bandwidth(bw) => Some(bw.width()),
+            HEBandwidth::RU(_) => None,
+        }
+    }
+
+    /// Returns the center frequency in MHz of the channel the data occupied,
+    /// see [Bandwidth::center_freq](struct.Bandwidth.html#method.center_freq),
+    /// or `None` for a resource unit.
+    pub fn center_freq(&self, freq: u16) -> Option<u16> {
+        match self {
+            HEBandwidth::Bandwidth(bw) => bw.center_freq(freq),
+            HEBandwidth::RU(_) => None,
+        }
+    }
+
+    /// Returns the Radiotap encoding of the bandwidth, the inverse of
+    /// [new](#method.new).
+    pub fn value(&self) -> Result<u8> {
+        Ok(match self {
+            HEBandwidth::Bandwidth(bw) => match bw.value()? {
+                0 => 0,
+                1 => 1,
+                4 => 2,
+                11 => 3,
+                _ => return Err(Error::InvalidFormat),
+            },
+            HEBandwidth::RU(RUSize::RU26) => 4,
+            HEBandwidth::RU(RUSize::RU52) => 5,
+            HEBandwidth::RU(RUSize::RU106) => 6,
+            HEBandwidth::RU(RUSize::RU242) => 7,
+            HEBandwidth::RU(RUSize::RU484) => 8,
+            HEBandwidth::RU(RUSize::RU996) => 9,
+            HEBandwidth::RU(RUSize::RU2x996) => 10,
+        })
+    }
+}
+
+/// The bandwidth of an 802.11be PPDU.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum EHTBandwidth {
+    MHz20,
+    MHz40,
+    MHz80,
+    MHz160,
+    /// 320 MHz using channelization 1.
+    MHz320_1,
+    /// 320 MHz using channelization 2.
+    MHz320_2,
+}
+
+impl EHTBandwidth {
+    pub fn new(value: u8) -> Result<EHTBandwidth> {
+        Ok(match value {
+            0 => EHTBandwidth::MHz20,
+            1 => EHTBandwidth::MHz40,
+            2 => EHTBandwidth::MHz80,
+            3 => EHTBandwidth::MHz160,
+            4 => EHTBandwidth::MHz320_1,
+            5 => EHTBandwidth::MHz320_2,
+            _ => {
+                return Err(Error::InvalidFormat);
+            }
+        })
+    }
+
+    /// Returns the Radiotap encoding of the bandwidth.
+    pub fn value(self) -> u8 {
+        match self {
+            EHTBandwidth::MHz20 => 0,
+            EHTBandwidth::MHz40 => 1,
+            EHTBandwidth::MHz80 => 2,
+            EHTBandwidth::MHz160 => 3,
+            EHTBandwidth::MHz320_1 => 4,
+            EHTBandwidth::MHz320_2 => 5,
+        }
+    }
+
+    /// Returns the bandwidth in MHz.
+    pub fn mhz(self) -> u16 {
+        match self {
+            EHTBandwidth::MHz20 => 20,
+            EHTBandwidth::MHz40 => 40,
+            EHTBandwidth::MHz80 => 80,
+            EHTBandwidth::MHz160 => 160,
+            EHTBandwidth::MHz320_1 | EHTBandwidth::MHz320_2 => 320,
+        }
+    }
+}
+
 /// The time unit of the [Timestamp](../struct.Timestamp.html).
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum TimeUnit {
     Milliseconds,
     Microseconds,
@@ -334,10 +1098,22 @@ impl TimeUnit {
             }
         })
     }
+
+    /// Returns the Radiotap encoding of the time unit.
+    pub fn value(self) -> u8 {
+        match self {
+            TimeUnit::Milliseconds => 0,
+            TimeUnit::Microseconds => 1,
+            TimeUnit::Nanoseconds => 2,
+        }
+    }
 }
 
 /// The sampling position of the [Timestamp](../struct.Timestamp.html).
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum SamplingPosition {
     StartMPDU,
     StartPLCP,
@@ -357,4 +1133,183 @@ impl SamplingPosition {
             _ => return Err(Error::InvalidFormat),
         })
     }
+
+    /// Returns the Radiotap encoding of the sampling position.
+    pub fn value(self) -> u8 {
+        match self {
+            SamplingPosition::StartMPDU => 0,
+            SamplingPosition::StartPLCP => 1,
+            SamplingPosition::EndPPDU => 2,
+            SamplingPosition::EndMPDU => 3,
+            SamplingPosition::Unknown => 15,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    fn sizes(value: u8) -> Vec<RUSize> {
+        RUAllocation::new(value)
+            .unwrap()
+            .units
+            .iter()
+            .map(|unit| unit.size)
+            .collect()
+    }
+
+    #[test]
+    fn ht_rates() {
+        let bw20 = Bandwidth::new(0).unwrap();
+        let bw40 = Bandwidth::new(1).unwrap();
+        let bw20u = Bandwidth::new(3).unwrap();
+        assert_eq!(ht_rate(0, bw20, GuardInterval::Long).unwrap(), 6.5);
+        assert_eq!(ht_rate(31, bw40, GuardInterval::Short).unwrap(), 600.0);
+        assert_eq!(ht_rate(24, bw20, GuardInterval::Short).unwrap(), 28.9);
+        assert_eq!(ht_rate(32, bw40, GuardInterval::Long).unwrap(), 6.0);
+        assert_eq!(ht_rate(33, bw20, GuardInterval::Long).unwrap(), 39.0);
+        assert_eq!(ht_rate(76, bw20, GuardInterval::Long).unwrap(), 214.5);
+        assert_eq!(ht_rate(7, bw20u, GuardInterval::Long).unwrap(), 65.0);
+        assert!(ht_rate(32, bw20, GuardInterval::Long).is_err());
+        assert!(ht_rate(77, bw20, GuardInterval::Long).is_err());
+        assert!(ht_rate(0, Bandwidth::new(4).unwrap(), GuardInterval::Long).is_err());
+    }
+
+    #[test]
+    fn vht_rates() {
+        let bw20 = Bandwidth::new(0).unwrap();
+        let bw80 = Bandwidth::new(4).unwrap();
+        let bw160 = Bandwidth::new(11).unwrap();
+        let bw20u = Bandwidth::new(3).unwrap();
+        assert_eq!(vht_rate(0, bw20, GuardInterval::Long, 1).unwrap(), 6.5);
+        assert_eq!(vht_rate(9, bw20, GuardInterval::Long, 3).unwrap(), 260.0);
+        assert_eq!(vht_rate(6, bw20, GuardInterval::Short, 2).unwrap(), 130.0);
+        assert_eq!(vht_rate(8, bw20, GuardInterval::Long, 8).unwrap(), 624.0);
+        assert_eq!(vht_rate(9, bw160, GuardInterval::Short, 8).unwrap(), 6933.3);
+        assert_eq!(vht_rate(7, bw20u, GuardInterval::Long, 1).unwrap(), 65.0);
+        assert!(vht_rate(9, bw20, GuardInterval::Long, 1).is_err());
+        assert!(vht_rate(6, bw80, GuardInterval::Long, 7).is_err());
+        assert!(vht_rate(9, bw80, GuardInterval::Long, 6).is_err());
+        assert!(vht_rate(9, bw160, GuardInterval::Long, 3).is_err());
+        assert!(vht_rate(0, bw20, GuardInterval::Long, 0).is_err());
+        assert!(vht_rate(10, bw20, GuardInterval::Long, 1).is_err());
+        assert!(vht_rate(0, bw20, GuardInterval::Long, 9).is_err());
+    }
+
+    #[test]
+    fn he_rates() {
+        let bw20 = HEBandwidth::Bandwidth(Bandwidth::new(0).unwrap());
+        let bw160 = HEBandwidth::Bandwidth(Bandwidth::new(11).unwrap());
+        let ru106 = HEBandwidth::RU(RUSize::RU106);
+        assert_eq!(
+            he_rate(0, bw20, HEGuardInterval::GI800, 1, false).unwrap(),
+            8.6
+        );
+        assert_eq!(
+            he_rate(7, bw20, HEGuardInterval::GI1600, 2, false).unwrap(),
+            162.5
+        );
+        assert_eq!(
+            he_rate(11, bw160, HEGuardInterval::GI800, 8, false).unwrap(),
+            9607.8
+        );
+        assert_eq!(
+            he_rate(11, bw160, HEGuardInterval::GI3200, 1, false).unwrap(),
+            1020.8
+        );
+        assert_eq!(
+            he_rate(4, ru106, HEGuardInterval::GI800, 2, true).unwrap(),
+            22.5
+        );
+        assert_eq!(
+            he_rate(
+                9,
+                HEBandwidth::RU(RUSize::RU2x996),
+                HEGuardInterval::GI800,
+                1,
+                false
+            )
+            .unwrap(),
+            he_rate(9, bw160, HEGuardInterval::GI800, 1, false).unwrap()
+        );
+        assert!(he_rate(12, bw20, HEGuardInterval::GI800, 1, false).is_err());
+        assert!(he_rate(0, bw20, HEGuardInterval::GI800, 0, false).is_err());
+        assert!(he_rate(0, bw20, HEGuardInterval::GI800, 9, false).is_err());
+        assert!(he_rate(5, bw20, HEGuardInterval::GI800, 1, true).is_err());
+        assert!(he_rate(1, bw20, HEGuardInterval::GI800, 3, true).is_err());
+    }
+
+    #[test]
+    fn bandwidth_values() {
+        for value in 0..=25 {
+            assert_eq!(Bandwidth::new(value).unwrap().value().unwrap(), value);
+        }
+        let bw = Bandwidth {
+            bandwidth: 80,
+            sideband: Some(40),
+            sideband_index: Some(2),
+        };
+        assert!(bw.value().is_err());
+
+        for value in 0..=10 {
+            assert_eq!(HEBandwidth::new(value).unwrap().value().unwrap(), value);
+        }
+        let bw = HEBandwidth::Bandwidth(Bandwidth::new(2).unwrap());
+        assert!(bw.value().is_err());
+    }
+
+    #[test]
+    fn ru_allocation() {
+        use RUSize::*;
+
+        assert_eq!(sizes(0), vec![RU26; 9]);
+        assert_eq!(
+            sizes(1),
+            vec![RU26, RU26, RU26, RU26, RU26, RU26, RU26, RU52]
+        );
+        assert_eq!(
+            sizes(4),
+            vec![RU26, RU26, RU52, RU26, RU26, RU26, RU26, RU26]
+        );
+        assert_eq!(sizes(15), vec![RU52, RU52, RU26, RU52, RU52]);
+        assert_eq!(sizes(18), vec![RU52, RU52, RU106]);
+        assert_eq!(sizes(40), vec![RU26, RU26, RU52, RU26, RU106]);
+        assert_eq!(sizes(80), vec![RU106, RU26, RU52, RU26, RU26]);
+        assert_eq!(sizes(112), vec![RU52; 4]);
+
+        let allocation = RUAllocation::new(0b1001_1010).unwrap();
+        assert_eq!(allocation.units[0].users, 4);
+        assert_eq!(allocation.units[2].users, 3);
+        assert_eq!(RUAllocation::new(113).unwrap().units[0].users, 0);
+        assert_eq!(RUAllocation::new(194).unwrap().units[0].users, 3);
+        assert!(RUAllocation::new(116).is_err());
+    }
+
+    #[test]
+    fn eht_users() {
+        let data = [0xbf, 0x2a, 0x18, 0x22, 0x80, 0x00, 0x00, 0x00, 0xff];
+
+        let users: Vec<_> = EHTUsers::new(&data).collect();
+        assert_eq!(users.len(), 2);
+        assert_eq!(
+            users[0],
+            EHTUser {
+                sta_id: Some(42),
+                index: Some(1),
+                fec: Some(FEC::LDPC),
+                nss: Some(2),
+                beamformed: Some(true),
+                spatial_config: None,
+                data_captured: true,
+            }
+        );
+        assert_eq!(
+            users[1],
+            EHTUser {
+                data_captured: true,
+                ..Default::default()
+            }
+        );
+    }
 }
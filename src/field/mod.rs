@@ -1,17 +1,70 @@
 //! Radiotap field definitions and parsers.
 
 pub mod ext;
+#[cfg(feature = "oui-names")]
+mod oui;
+mod reader;
+pub mod tlv;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use byteorder::LE;
+use core::{convert::TryFrom, fmt, iter::FusedIterator};
+
+pub(crate) use self::reader::Reader;
+use crate::{
+    bits::BitOps,
+    field::ext::*,
+    io::{Write, WriteBytesExt},
+    util::power::{Db, Dbm},
+    Error, Result,
+};
+
+/// An IEEE organizationally unique identifier, displayed as `aa:bb:cc`.
+///
+/// ```
+/// use radiotap::field::Oui;
+///
+/// assert_eq!(Oui([0x00, 0x17, 0xf2]).to_string(), "00:17:f2");
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Oui(pub [u8; 3]);
+
+impl Oui {
+    /// Returns the name of the manufacturer the OUI is assigned to, if it is
+    /// well known.
+    ///
+    /// Enabled with the `oui-names` feature.
+    #[cfg(feature = "oui-names")]
+    pub fn manufacturer(&self) -> Option<&'static str> {
+        oui::MANUFACTURERS
+            .binary_search_by_key(&self.0, |(oui, _)| *oui)
+            .ok()
+            .map(|i| oui::MANUFACTURERS[i].1)
+    }
+}
 
-use bitops::BitOps;
-use byteorder::{ReadBytesExt, LE};
-use std::io::{Cursor, Read};
-
-use crate::{field::ext::*, Error, Result};
+impl From<[u8; 3]> for Oui {
+    fn from(oui: [u8; 3]) -> Oui {
+        Oui(oui)
+    }
+}
 
-type Oui = [u8; 3];
+impl fmt::Display for Oui {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02x}:{:02x}:{:02x}", self.0[0], self.0[1], self.0[2])
+    }
+}
 
 /// The type of Radiotap field.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub enum Kind {
     TSFT,
     Flags,
@@ -36,9 +89,70 @@ pub enum Kind {
     AMPDUStatus,
     VHT,
     Timestamp,
+    HE,
+    HEMU,
+    HEMUOtherUser,
+    LSig,
+    /// A TLV record, identified by its type.
+    TLV(Option<u16>),
+    /// The start of another radiotap namespace, whose fields may repeat those
+    /// of the previous namespaces. It has no data.
+    RadiotapNamespace,
     VendorNamespace(Option<VendorNamespace>),
+    /// A field this crate does not know how to decode, identified by its
+    /// present bit.
+    Unknown(u8),
+}
+
+/// The namespace defining a kind of field, see
+/// [Kind::namespace](enum.Kind.html#method.namespace).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Namespace {
+    /// The radiotap namespace, the field has a present bit.
+    Radiotap,
+    /// The radiotap namespace, the field is only written as a TLV record.
+    Tlv,
+    /// A vendor namespace, its fields are defined by the vendor.
+    Vendor,
 }
 
+/// Every kind of field this crate decodes, in the order of their present bits
+/// followed by the fields only written as TLV records.
+const ALL_KINDS: [Kind; 29] = [
+    Kind::TSFT,
+    Kind::Flags,
+    Kind::Rate,
+    Kind::Channel,
+    Kind::FHSS,
+    Kind::AntennaSignal,
+    Kind::AntennaNoise,
+    Kind::LockQuality,
+    Kind::TxAttenuation,
+    Kind::TxAttenuationDb,
+    Kind::TxPower,
+    Kind::Antenna,
+    Kind::AntennaSignalDb,
+    Kind::AntennaNoiseDb,
+    Kind::RxFlags,
+    Kind::TxFlags,
+    Kind::RTSRetries,
+    Kind::DataRetries,
+    Kind::XChannel,
+    Kind::MCS,
+    Kind::AMPDUStatus,
+    Kind::VHT,
+    Kind::Timestamp,
+    Kind::HE,
+    Kind::HEMU,
+    Kind::HEMUOtherUser,
+    Kind::LSig,
+    Kind::TLV(Some(tlv::U_SIG)),
+    Kind::TLV(Some(tlv::EHT)),
+];
+
 impl Kind {
     pub fn new(value: u8) -> Result<Kind> {
         Ok(match value {
@@ -65,17 +179,123 @@ impl Kind {
             20 => Kind::AMPDUStatus,
             21 => Kind::VHT,
             22 => Kind::Timestamp,
-            _ => {
-                return Err(Error::UnsupportedField);
-            }
+            23 => Kind::HE,
+            24 => Kind::HEMU,
+            25 => Kind::HEMUOtherUser,
+            27 => Kind::LSig,
+            // We'll figure out the types of the records later, just use none
+            28 => Kind::TLV(None),
+            _ => Kind::Unknown(value),
         })
     }
 
+    /// Returns the kind with the given present bit of the radiotap namespace,
+    /// or `None` if the bit is not defined.
+    ///
+    /// Unlike [new](#method.new), the namespace switches are also returned,
+    /// and undefined bits are not returned as `Unknown`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::field::Kind;
+    ///
+    /// assert_eq!(Kind::from_bit(21), Some(Kind::VHT));
+    /// assert_eq!(Kind::from_bit(29), Some(Kind::RadiotapNamespace));
+    /// assert_eq!(Kind::from_bit(26), None);
+    /// ```
+    pub const fn from_bit(bit: u8) -> Option<Kind> {
+        Some(match bit {
+            0..=25 => ALL_KINDS[bit as usize],
+            27 => Kind::LSig,
+            28 => Kind::TLV(None),
+            29 => Kind::RadiotapNamespace,
+            30 => Kind::VendorNamespace(None),
+            _ => return None,
+        })
+    }
+
+    /// Returns every kind of field this crate decodes, in the order of their
+    /// present bits followed by the fields only written as TLV records, such
+    /// as [U_SIG](tlv/constant.U_SIG.html).
+    ///
+    /// The namespace switches and TLV records of other types are not
+    /// included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::field::{Kind, Namespace};
+    ///
+    /// let tlv_only = Kind::all().iter().filter(|kind| kind.namespace() == Namespace::Tlv);
+    /// assert_eq!(tlv_only.count(), 2);
+    /// ```
+    pub const fn all() -> &'static [Kind] {
+        &ALL_KINDS
+    }
+
+    /// Returns the namespace defining the field.
+    ///
+    /// TLV records of fields that have a present bit belong to the radiotap
+    /// namespace, like the present bit.
+    pub const fn namespace(self) -> Namespace {
+        match self {
+            Kind::TLV(Some(tlv::VENDOR_NAMESPACE)) | Kind::VendorNamespace(_) => Namespace::Vendor,
+            Kind::TLV(Some(kind)) if kind >= 28 => Namespace::Tlv,
+            _ => Namespace::Radiotap,
+        }
+    }
+
+    /// Returns the present bit of the field.
+    pub const fn bit(self) -> u8 {
+        match self {
+            Kind::TSFT => 0,
+            Kind::Flags => 1,
+            Kind::Rate => 2,
+            Kind::Channel => 3,
+            Kind::FHSS => 4,
+            Kind::AntennaSignal => 5,
+            Kind::AntennaNoise => 6,
+            Kind::LockQuality => 7,
+            Kind::TxAttenuation => 8,
+            Kind::TxAttenuationDb => 9,
+            Kind::TxPower => 10,
+            Kind::Antenna => 11,
+            Kind::AntennaSignalDb => 12,
+            Kind::AntennaNoiseDb => 13,
+            Kind::RxFlags => 14,
+            Kind::TxFlags => 15,
+            Kind::RTSRetries => 16,
+            Kind::DataRetries => 17,
+            Kind::XChannel => 18,
+            Kind::MCS => 19,
+            Kind::AMPDUStatus => 20,
+            Kind::VHT => 21,
+            Kind::Timestamp => 22,
+            Kind::HE => 23,
+            Kind::HEMU => 24,
+            Kind::HEMUOtherUser => 25,
+            Kind::LSig => 27,
+            Kind::TLV(_) => 28,
+            Kind::RadiotapNamespace => 29,
+            Kind::VendorNamespace(_) => 30,
+            Kind::Unknown(bit) => bit,
+        }
+    }
+
+    /// Returns whether the size of the field is known, this is only false for
+    /// unknown fields not defined by the specification.
+    pub(crate) const fn is_sized(self) -> bool {
+        !matches!(self, Kind::Unknown(_)) || self.size() > 0
+    }
+
     /// Returns the align value for the field.
-    pub fn align(self) -> u64 {
+    ///
+    /// Unknown fields whose layout cannot be derived have an align of 1.
+    pub const fn align(self) -> u64 {
         match self {
             Kind::TSFT | Kind::Timestamp => 8,
-            Kind::XChannel | Kind::AMPDUStatus => 4,
+            Kind::XChannel | Kind::AMPDUStatus | Kind::TLV(_) => 4,
             Kind::Channel
             | Kind::FHSS
             | Kind::LockQuality
@@ -84,18 +304,25 @@ impl Kind {
             | Kind::RxFlags
             | Kind::TxFlags
             | Kind::VHT
+            | Kind::HE
+            | Kind::HEMU
+            | Kind::HEMUOtherUser
+            | Kind::LSig
             | Kind::VendorNamespace(_) => 2,
             _ => 1,
         }
     }
 
-    /// Returns the size of the field.
-    pub fn size(self) -> usize {
+    /// Returns the size of the field, for vendor namespaces and TLVs this is the
+    /// size of their header.
+    ///
+    /// Unknown fields whose layout cannot be derived have a size of 0.
+    pub const fn size(self) -> usize {
         match self {
-            Kind::VHT | Kind::Timestamp => 12,
+            Kind::VHT | Kind::Timestamp | Kind::HE | Kind::HEMU => 12,
             Kind::TSFT | Kind::AMPDUStatus | Kind::XChannel => 8,
-            Kind::VendorNamespace(_) => 6,
-            Kind::Channel => 4,
+            Kind::VendorNamespace(_) | Kind::HEMUOtherUser => 6,
+            Kind::Channel | Kind::LSig | Kind::TLV(_) => 4,
             Kind::MCS => 3,
             Kind::FHSS
             | Kind::LockQuality
@@ -103,15 +330,175 @@ impl Kind {
             | Kind::TxAttenuationDb
             | Kind::RxFlags
             | Kind::TxFlags => 2,
+            Kind::RadiotapNamespace | Kind::Unknown(_) => 0,
             _ => 1,
         }
     }
 }
 
-pub trait Field {
-    fn from_bytes(input: &[u8]) -> Result<Self>
-    where
-        Self: Sized;
+/// A field that can be decoded from, and written as, its Radiotap encoding.
+///
+/// Every field struct of this crate implements it, so generic code can
+/// decode and write any field. Implementing it, and
+/// [PresentField](trait.PresentField.html), for a type of your own plugs it
+/// into the same machinery, for example to decode a field differently:
+///
+/// ```
+/// use radiotap::{
+///     field::{Field, Kind, PresentField},
+///     Error, Radiotap,
+/// };
+///
+/// struct Signal(i8);
+///
+/// impl Field for Signal {
+///     fn from_bytes(input: &[u8]) -> Result<Signal, Error> {
+///         Ok(Signal(*input.first().ok_or(Error::IncompleteError)? as i8))
+///     }
+/// }
+///
+/// impl PresentField for Signal {
+///     const KIND: Kind = Kind::AntennaSignal;
+/// }
+///
+/// let capture = [0, 0, 10, 0, 0x24, 0, 0, 0, 12, 0xd6];
+/// let signal = Radiotap::get_one::<Signal>(&capture).unwrap();
+/// assert_eq!(signal.unwrap().0, -42);
+/// ```
+pub trait Field: Sized {
+    /// Decodes the field from its Radiotap encoding, without any alignment
+    /// padding.
+    fn from_bytes(input: &[u8]) -> Result<Self>;
+
+    /// Writes the field in the Radiotap format, returning the number of bytes
+    /// written. Fields that cannot be written return an `UnsupportedField`
+    /// error.
+    fn unparse<W: Write>(&self, _writer: &mut W) -> Result<usize> {
+        Err(Error::UnsupportedField)
+    }
+}
+
+/// A field identified by a present bit of the radiotap namespace, or by a TLV
+/// type, so it can be located in a capture.
+pub trait PresentField: Field {
+    /// The type of the field.
+    const KIND: Kind;
+}
+
+impl PresentField for TSFT {
+    const KIND: Kind = Kind::TSFT;
+}
+
+impl PresentField for Flags {
+    const KIND: Kind = Kind::Flags;
+}
+
+impl PresentField for Rate {
+    const KIND: Kind = Kind::Rate;
+}
+
+impl PresentField for Channel {
+    const KIND: Kind = Kind::Channel;
+}
+
+impl PresentField for FHSS {
+    const KIND: Kind = Kind::FHSS;
+}
+
+impl PresentField for AntennaSignal {
+    const KIND: Kind = Kind::AntennaSignal;
+}
+
+impl PresentField for AntennaNoise {
+    const KIND: Kind = Kind::AntennaNoise;
+}
+
+impl PresentField for LockQuality {
+    const KIND: Kind = Kind::LockQuality;
+}
+
+impl PresentField for TxAttenuation {
+    const KIND: Kind = Kind::TxAttenuation;
+}
+
+impl PresentField for TxAttenuationDb {
+    const KIND: Kind = Kind::TxAttenuationDb;
+}
+
+impl PresentField for TxPower {
+    const KIND: Kind = Kind::TxPower;
+}
+
+impl PresentField for Antenna {
+    const KIND: Kind = Kind::Antenna;
+}
+
+impl PresentField for AntennaSignalDb {
+    const KIND: Kind = Kind::AntennaSignalDb;
+}
+
+impl PresentField for AntennaNoiseDb {
+    const KIND: Kind = Kind::AntennaNoiseDb;
+}
+
+impl PresentField for RxFlags {
+    const KIND: Kind = Kind::RxFlags;
+}
+
+impl PresentField for TxFlags {
+    const KIND: Kind = Kind::TxFlags;
+}
+
+impl PresentField for RTSRetries {
+    const KIND: Kind = Kind::RTSRetries;
+}
+
+impl PresentField for DataRetries {
+    const KIND: Kind = Kind::DataRetries;
+}
+
+impl PresentField for XChannel {
+    const KIND: Kind = Kind::XChannel;
+}
+
+impl PresentField for MCS {
+    const KIND: Kind = Kind::MCS;
+}
+
+impl PresentField for AMPDUStatus {
+    const KIND: Kind = Kind::AMPDUStatus;
+}
+
+impl PresentField for VHT {
+    const KIND: Kind = Kind::VHT;
+}
+
+impl PresentField for Timestamp {
+    const KIND: Kind = Kind::Timestamp;
+}
+
+impl PresentField for HE {
+    const KIND: Kind = Kind::HE;
+}
+
+impl PresentField for HEMU {
+    const KIND: Kind = Kind::HEMU;
+}
+
+impl PresentField for HEMUOtherUser {
+    const KIND: Kind = Kind::HEMUOtherUser;
+}
+
+impl PresentField for LSig {
+    const KIND: Kind = Kind::LSig;
+}
+
+impl PresentField for USig {
+    const KIND: Kind = Kind::TLV(Some(tlv::U_SIG));
+}
+
+impl PresentField for EHT {
+    const KIND: Kind = Kind::TLV(Some(tlv::EHT));
 }
 
 /// Parse any `Field` and return a `Result<T>`.
@@ -130,93 +517,679 @@ where
     Ok(Some(T::from_bytes(input)?))
 }
 
+/// Write any `Field` if it is `Some`, returning the number of bytes written.
+pub fn unparse_some<T, W>(field: &Option<T>, writer: &mut W) -> Result<usize>
+where
+    T: Field,
+    W: Write,
+{
+    match field {
+        Some(field) => field.unparse(writer),
+        None => Ok(0),
+    }
+}
+
+/// Returns `flag` if `value` is true, or no flags otherwise.
+fn flag_if<T: Default>(value: bool, flag: T) -> T {
+    if value {
+        flag
+    } else {
+        T::default()
+    }
+}
+
+/// A parsed Radiotap field of any kind.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum Value {
+    TSFT(TSFT),
+    Flags(Flags),
+    Rate(Rate),
+    Channel(Channel),
+    FHSS(FHSS),
+    AntennaSignal(AntennaSignal),
+    AntennaNoise(AntennaNoise),
+    LockQuality(LockQuality),
+    TxAttenuation(TxAttenuation),
+    TxAttenuationDb(TxAttenuationDb),
+    TxPower(TxPower),
+    Antenna(Antenna),
+    AntennaSignalDb(AntennaSignalDb),
+    AntennaNoiseDb(AntennaNoiseDb),
+    RxFlags(RxFlags),
+    TxFlags(TxFlags),
+    RTSRetries(RTSRetries),
+    DataRetries(DataRetries),
+    XChannel(XChannel),
+    MCS(MCS),
+    AMPDUStatus(AMPDUStatus),
+    VHT(VHT),
+    Timestamp(Timestamp),
+    HE(HE),
+    HEMU(HEMU),
+    HEMUOtherUser(HEMUOtherUser),
+    LSig(LSig),
+    VendorNamespace(VendorNamespace),
+    USig(USig),
+    EHT(EHT),
+}
+
+impl Value {
+    /// Parses the field data of the given kind. Unknown fields return an
+    /// `UnsupportedField` error.
+    pub fn from_bytes(kind: Kind, input: &[u8]) -> Result<Value> {
+        Ok(match kind {
+            Kind::TSFT => Value::TSFT(from_bytes(input)?),
+            Kind::Flags => Value::Flags(from_bytes(input)?),
+            Kind::Rate => Value::Rate(from_bytes(input)?),
+            Kind::Channel => Value::Channel(from_bytes(input)?),
+            Kind::FHSS => Value::FHSS(from_bytes(input)?),
+            Kind::AntennaSignal => Value::AntennaSignal(from_bytes(input)?),
+            Kind::AntennaNoise => Value::AntennaNoise(from_bytes(input)?),
+            Kind::LockQuality => Value::LockQuality(from_bytes(input)?),
+            Kind::TxAttenuation => Value::TxAttenuation(from_bytes(input)?),
+            Kind::TxAttenuationDb => Value::TxAttenuationDb(from_bytes(input)?),
+            Kind::TxPower => Value::TxPower(from_bytes(input)?),
+            Kind::Antenna => Value::Antenna(from_bytes(input)?),
+            Kind::AntennaSignalDb => Value::AntennaSignalDb(from_bytes(input)?),
+            Kind::AntennaNoiseDb => Value::AntennaNoiseDb(from_bytes(input)?),
+            Kind::RxFlags => Value::RxFlags(from_bytes(input)?),
+            Kind::TxFlags => Value::TxFlags(from_bytes(input)?),
+            Kind::RTSRetries => Value::RTSRetries(from_bytes(input)?),
+            Kind::DataRetries => Value::DataRetries(from_bytes(input)?),
+            Kind::XChannel => Value::XChannel(from_bytes(input)?),
+            Kind::MCS => Value::MCS(from_bytes(input)?),
+            Kind::AMPDUStatus => Value::AMPDUStatus(from_bytes(input)?),
+            Kind::VHT => Value::VHT(from_bytes(input)?),
+            Kind::Timestamp => Value::Timestamp(from_bytes(input)?),
+            Kind::HE => Value::HE(from_bytes(input)?),
+            Kind::HEMU => Value::HEMU(from_bytes(input)?),
+            Kind::HEMUOtherUser => Value::HEMUOtherUser(from_bytes(input)?),
+            Kind::LSig => Value::LSig(from_bytes(input)?),
+            Kind::VendorNamespace(_) => Value::VendorNamespace(from_bytes(input)?),
+            Kind::TLV(Some(kind)) if kind < 28 => Value::from_bytes(Kind::new(kind as u8)?, input)?,
+            Kind::TLV(Some(tlv::U_SIG)) => Value::USig(from_bytes(input)?),
+            Kind::TLV(Some(tlv::EHT)) => Value::EHT(from_bytes(input)?),
+            Kind::TLV(_) | Kind::RadiotapNamespace | Kind::Unknown(_) => {
+                return Err(Error::UnsupportedField)
+            }
+        })
+    }
+
+    /// Returns the kind of the field.
+    pub fn kind(&self) -> Kind {
+        match self {
+            Value::TSFT(_) => Kind::TSFT,
+            Value::Flags(_) => Kind::Flags,
+            Value::Rate(_) => Kind::Rate,
+            Value::Channel(_) => Kind::Channel,
+            Value::FHSS(_) => Kind::FHSS,
+            Value::AntennaSignal(_) => Kind::AntennaSignal,
+            Value::AntennaNoise(_) => Kind::AntennaNoise,
+            Value::LockQuality(_) => Kind::LockQuality,
+            Value::TxAttenuation(_) => Kind::TxAttenuation,
+            Value::TxAttenuationDb(_) => Kind::TxAttenuationDb,
+            Value::TxPower(_) => Kind::TxPower,
+            Value::Antenna(_) => Kind::Antenna,
+            Value::AntennaSignalDb(_) => Kind::AntennaSignalDb,
+            Value::AntennaNoiseDb(_) => Kind::AntennaNoiseDb,
+            Value::RxFlags(_) => Kind::RxFlags,
+            Value::TxFlags(_) => Kind::TxFlags,
+            Value::RTSRetries(_) => Kind::RTSRetries,
+            Value::DataRetries(_) => Kind::DataRetries,
+            Value::XChannel(_) => Kind::XChannel,
+            Value::MCS(_) => Kind::MCS,
+            Value::AMPDUStatus(_) => Kind::AMPDUStatus,
+            Value::VHT(_) => Kind::VHT,
+            Value::Timestamp(_) => Kind::Timestamp,
+            Value::HE(_) => Kind::HE,
+            Value::HEMU(_) => Kind::HEMU,
+            Value::HEMUOtherUser(_) => Kind::HEMUOtherUser,
+            Value::LSig(_) => Kind::LSig,
+            Value::VendorNamespace(vns) => Kind::VendorNamespace(Some(*vns)),
+            Value::USig(_) => USig::KIND,
+            Value::EHT(_) => EHT::KIND,
+        }
+    }
+
+    /// Writes the field data, the inverse of
+    /// [from_bytes](#method.from_bytes), returning the number of bytes
+    /// written.
+    pub fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        match self {
+            Value::TSFT(field) => field.unparse(writer),
+            Value::Flags(field) => field.unparse(writer),
+            Value::Rate(field) => field.unparse(writer),
+            Value::Channel(field) => field.unparse(writer),
+            Value::FHSS(field) => field.unparse(writer),
+            Value::AntennaSignal(field) => field.unparse(writer),
+            Value::AntennaNoise(field) => field.unparse(writer),
+            Value::LockQuality(field) => field.unparse(writer),
+            Value::TxAttenuation(field) => field.unparse(writer),
+            Value::TxAttenuationDb(field) => field.unparse(writer),
+            Value::TxPower(field) => field.unparse(writer),
+            Value::Antenna(field) => field.unparse(writer),
+            Value::AntennaSignalDb(field) => field.unparse(writer),
+            Value::AntennaNoiseDb(field) => field.unparse(writer),
+            Value::RxFlags(field) => field.unparse(writer),
+            Value::TxFlags(field) => field.unparse(writer),
+            Value::RTSRetries(field) => field.unparse(writer),
+            Value::DataRetries(field) => field.unparse(writer),
+            Value::XChannel(field) => field.unparse(writer),
+            Value::MCS(field) => field.unparse(writer),
+            Value::AMPDUStatus(field) => field.unparse(writer),
+            Value::VHT(field) => field.unparse(writer),
+            Value::Timestamp(field) => field.unparse(writer),
+            Value::HE(field) => field.unparse(writer),
+            Value::HEMU(field) => field.unparse(writer),
+            Value::HEMUOtherUser(field) => field.unparse(writer),
+            Value::LSig(field) => field.unparse(writer),
+            Value::VendorNamespace(field) => field.unparse(writer),
+            Value::USig(field) => field.unparse(writer),
+            Value::EHT(field) => field.unparse(writer),
+        }
+    }
+}
+
+/// Parses a single field of the given kind located at `offset` in a Radiotap
+/// capture, returning the field and the offset just past it.
+///
+/// The offset is aligned to the field's alignment before parsing, so it may be
+/// the end of the previous field. For a vendor namespace the returned offset
+/// skips the vendor data.
+///
+/// # Examples
+///
+/// ```
+/// use radiotap::field::{parse_at, Kind, Value};
+///
+/// let capture = [0, 0, 14, 0, 12, 0, 0, 0, 12, 0, 108, 9, 160, 0];
+/// let (value, end) = parse_at(Kind::Channel, &capture, 9).unwrap();
+/// assert_eq!(value.kind(), Kind::Channel);
+/// assert_eq!(end, 14);
+/// if let Value::Channel(channel) = value {
+///     assert_eq!(channel.freq, 2412);
+/// }
+/// ```
+pub fn parse_at(kind: Kind, input: &[u8], offset: usize) -> Result<(Value, usize)> {
+    if !kind.is_sized() {
+        return Err(Error::UnsupportedField);
+    }
+
+    let start = offset
+        .checked_add(kind.align() as usize - 1)
+        .ok_or(Error::IncompleteError)?
+        & !(kind.align() as usize - 1);
+    let mut end = start + kind.size();
+    let data = input.get(start..end).ok_or(Error::IncompleteError)?;
+
+    let value = Value::from_bytes(kind, data)?;
+    if let Value::VendorNamespace(vns) = value {
+        end += vns.skip_length as usize;
+        if end > input.len() {
+            return Err(Error::IncompleteError);
+        }
+    }
+
+    Ok((value, end))
+}
+
 /// The Radiotap header, contained in all Radiotap captures.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Header {
-    /// The Radiotap version, only version 0 is supported.
+    /// The Radiotap version, only version 0 is supported unless parsed with
+    /// [parse](#method.parse) accepting any version.
     pub version: u8,
     /// The length of the entire Radiotap capture.
     pub length: usize,
     /// The size of the Radiotap header.
     pub size: usize,
     /// The fields present in the Radiotap capture.
-    pub present: Vec<Kind>,
+    pub present: PresentFlags,
 }
 
 impl Field for Header {
     fn from_bytes(input: &[u8]) -> Result<Header> {
-        let mut cursor = Cursor::new(input);
+        Header::parse(input, false)
+    }
+}
+
+impl Header {
+    /// The maximum size of a Radiotap header, with
+    /// [MAX_PRESENT_WORDS](constant.MAX_PRESENT_WORDS.html) present words.
+    pub const MAX_SIZE: usize = 4 + 4 * MAX_PRESENT_WORDS;
 
-        let version = cursor.read_u8()?;
-        if version != 0 {
+    /// Returns the parsed header, accepting any version if `any_version` is
+    /// set. Some broken firmwares set a non-zero version on captures with the
+    /// standard layout.
+    pub fn parse(input: &[u8], any_version: bool) -> Result<Header> {
+        let mut reader = Reader::new(input);
+
+        let version = reader.read_u8()?;
+        if version != 0 && !any_version {
             // We only support version 0
             return Err(Error::UnsupportedVersion);
         }
 
-        cursor.read_u8()?; // Account for 1 byte padding field
+        reader.read_u8()?; // Account for 1 byte padding field
 
-        let length = cursor.read_u16::<LE>()?;
+        let length = reader.read_u16()?;
         if input.len() < length as usize {
             return Err(Error::InvalidLength);
         }
 
-        let mut present;
-        let mut present_count = 0;
-        let mut vendor_namespace = false;
-        let mut kinds = Vec::new();
-
+        let mut present = PresentFlags::default();
         loop {
-            present = cursor.read_u32::<LE>()?;
-
-            if !vendor_namespace {
-                for bit in 0..29 {
-                    if present.is_bit_set(bit) {
-                        match Kind::new(present_count * 32 + bit) {
-                            Ok(kind) => {
-                                kinds.push(kind);
-                            }
-                            Err(Error::UnsupportedField) => {
-                                // Does not matter, we will just parse the ones we can
-                            }
-                            Err(e) => return Err(e),
-                        }
-                    }
+            let word = reader.read_u32()?;
+            present.push(word)?;
+
+            // More present words do not exist
+            if !word.is_bit_set(31) {
+                break;
+            }
+        }
+
+        // The length covers at least the present words
+        if usize::from(length) < reader.position() {
+            return Err(Error::InvalidLength);
+        }
+
+        Ok(Header {
+            version,
+            length: length as usize,
+            size: reader.position(),
+            present,
+        })
+    }
+
+    /// Returns an iterator over all set present bits as
+    /// `(word_index, bit_index, kind)`, including the bits of fields this
+    /// crate cannot decode, see [PresentFlags::bits](struct.PresentFlags.html#method.bits).
+    pub fn present_bits(&self) -> PresentBits {
+        self.present.bits()
+    }
+}
+
+/// The maximum number of present words in a header.
+pub const MAX_PRESENT_WORDS: usize = 16;
+
+/// The fields defined by the present bits of the radiotap namespace, in the
+/// order they appear in a capture.
+const FIXED_KINDS: [Kind; 27] = [
+    Kind::TSFT,
+    Kind::Flags,
+    Kind::Rate,
+    Kind::Channel,
+    Kind::FHSS,
+    Kind::AntennaSignal,
+    Kind::AntennaNoise,
+    Kind::LockQuality,
+    Kind::TxAttenuation,
+    Kind::TxAttenuationDb,
+    Kind::TxPower,
+    Kind::Antenna,
+    Kind::AntennaSignalDb,
+    Kind::AntennaNoiseDb,
+    Kind::RxFlags,
+    Kind::TxFlags,
+    Kind::RTSRetries,
+    Kind::DataRetries,
+    Kind::XChannel,
+    Kind::MCS,
+    Kind::AMPDUStatus,
+    Kind::VHT,
+    Kind::Timestamp,
+    Kind::HE,
+    Kind::HEMU,
+    Kind::HEMUOtherUser,
+    Kind::LSig,
+];
+
+/// The maximum length of a Radiotap capture with a single present word,
+/// holding every field defined by the radiotap namespace and no TLVs.
+pub const MAX_FIXED_HEADER_LEN: usize = {
+    let mut offset = 8;
+    let mut i = 0;
+    while i < FIXED_KINDS.len() {
+        let align = FIXED_KINDS[i].align() as usize;
+        offset = (offset + align - 1) & !(align - 1);
+        offset += FIXED_KINDS[i].size();
+        i += 1;
+    }
+    offset
+};
+
+/// The present words of a Radiotap header, including bits this crate does
+/// not know.
+///
+/// Iterating yields the kinds of the fields in the order they appear in the
+/// capture, with a [RadiotapNamespace](enum.Kind.html#variant.RadiotapNamespace)
+/// or [VendorNamespace](enum.Kind.html#variant.VendorNamespace) for each
+/// namespace switch.
+///
+/// ```
+/// use radiotap::field::{Kind, PresentFlags};
+///
+/// let present = PresentFlags::new(&[0x0000_0006]).unwrap();
+/// assert!(present.contains(Kind::Rate));
+/// assert_eq!(present.iter().collect::<Vec<_>>(), [Kind::Flags, Kind::Rate]);
+/// assert_eq!(present.to_string(), "0x00000006");
+/// ```
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PresentFlags {
+    words: [u32; MAX_PRESENT_WORDS],
+    len: usize,
+}
+
+impl PresentFlags {
+    /// Returns the present flags with the given words, the extension bit (31)
+    /// of each word is used as is.
+    pub fn new(words: &[u32]) -> Result<PresentFlags> {
+        let mut present = PresentFlags::default();
+        for &word in words {
+            present.push(word)?;
+        }
+        Ok(present)
+    }
+
+    /// Appends a present word.
+    pub(crate) fn push(&mut self, word: u32) -> Result<()> {
+        if self.len == MAX_PRESENT_WORDS {
+            return Err(Error::InvalidFormat);
+        }
+        self.words[self.len] = word;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Returns the raw present words.
+    pub fn words(&self) -> &[u32] {
+        &self.words[..self.len]
+    }
+
+    /// Returns whether a field of the given kind is present, in any
+    /// namespace.
+    pub fn contains(&self, kind: Kind) -> bool {
+        self.iter().any(|k| match (k, kind) {
+            (Kind::TLV(_), Kind::TLV(_)) | (Kind::VendorNamespace(_), Kind::VendorNamespace(_)) => {
+                true
+            }
+            (k, kind) => k == kind,
+        })
+    }
+
+    /// Returns an iterator over the kinds of the present fields.
+    pub fn iter(&self) -> PresentKinds {
+        PresentKinds {
+            present: *self,
+            word: 0,
+            bit: 0,
+            count: 0,
+            vendor_namespace: false,
+        }
+    }
+
+    /// Returns an iterator over all set present bits, except the extension
+    /// bits, including the bits this crate does not know.
+    ///
+    /// ```
+    /// use radiotap::field::{Kind, PresentFlags};
+    ///
+    /// let present = PresentFlags::new(&[0x8000_0002, 0x0000_0001]).unwrap();
+    /// assert_eq!(
+    ///     present.bits().collect::<Vec<_>>(),
+    ///     [(0, 1, Some(Kind::Flags)), (1, 0, None)]
+    /// );
+    /// ```
+    pub fn bits(&self) -> PresentBits {
+        PresentBits {
+            present: *self,
+            word: 0,
+            bit: 0,
+            count: 0,
+            vendor_namespace: false,
+        }
+    }
+}
+
+impl fmt::Debug for PresentFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl fmt::Display for PresentFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, word) in self.words().iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{:#010x}", word)?;
+        }
+        Ok(())
+    }
+}
+
+/// Serialized as the list of raw present words.
+#[cfg(feature = "serde")]
+impl serde::Serialize for PresentFlags {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.words())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PresentFlags {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> core::result::Result<PresentFlags, D::Error> {
+        struct Words;
+
+        impl<'de> serde::de::Visitor<'de> for Words {
+            type Value = PresentFlags;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "at most {} present words", MAX_PRESENT_WORDS)
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> core::result::Result<PresentFlags, A::Error> {
+                let mut present = PresentFlags::default();
+                while let Some(word) = seq.next_element()? {
+                    present.push(word).map_err(|_| {
+                        serde::de::Error::invalid_length(MAX_PRESENT_WORDS + 1, &self)
+                    })?;
+                }
+                Ok(present)
+            }
+        }
+
+        deserializer.deserialize_seq(Words)
+    }
+}
+
+/// Generates up to [MAX_PRESENT_WORDS](constant.MAX_PRESENT_WORDS.html)
+/// arbitrary present words.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for PresentFlags {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<PresentFlags> {
+        let mut present = PresentFlags::default();
+        for _ in 0..u.int_in_range(0..=MAX_PRESENT_WORDS)? {
+            // Cannot fail, there are at most MAX_PRESENT_WORDS words
+            let _ = present.push(u.arbitrary()?);
+        }
+        Ok(present)
+    }
+}
+
+impl IntoIterator for &PresentFlags {
+    type Item = Kind;
+    type IntoIter = PresentKinds;
+
+    fn into_iter(self) -> PresentKinds {
+        self.iter()
+    }
+}
+
+/// An iterator over the kinds of the fields in
+/// [PresentFlags](struct.PresentFlags.html).
+#[derive(Clone, Debug)]
+pub struct PresentKinds {
+    present: PresentFlags,
+    word: usize,
+    bit: u8,
+    /// The number of present words so far in the current namespace.
+    count: u32,
+    vendor_namespace: bool,
+}
+
+impl Iterator for PresentKinds {
+    type Item = Kind;
+
+    fn next(&mut self) -> Option<Kind> {
+        while let Some(&word) = self.present.words().get(self.word) {
+            if self.bit < 29 {
+                let bit = self.bit;
+                self.bit += 1;
+                if !self.vendor_namespace && word.is_bit_set(bit) {
+                    // Bits beyond 255 are never defined, they all map to an
+                    // unknown kind which stops the iteration anyway
+                    let value = u8::try_from(self.count * 32 + u32::from(bit)).unwrap_or(u8::MAX);
+                    return Some(Kind::new(value).unwrap_or(Kind::Unknown(value)));
                 }
+                continue;
             }
 
+            self.word += 1;
+            self.bit = 0;
+
             // Need to move to radiotap namespace
-            if present.is_bit_set(29) {
-                present_count = 0;
-                vendor_namespace = false;
+            if word.is_bit_set(29) {
+                self.count = 0;
+                self.vendor_namespace = false;
+                return Some(Kind::RadiotapNamespace);
 
             // Need to move to vendor namespace
-            } else if present.is_bit_set(30) {
-                present_count = 0;
-                vendor_namespace = true;
+            } else if word.is_bit_set(30) {
+                self.count = 0;
+                self.vendor_namespace = true;
                 // We'll figure out what namespace it is later, just use none
-                kinds.push(Kind::VendorNamespace(None))
+                return Some(Kind::VendorNamespace(None));
 
             // Need to stay in the same namespace
             } else {
-                present_count += 1;
+                self.count += 1;
             }
+        }
+        None
+    }
 
-            // More present words do not exist
-            if !present.is_bit_set(31) {
-                break;
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining();
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for PresentKinds {}
+
+impl FusedIterator for PresentKinds {}
+
+impl PresentKinds {
+    /// Returns the number of kinds left, counting the set bits of the
+    /// remaining words outside of vendor namespaces.
+    fn remaining(&self) -> usize {
+        let mut vendor_namespace = self.vendor_namespace;
+        let mut remaining = 0;
+        for (i, &word) in self.present.words().iter().enumerate().skip(self.word) {
+            let bit = if i == self.word { self.bit } else { 0 };
+            if !vendor_namespace && bit < 29 {
+                remaining += ((word & 0x1fff_ffff) >> bit).count_ones() as usize;
+            }
+            if word.is_bit_set(29) {
+                remaining += 1;
+                vendor_namespace = false;
+            } else if word.is_bit_set(30) {
+                remaining += 1;
+                vendor_namespace = true;
             }
         }
+        remaining
+    }
+}
 
-        Ok(Header {
-            version,
-            length: length as usize,
-            size: cursor.position() as usize,
-            present: kinds,
-        })
+/// An iterator over the set bits in [PresentFlags](struct.PresentFlags.html),
+/// see [bits](struct.PresentFlags.html#method.bits).
+///
+/// Yields the index of the present word, the index of the bit in the word,
+/// and the kind of the field if it is known. Bits in vendor namespaces never
+/// have a kind.
+#[derive(Clone, Debug)]
+pub struct PresentBits {
+    present: PresentFlags,
+    word: usize,
+    bit: u8,
+    /// The number of present words so far in the current namespace.
+    count: u32,
+    vendor_namespace: bool,
+}
+
+impl Iterator for PresentBits {
+    type Item = (usize, u8, Option<Kind>);
+
+    fn next(&mut self) -> Option<(usize, u8, Option<Kind>)> {
+        while let Some(&word) = self.present.words().get(self.word) {
+            if self.bit == 31 {
+                // The namespace switches the same way as for PresentKinds
+                if word.is_bit_set(29) {
+                    self.count = 0;
+                    self.vendor_namespace = false;
+                } else if word.is_bit_set(30) {
+                    self.count = 0;
+                    self.vendor_namespace = true;
+                } else {
+                    self.count += 1;
+                }
+                self.word += 1;
+                self.bit = 0;
+                continue;
+            }
+
+            let bit = self.bit;
+            self.bit += 1;
+            if !word.is_bit_set(bit) {
+                continue;
+            }
+            let kind = match bit {
+                29 => Some(Kind::RadiotapNamespace),
+                30 => Some(Kind::VendorNamespace(None)),
+                _ if self.vendor_namespace => None,
+                _ => u8::try_from(self.count * 32 + u32::from(bit))
+                    .ok()
+                    .and_then(|value| Kind::new(value).ok())
+                    .filter(|kind| !matches!(kind, Kind::Unknown(_))),
+            };
+            return Some((self.word, bit, kind));
+        }
+        None
     }
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct VendorNamespace {
     pub oui: Oui,
     pub sub_namespace: u8,
@@ -225,36 +1198,109 @@ pub struct VendorNamespace {
 
 impl Field for VendorNamespace {
     fn from_bytes(input: &[u8]) -> Result<VendorNamespace> {
-        let mut cursor = Cursor::new(input);
+        let mut reader = Reader::new(input);
         let mut oui = [0; 3];
-        cursor.read_exact(&mut oui)?;
-        let sub_namespace = cursor.read_u8()?;
-        let skip_length = cursor.read_u16::<LE>()?;
+        reader.read_exact(&mut oui)?;
+        let sub_namespace = reader.read_u8()?;
+        let skip_length = reader.read_u16()?;
         Ok(VendorNamespace {
-            oui,
+            oui: Oui(oui),
             sub_namespace,
             skip_length,
         })
     }
 }
 
+/// A vendor namespace with its data.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct VendorData {
+    /// The OUI of the vendor.
+    pub oui: Oui,
+    /// The sub namespace, defined by the vendor.
+    pub sub_namespace: u8,
+    /// The present words of the namespace, defined by the vendor, without bits
+    /// 29 to 31 which switch between words and namespaces. A namespace without
+    /// present words is written with a single empty one.
+    pub present: Vec<u32>,
+    /// The vendor data, its length is the skip length of the namespace.
+    pub data: Vec<u8>,
+}
+
+/// The undecoded bytes of a field, kept when parsing with the
+/// [raw](../struct.RadiotapParserBuilder.html#method.raw) option.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RawField {
+    /// The kind of the field, TLV records of fields defined by a present bit
+    /// have the kind of the present bit.
+    pub kind: Kind,
+    /// The data of the field, without padding or a TLV header.
+    pub data: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl Field for VendorData {
+    fn from_bytes(input: &[u8]) -> Result<VendorData> {
+        let vns = VendorNamespace::from_bytes(input)?;
+        let data = input
+            .get(6..6 + usize::from(vns.skip_length))
+            .ok_or(Error::IncompleteError)?;
+        let mut vendor = VendorData {
+            oui: vns.oui,
+            sub_namespace: vns.sub_namespace,
+            present: Vec::new(),
+            data: Vec::new(),
+        };
+        vendor.data.try_reserve(data.len())?;
+        vendor.data.extend_from_slice(data);
+        Ok(vendor)
+    }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        let skip_length = u16::try_from(self.data.len()).map_err(|_| Error::InvalidLength)?;
+        writer.write_all(&self.oui.0)?;
+        writer.write_u8(self.sub_namespace)?;
+        writer.write_u16::<LE>(skip_length)?;
+        writer.write_all(&self.data)?;
+        Ok(6 + self.data.len())
+    }
+}
+
 /// Value in microseconds of the MAC’s 64-bit 802.11 Time Synchronization
 /// Function timer when the first bit of the MPDU arrived at the MAC. For
 /// received frames only.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TSFT {
     pub value: u64,
 }
 
 impl Field for TSFT {
     fn from_bytes(input: &[u8]) -> Result<TSFT> {
-        let value = Cursor::new(input).read_u64::<LE>()?;
+        let value = Reader::new(input).read_u64()?;
         Ok(TSFT { value })
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_u64::<LE>(self.value)?;
+        Ok(8)
+    }
 }
 
 /// Properties of transmitted and received frames.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Flags {
     /// The frame was sent/received during CFP.
     pub cfp: bool,
@@ -277,7 +1323,7 @@ pub struct Flags {
 
 impl Field for Flags {
     fn from_bytes(input: &[u8]) -> Result<Flags> {
-        let flags = Cursor::new(input).read_u8()?;
+        let flags = Reader::new(input).read_u8()?;
         Ok(Flags {
             cfp: flags.is_flag_set(0x01),
             preamble: flags.is_flag_set(0x02),
@@ -289,26 +1335,87 @@ impl Field for Flags {
             sgi: flags.is_flag_set(0x80),
         })
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_u8(
+            flag_if(self.cfp, 0x01)
+                | flag_if(self.preamble, 0x02)
+                | flag_if(self.wep, 0x04)
+                | flag_if(self.fragmentation, 0x08)
+                | flag_if(self.fcs, 0x10)
+                | flag_if(self.data_pad, 0x20)
+                | flag_if(self.bad_fcs, 0x40)
+                | flag_if(self.sgi, 0x80),
+        )?;
+        Ok(1)
+    }
 }
 
-/// The legacy data rate in Mbps. Usually only one of the
+/// The legacy data rate, in units of 500 kbps. Usually only one of the
 /// [Rate](struct.Rate.html), [MCS](struct.MCS.html), and [VHT](struct.VHT.html)
 /// fields is present.
-#[derive(Clone, Copy, Debug, PartialEq)]
+///
+/// The raw encoding is kept so the rate round-trips exactly, use
+/// [as_mbps](#method.as_mbps) for the rate in Mbps.
+///
+/// ```
+/// use radiotap::field::Rate;
+///
+/// let rate = Rate::from_mbps(5.5).unwrap();
+/// assert_eq!(rate, Rate { raw: 11 });
+/// assert_eq!(rate.as_mbps(), 5.5);
+/// assert!(Rate::from_mbps(5.2).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Rate {
-    pub value: f32,
+    /// The rate in units of 500 kbps.
+    pub raw: u8,
+}
+
+impl Rate {
+    /// Returns the rate of the given Mbps, which must be a multiple of 0.5
+    /// Mbps up to 127.5 Mbps.
+    pub fn from_mbps(mbps: f32) -> Result<Rate> {
+        let raw = mbps * 2.0;
+        if !(0.0..=255.0).contains(&raw) || f32::from(raw as u8) != raw {
+            return Err(Error::InvalidFormat);
+        }
+        Ok(Rate { raw: raw as u8 })
+    }
+
+    /// Returns the rate in Mbps.
+    pub fn as_mbps(self) -> f32 {
+        f32::from(self.raw) / 2.0
+    }
+
+    /// Returns the rate in Mbps, like the `value` field did before 1.4.0.
+    #[deprecated(since = "1.4.0", note = "use `as_mbps` instead")]
+    pub fn value(&self) -> f32 {
+        self.as_mbps()
+    }
 }
 
 impl Field for Rate {
     fn from_bytes(input: &[u8]) -> Result<Rate> {
-        let value = f32::from(Cursor::new(input).read_i8()?) / 2.0;
-        Ok(Rate { value })
+        let raw = Reader::new(input).read_u8()?;
+        Ok(Rate { raw })
+    }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_u8(self.raw)?;
+        Ok(1)
     }
 }
 
 /// The transmitted or received frequency in MHz, including flags describing the
 /// channel.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Channel {
     /// The frequency in MHz.
     pub freq: u16,
@@ -318,9 +1425,9 @@ pub struct Channel {
 
 impl Field for Channel {
     fn from_bytes(input: &[u8]) -> Result<Channel> {
-        let mut cursor = Cursor::new(input);
-        let freq = cursor.read_u16::<LE>()?;
-        let flags = cursor.read_u16::<LE>()?;
+        let mut reader = Reader::new(input);
+        let freq = reader.read_u16()?;
+        let flags = reader.read_u16()?;
         let flags = ChannelFlags {
             turbo: flags.is_flag_set(0x0010),
             cck: flags.is_flag_set(0x0020),
@@ -333,10 +1440,29 @@ impl Field for Channel {
         };
         Ok(Channel { freq, flags })
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        let flags = &self.flags;
+        writer.write_u16::<LE>(self.freq)?;
+        writer.write_u16::<LE>(
+            flag_if(flags.turbo, 0x0010)
+                | flag_if(flags.cck, 0x0020)
+                | flag_if(flags.ofdm, 0x0040)
+                | flag_if(flags.ghz2, 0x0080)
+                | flag_if(flags.ghz5, 0x0100)
+                | flag_if(flags.passive, 0x0200)
+                | flag_if(flags.dynamic, 0x0400)
+                | flag_if(flags.gfsk, 0x0800),
+        )?;
+        Ok(4)
+    }
 }
 
 /// The hop set and pattern for frequency-hopping radios.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FHSS {
     pub hopset: u8,
     pub pattern: u8,
@@ -344,156 +1470,287 @@ pub struct FHSS {
 
 impl Field for FHSS {
     fn from_bytes(input: &[u8]) -> Result<FHSS> {
-        let mut cursor = Cursor::new(input);
-        let hopset = cursor.read_u8()?;
-        let pattern = cursor.read_u8()?;
+        let mut reader = Reader::new(input);
+        let hopset = reader.read_u8()?;
+        let pattern = reader.read_u8()?;
         Ok(FHSS { hopset, pattern })
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_u8(self.hopset)?;
+        writer.write_u8(self.pattern)?;
+        Ok(2)
+    }
 }
 
 /// RF signal power at the antenna in dBm. Indicates the RF signal power at the
 /// antenna, in decibels difference from 1mW.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AntennaSignal {
     pub value: i8,
 }
 
+impl AntennaSignal {
+    /// Returns the signal power.
+    pub fn dbm(&self) -> Dbm {
+        Dbm::from(self.value)
+    }
+}
+
 impl Field for AntennaSignal {
     fn from_bytes(input: &[u8]) -> Result<AntennaSignal> {
-        let value = Cursor::new(input).read_i8()?;
+        let value = Reader::new(input).read_i8()?;
         Ok(AntennaSignal { value })
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_i8(self.value)?;
+        Ok(1)
+    }
 }
 
 /// RF signal power at the antenna in dB. Indicates the RF signal power at the
 /// antenna, in decibels difference from an arbitrary, fixed reference.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AntennaSignalDb {
     pub value: u8,
 }
 
+impl AntennaSignalDb {
+    /// Returns the signal power relative to the fixed reference.
+    pub fn db(&self) -> Db {
+        Db::from(self.value)
+    }
+}
+
 impl Field for AntennaSignalDb {
     fn from_bytes(input: &[u8]) -> Result<AntennaSignalDb> {
-        let value = Cursor::new(input).read_u8()?;
+        let value = Reader::new(input).read_u8()?;
         Ok(AntennaSignalDb { value })
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_u8(self.value)?;
+        Ok(1)
+    }
 }
 
 /// RF noise power at the antenna in dBm. Indicates the RF signal noise at the
 /// antenna, in decibels  difference from 1mW.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AntennaNoise {
     pub value: i8,
 }
 
+impl AntennaNoise {
+    /// Returns the noise power.
+    pub fn dbm(&self) -> Dbm {
+        Dbm::from(self.value)
+    }
+}
+
 impl Field for AntennaNoise {
     fn from_bytes(input: &[u8]) -> Result<AntennaNoise> {
-        let value = Cursor::new(input).read_i8()?;
+        let value = Reader::new(input).read_i8()?;
         Ok(AntennaNoise { value })
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_i8(self.value)?;
+        Ok(1)
+    }
 }
 
 /// RF noise power at the antenna in dB. Indicates the RF signal noise at the
 /// antenna, in decibels difference from an arbitrary, fixed reference.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AntennaNoiseDb {
     pub value: u8,
 }
 
+impl AntennaNoiseDb {
+    /// Returns the noise power relative to the fixed reference.
+    pub fn db(&self) -> Db {
+        Db::from(self.value)
+    }
+}
+
 impl Field for AntennaNoiseDb {
     fn from_bytes(input: &[u8]) -> Result<AntennaNoiseDb> {
-        let value = Cursor::new(input).read_u8()?;
+        let value = Reader::new(input).read_u8()?;
         Ok(AntennaNoiseDb { value })
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_u8(self.value)?;
+        Ok(1)
+    }
 }
 
 /// Quality of Barker code lock, unitless. Monotonically nondecreasing with
 /// "better" lock strength. Called "Signal Quality" in datasheets.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct LockQuality {
     pub value: u16,
 }
 
 impl Field for LockQuality {
     fn from_bytes(input: &[u8]) -> Result<LockQuality> {
-        let value = Cursor::new(input).read_u16::<LE>()?;
+        let value = Reader::new(input).read_u16()?;
         Ok(LockQuality { value })
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_u16::<LE>(self.value)?;
+        Ok(2)
+    }
 }
 
 /// Transmit power expressed as unitless distance from max power. 0 is max
 /// power. Monotonically nondecreasing with lower power levels.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TxAttenuation {
     pub value: u16,
 }
 
 impl Field for TxAttenuation {
     fn from_bytes(input: &[u8]) -> Result<TxAttenuation> {
-        let value = Cursor::new(input).read_u16::<LE>()?;
+        let value = Reader::new(input).read_u16()?;
         Ok(TxAttenuation { value })
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_u16::<LE>(self.value)?;
+        Ok(2)
+    }
 }
 
 /// Transmit power in dB. 0 is max power. Monotonically nondecreasing with lower
 /// power levels.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TxAttenuationDb {
     pub value: u16,
 }
 
+impl TxAttenuationDb {
+    /// Returns the attenuation from the maximum power.
+    pub fn db(&self) -> Db {
+        Db::from(self.value)
+    }
+}
+
 impl Field for TxAttenuationDb {
     fn from_bytes(input: &[u8]) -> Result<TxAttenuationDb> {
-        let value = Cursor::new(input).read_u16::<LE>()?;
+        let value = Reader::new(input).read_u16()?;
         Ok(TxAttenuationDb { value })
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_u16::<LE>(self.value)?;
+        Ok(2)
+    }
 }
 
 /// Transmit power in dBm. This is the absolute power level measured at the
 /// antenna port.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TxPower {
     pub value: i8,
 }
 
+impl TxPower {
+    /// Returns the transmit power.
+    pub fn dbm(&self) -> Dbm {
+        Dbm::from(self.value)
+    }
+}
+
 impl Field for TxPower {
     fn from_bytes(input: &[u8]) -> Result<TxPower> {
-        let value = Cursor::new(input).read_i8()?;
+        let value = Reader::new(input).read_i8()?;
         Ok(TxPower { value })
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_i8(self.value)?;
+        Ok(1)
+    }
 }
 
 /// Indication of the transmit/receive antenna for this frame. The first antenna
 /// is antenna 0.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Antenna {
     pub value: u8,
 }
 
 impl Field for Antenna {
     fn from_bytes(input: &[u8]) -> Result<Antenna> {
-        let value = Cursor::new(input).read_u8()?;
+        let value = Reader::new(input).read_u8()?;
         Ok(Antenna { value })
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_u8(self.value)?;
+        Ok(1)
+    }
 }
 
 /// Properties of received frames.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RxFlags {
     pub bad_plcp: bool,
 }
 
 impl Field for RxFlags {
     fn from_bytes(input: &[u8]) -> Result<RxFlags> {
-        let flags = Cursor::new(input).read_u16::<LE>()?;
+        let flags = Reader::new(input).read_u16()?;
         Ok(RxFlags {
             bad_plcp: flags.is_flag_set(0x0002),
         })
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_u16::<LE>(flag_if(self.bad_plcp, 0x0002))?;
+        Ok(2)
+    }
 }
 
 /// Properties of transmitted frames.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TxFlags {
     /// Transmission failed due to excessive retries.
     pub fail: bool,
@@ -511,7 +1768,7 @@ pub struct TxFlags {
 
 impl Field for TxFlags {
     fn from_bytes(input: &[u8]) -> Result<TxFlags> {
-        let flags = Cursor::new(input).read_u8()?;
+        let flags = Reader::new(input).read_u8()?;
         Ok(TxFlags {
             fail: flags.is_flag_set(0x0001),
             cts: flags.is_flag_set(0x0002),
@@ -520,36 +1777,66 @@ impl Field for TxFlags {
             no_seq: flags.is_flag_set(0x0010),
         })
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_u16::<LE>(
+            flag_if(self.fail, 0x0001)
+                | flag_if(self.cts, 0x0002)
+                | flag_if(self.rts, 0x0004)
+                | flag_if(self.no_ack, 0x0008)
+                | flag_if(self.no_seq, 0x0010),
+        )?;
+        Ok(2)
+    }
 }
 
 /// Number of RTS retries a transmitted frame used.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RTSRetries {
     pub value: u8,
 }
 
 impl Field for RTSRetries {
     fn from_bytes(input: &[u8]) -> Result<RTSRetries> {
-        let value = Cursor::new(input).read_u8()?;
+        let value = Reader::new(input).read_u8()?;
         Ok(RTSRetries { value })
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_u8(self.value)?;
+        Ok(1)
+    }
 }
 
 /// Number of data retries a transmitted frame used.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DataRetries {
     pub value: u8,
 }
 
 impl Field for DataRetries {
     fn from_bytes(input: &[u8]) -> Result<DataRetries> {
-        let value = Cursor::new(input).read_u8()?;
+        let value = Reader::new(input).read_u8()?;
         Ok(DataRetries { value })
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_u8(self.value)?;
+        Ok(1)
+    }
 }
 
 /// Extended channel information.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct XChannel {
     /// The channel flags.
     pub flags: XChannelFlags,
@@ -563,11 +1850,11 @@ pub struct XChannel {
 
 impl Field for XChannel {
     fn from_bytes(input: &[u8]) -> Result<XChannel> {
-        let mut cursor = Cursor::new(input);
-        let flags = cursor.read_u32::<LE>()?;
-        let freq = cursor.read_u16::<LE>()?;
-        let channel = cursor.read_u8()?;
-        let max_power = cursor.read_u8()?;
+        let mut reader = Reader::new(input);
+        let flags = reader.read_u32()?;
+        let freq = reader.read_u16()?;
+        let channel = reader.read_u8()?;
+        let max_power = reader.read_u8()?;
         Ok(XChannel {
             flags: XChannelFlags {
                 turbo: flags.is_flag_set(0x0000_0010),
@@ -591,12 +1878,40 @@ impl Field for XChannel {
             max_power,
         })
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        let flags = &self.flags;
+        writer.write_u32::<LE>(
+            flag_if(flags.turbo, 0x0000_0010)
+                | flag_if(flags.cck, 0x0000_0020)
+                | flag_if(flags.ofdm, 0x0000_0040)
+                | flag_if(flags.ghz2, 0x0000_0080)
+                | flag_if(flags.ghz5, 0x0000_0100)
+                | flag_if(flags.passive, 0x0000_0200)
+                | flag_if(flags.dynamic, 0x0000_0400)
+                | flag_if(flags.gfsk, 0x0000_0800)
+                | flag_if(flags.gsm, 0x0000_1000)
+                | flag_if(flags.sturbo, 0x0000_2000)
+                | flag_if(flags.half, 0x0000_4000)
+                | flag_if(flags.quarter, 0x0000_8000)
+                | flag_if(flags.ht20, 0x0001_0000)
+                | flag_if(flags.ht40u, 0x0002_0000)
+                | flag_if(flags.ht40d, 0x0004_0000),
+        )?;
+        writer.write_u16::<LE>(self.freq)?;
+        writer.write_u8(self.channel)?;
+        writer.write_u8(self.max_power)?;
+        Ok(8)
+    }
 }
 
 /// The IEEE 802.11n data rate index. Usually only one of the
 /// [Rate](struct.Rate.html), [MCS](struct.MCS.html), and [VHT] fields is
 /// present.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct MCS {
     /// The bandwidth.
     pub bw: Option<Bandwidth>,
@@ -612,20 +1927,21 @@ pub struct MCS {
     pub stbc: Option<u8>,
     /// Number of extension spatial streams.
     pub ness: Option<u8>,
-    /// The datarate in Mbps
+    /// The datarate in Mbps, see [ht_rate](ext/fn.ht_rate.html). Only set
+    /// when the index, bandwidth and guard interval are known.
     pub datarate: Option<f32>,
 }
 
 impl Field for MCS {
     fn from_bytes(input: &[u8]) -> Result<MCS> {
-        let mut cursor = Cursor::new(input);
+        let mut reader = Reader::new(input);
         let mut mcs = MCS {
             ..Default::default()
         };
 
-        let known = cursor.read_u8()?;
-        let flags = cursor.read_u8()?;
-        let index = cursor.read_u8()?;
+        let known = reader.read_u8()?;
+        let flags = reader.read_u8()?;
+        let index = reader.read_u8()?;
 
         if known.is_flag_set(0x01) {
             mcs.bw = Some(Bandwidth::new(flags & 0x03)?)
@@ -665,20 +1981,68 @@ impl Field for MCS {
 
         if known.is_flag_set(0x40) {
             // Yes this is stored weirdly
-            mcs.ness = Some(known & 0x80 >> 6 | flags & 0x80 >> 7)
+            mcs.ness = Some((known & 0x80) >> 6 | (flags & 0x80) >> 7)
         }
 
-        if mcs.bw.is_some() && mcs.gi.is_some() {
-            mcs.datarate = Some(ht_rate(index, mcs.bw.unwrap(), mcs.gi.unwrap())?);
+        // An undefined combination is not an error, the rate is just unknown
+        if let (Some(index), Some(bw), Some(gi)) = (mcs.index, mcs.bw, mcs.gi) {
+            mcs.datarate = ht_rate(index, bw, gi).ok();
         }
 
         Ok(mcs)
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        let mut known = 0;
+        let mut flags = 0;
+
+        if let Some(bw) = self.bw {
+            known |= 0x01;
+            flags |= bw.value()? & 0x03;
+        }
+
+        if self.index.is_some() {
+            known |= 0x02;
+        }
+
+        if let Some(gi) = self.gi {
+            known |= 0x04;
+            flags |= flag_if(gi == GuardInterval::Short, 0x04);
+        }
+
+        if let Some(format) = self.format {
+            known |= 0x08;
+            flags |= flag_if(format == HTFormat::Greenfield, 0x08);
+        }
+
+        if let Some(fec) = self.fec {
+            known |= 0x10;
+            flags |= flag_if(fec == FEC::LDPC, 0x10);
+        }
+
+        if let Some(stbc) = self.stbc {
+            known |= 0x20;
+            flags |= (stbc & 0x03) << 5;
+        }
+
+        if let Some(ness) = self.ness {
+            known |= 0x40 | (ness & 0x02) << 6;
+            flags |= (ness & 0x01) << 7;
+        }
+
+        writer.write_u8(known)?;
+        writer.write_u8(flags)?;
+        writer.write_u8(self.index.unwrap_or(0))?;
+        Ok(3)
+    }
 }
 
 /// The presence of this field indicates that the frame was received as part of
 /// an a-MPDU.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AMPDUStatus {
     /// The A-MPDU reference number.
     pub reference: u32,
@@ -692,14 +2056,14 @@ pub struct AMPDUStatus {
 
 impl Field for AMPDUStatus {
     fn from_bytes(input: &[u8]) -> Result<AMPDUStatus> {
-        let mut cursor = Cursor::new(input);
+        let mut reader = Reader::new(input);
         let mut ampdu = AMPDUStatus {
             ..Default::default()
         };
 
-        ampdu.reference = cursor.read_u32::<LE>()?;
-        let flags = cursor.read_u16::<LE>()?;
-        let delim_crc = cursor.read_u8()?;
+        ampdu.reference = reader.read_u32()?;
+        let flags = reader.read_u16()?;
+        let delim_crc = reader.read_u8()?;
 
         if flags.is_flag_set(0x0001) {
             ampdu.zero_length = Some(flags.is_flag_set(0x0002));
@@ -715,12 +2079,37 @@ impl Field for AMPDUStatus {
 
         Ok(ampdu)
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        let mut flags = 0;
+
+        if let Some(zero_length) = self.zero_length {
+            flags |= 0x0001 | flag_if(zero_length, 0x0002);
+        }
+
+        if let Some(last) = self.last {
+            flags |= 0x0004 | flag_if(last, 0x0008);
+        }
+
+        if self.delimiter_crc.is_some() {
+            flags |= 0x0020;
+        }
+
+        writer.write_u32::<LE>(self.reference)?;
+        writer.write_u16::<LE>(flags)?;
+        writer.write_u8(self.delimiter_crc.unwrap_or(0))?;
+        writer.write_u8(0)?; // Reserved
+        Ok(8)
+    }
 }
 
 /// The IEEE 802.11ac data rate index. Usually only one of the
 /// [Rate](struct.Rate.html), [MCS](struct.MCS.html), and [VHT](struct.VHT.html)
 /// fields is present.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct VHT {
     /// Whether all spatial streams of all users have STBC.
     pub stbc: Option<bool>,
@@ -748,19 +2137,19 @@ pub struct VHT {
 
 impl Field for VHT {
     fn from_bytes(input: &[u8]) -> Result<VHT> {
-        let mut cursor = Cursor::new(input);
+        let mut reader = Reader::new(input);
         let mut vht = VHT {
             ..Default::default()
         };
 
-        let known = cursor.read_u16::<LE>()?;
-        let flags = cursor.read_u8()?;
-        let bandwidth = cursor.read_u8()?;
+        let known = reader.read_u16()?;
+        let flags = reader.read_u8()?;
+        let bandwidth = reader.read_u8()?;
         let mut mcs_nss = [0; 4];
-        cursor.read_exact(&mut mcs_nss)?;
-        let coding = cursor.read_u8()?;
-        let group_id = cursor.read_u8()?;
-        let partial_aid = cursor.read_u16::<LE>()?;
+        reader.read_exact(&mut mcs_nss)?;
+        let coding = reader.read_u8()?;
+        let group_id = reader.read_u8()?;
+        let partial_aid = reader.read_u16()?;
 
         if known.is_flag_set(0x0001) {
             vht.stbc = Some(flags.is_flag_set(0x01));
@@ -810,18 +2199,18 @@ impl Field for VHT {
             }
 
             let index = (user & 0xf0) >> 4;
-            let nsts = nss << (flags & 0x01);
+            // STBC doubles the space-time streams, only if it is known
+            let nsts = nss << u8::from(vht.stbc == Some(true));
             let id = i as u8;
 
-            let datarate = if vht.bw.is_some() && vht.gi.is_some() {
-                Some(vht_rate(index, vht.bw.unwrap(), vht.gi.unwrap(), nss)?)
-            } else {
-                None
+            let datarate = match (vht.bw, vht.gi) {
+                (Some(bw), Some(gi)) => vht_rate(index, bw, gi, nss).ok(),
+                _ => None,
             };
 
             vht.users[id as usize] = Some(VHTUser {
                 index,
-                fec: match (coding & 2 ^ id) >> id {
+                fec: match (coding >> id) & 0x01 {
                     1 => FEC::LDPC,
                     _ => FEC::BCC,
                 },
@@ -833,10 +2222,65 @@ impl Field for VHT {
 
         Ok(vht)
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        let mut known = 0;
+        let mut flags = 0;
+        let mut bandwidth = 0;
+        let mut mcs_nss = [0; 4];
+        let mut coding = 0;
+
+        let bits = [
+            (self.stbc, 0x01),
+            (self.txop_ps, 0x02),
+            (self.gi.map(|gi| gi == GuardInterval::Short), 0x04),
+            (self.sgi_nsym_da, 0x08),
+            (self.ldpc_extra, 0x10),
+            (self.beamformed, 0x20),
+        ];
+        for &(value, flag) in bits.iter() {
+            if let Some(value) = value {
+                known |= u16::from(flag);
+                flags |= flag_if(value, flag);
+            }
+        }
+
+        if let Some(bw) = self.bw {
+            known |= 0x0040;
+            bandwidth = bw.value()?;
+        }
+
+        if self.group_id.is_some() {
+            known |= 0x0080;
+        }
+
+        if self.partial_aid.is_some() {
+            known |= 0x0100;
+        }
+
+        for (i, user) in self.users.iter().enumerate() {
+            if let Some(user) = user {
+                mcs_nss[i] = user.index << 4 | (user.nss & 0x0f);
+                coding |= flag_if(user.fec == FEC::LDPC, 1 << i);
+            }
+        }
+
+        writer.write_u16::<LE>(known)?;
+        writer.write_u8(flags)?;
+        writer.write_u8(bandwidth)?;
+        writer.write_all(&mcs_nss)?;
+        writer.write_u8(coding)?;
+        writer.write_u8(self.group_id.unwrap_or(0))?;
+        writer.write_u16::<LE>(self.partial_aid.unwrap_or(0))?;
+        Ok(12)
+    }
 }
 
 /// The time the frame was transmitted or received.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Timestamp {
     /// The actual timestamp.
     pub timestamp: u64,
@@ -850,14 +2294,14 @@ pub struct Timestamp {
 
 impl Field for Timestamp {
     fn from_bytes(input: &[u8]) -> Result<Timestamp> {
-        let mut cursor = Cursor::new(input);
+        let mut reader = Reader::new(input);
 
-        let timestamp = cursor.read_u64::<LE>()?;
-        let mut accuracy = Some(cursor.read_u16::<LE>()?);
-        let unit_position = cursor.read_u8()?;
+        let timestamp = reader.read_u64()?;
+        let mut accuracy = Some(reader.read_u16()?);
+        let unit_position = reader.read_u8()?;
         let unit = TimeUnit::new(unit_position & 0x0f)?;
-        let position = SamplingPosition::from(unit_position & 0xf0 >> 4)?;
-        let flags = cursor.read_u8()?;
+        let position = SamplingPosition::from((unit_position & 0xf0) >> 4)?;
+        let flags = reader.read_u8()?;
 
         if !flags.is_flag_set(0x02) {
             accuracy = None;
@@ -870,4 +2314,1126 @@ impl Field for Timestamp {
             accuracy,
         })
     }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_u64::<LE>(self.timestamp)?;
+        writer.write_u16::<LE>(self.accuracy.unwrap_or(0))?;
+        writer.write_u8(self.unit.value() | self.position.value() << 4)?;
+        writer.write_u8(flag_if(self.accuracy.is_some(), 0x02))?;
+        Ok(12)
+    }
+}
+
+/// The IEEE 802.11ax (HE) PPDU information.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct HE {
+    /// The HE PPDU format.
+    pub format: Option<HEFormat>,
+    /// The BSS color.
+    pub bss_color: Option<u8>,
+    /// Whether the beam changed between the pre-HE and HE modulated fields.
+    pub beam_change: Option<bool>,
+    /// Whether the PPDU was sent uplink.
+    pub uplink: Option<bool>,
+    /// The 802.11ax MCS index.
+    pub index: Option<u8>,
+    /// Whether dual carrier modulation was used.
+    pub dcm: Option<bool>,
+    /// The FEC type.
+    pub fec: Option<FEC>,
+    /// Whether the LDPC encoding process resulted in an extra symbol segment.
+    pub ldpc_extra: Option<bool>,
+    /// Whether STBC was used.
+    pub stbc: Option<bool>,
+    /// The spatial reuse values, only trigger based PPDUs have more than one.
+    pub spatial_reuse: [Option<u8>; 4],
+    /// The STA-ID of the user the data belongs to, for MU PPDUs.
+    pub sta_id: Option<u16>,
+    /// The bandwidth or resource unit size of the data.
+    pub bw: Option<HEBandwidth>,
+    /// The offset of the resource unit the data was received on.
+    pub ru_offset: Option<u8>,
+    /// Whether the data was received on the secondary 80 MHz channel.
+    pub secondary_80: Option<bool>,
+    /// The guard interval.
+    pub gi: Option<HEGuardInterval>,
+    /// The size of the long training field symbols.
+    pub ltf_size: Option<LTFSize>,
+    /// The number of long training field symbols.
+    pub ltf_symbols: Option<u8>,
+    /// The pre-FEC padding factor.
+    pub pre_fec_padding: Option<u8>,
+    /// The frame was transmitted/received using beamforming.
+    pub beamformed: Option<bool>,
+    /// The packet extension disambiguity.
+    pub pe_disambiguity: Option<bool>,
+    /// Number of space-time streams.
+    pub nsts: Option<u8>,
+    /// Whether doppler was used.
+    pub doppler: Option<bool>,
+    /// The TXOP duration.
+    pub txop: Option<u8>,
+    /// The midamble periodicity in symbols.
+    pub midamble_periodicity: Option<u8>,
+}
+
+impl Field for HE {
+    fn from_bytes(input: &[u8]) -> Result<HE> {
+        let mut reader = Reader::new(input);
+        let mut he = HE {
+            ..Default::default()
+        };
+
+        let data1 = reader.read_u16()?;
+        let data2 = reader.read_u16()?;
+        let data3 = reader.read_u16()?;
+        let data4 = reader.read_u16()?;
+        let data5 = reader.read_u16()?;
+        let data6 = reader.read_u16()?;
+
+        let format = HEFormat::new(data1.bits_as_int(0, 2) as u8)?;
+        he.format = Some(format);
+
+        if data1.is_flag_set(0x0004) {
+            he.bss_color = Some(data3.bits_as_int(0, 6) as u8);
+        }
+
+        if data1.is_flag_set(0x0008) {
+            he.beam_change = Some(data3.is_flag_set(0x0040));
+        }
+
+        if data1.is_flag_set(0x0010) {
+            he.uplink = Some(data3.is_flag_set(0x0080));
+        }
+
+        if data1.is_flag_set(0x0020) {
+            he.index = Some(data3.bits_as_int(8, 4) as u8);
+        }
+
+        if data1.is_flag_set(0x0040) {
+            he.dcm = Some(data3.is_flag_set(0x1000));
+        }
+
+        if data1.is_flag_set(0x0080) {
+            he.fec = Some(if data3.is_flag_set(0x2000) {
+                FEC::LDPC
+            } else {
+                FEC::BCC
+            });
+        }
+
+        if data1.is_flag_set(0x0100) {
+            he.ldpc_extra = Some(data3.is_flag_set(0x4000));
+        }
+
+        if data1.is_flag_set(0x0200) {
+            he.stbc = Some(data3.is_flag_set(0x8000));
+        }
+
+        if format == HEFormat::Trig {
+            // Trigger based PPDUs have four spatial reuse values
+            for (i, flag) in [0x0400, 0x0800, 0x1000, 0x2000].iter().enumerate() {
+                if data1.is_flag_set(*flag) {
+                    he.spatial_reuse[i] = Some(data4.bits_as_int(i as u8 * 4, 4) as u8);
+                }
+            }
+        } else {
+            if data1.is_flag_set(0x0400) {
+                he.spatial_reuse[0] = Some(data4.bits_as_int(0, 4) as u8);
+            }
+            if format == HEFormat::MU && data1.is_flag_set(0x0800) {
+                he.sta_id = Some(data4.bits_as_int(4, 11));
+            }
+        }
+
+        if data1.is_flag_set(0x4000) {
+            he.bw = Some(HEBandwidth::new(data5.bits_as_int(0, 4) as u8)?);
+        }
+
+        if data1.is_flag_set(0x8000) {
+            he.doppler = Some(data6.is_flag_set(0x0010));
+        }
+
+        if data2.is_flag_set(0x0001) {
+            he.secondary_80 = Some(data2.is_flag_set(0x8000));
+        }
+
+        if data2.is_flag_set(0x0002) {
+            he.gi = Some(HEGuardInterval::new(data5.bits_as_int(4, 2) as u8)?);
+        }
+
+        he.ltf_size = LTFSize::new(data5.bits_as_int(6, 2) as u8);
+
+        if data2.is_flag_set(0x0004) {
+            he.ltf_symbols = Some(match data5.bits_as_int(8, 3) {
+                0 => 1,
+                1 => 2,
+                2 => 4,
+                3 => 6,
+                4 => 8,
+                _ => return Err(Error::InvalidFormat),
+            });
+        }
+
+        if data2.is_flag_set(0x0008) {
+            he.pre_fec_padding = Some(data5.bits_as_int(12, 2) as u8);
+        }
+
+        if data2.is_flag_set(0x0010) {
+            he.beamformed = Some(data5.is_flag_set(0x4000));
+        }
+
+        if data2.is_flag_set(0x0020) {
+            he.pe_disambiguity = Some(data5.is_flag_set(0x8000));
+        }
+
+        if data2.is_flag_set(0x0040) {
+            he.txop = Some(data6.bits_as_int(8, 7) as u8);
+        }
+
+        if data2.is_flag_set(0x0080) {
+            he.midamble_periodicity = Some(if data6.is_flag_set(0x8000) { 20 } else { 10 });
+        }
+
+        if data2.is_flag_set(0x4000) {
+            he.ru_offset = Some(data2.bits_as_int(8, 6) as u8);
+        }
+
+        he.nsts = Some(data6.bits_as_int(0, 4) as u8).filter(|&nsts| nsts != 0);
+
+        Ok(he)
+    }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        let format = self.format.unwrap_or(HEFormat::SU);
+        let mut data1 = u16::from(format.value());
+        let mut data2 = 0;
+        let mut data3 = 0;
+        let mut data4 = 0;
+        let mut data5 = 0;
+        let mut data6 = 0;
+
+        if let Some(bss_color) = self.bss_color {
+            data1 |= 0x0004;
+            data3 |= u16::from(bss_color & 0x3f);
+        }
+
+        let bits = [
+            (self.beam_change, 0x0008, 0x0040),
+            (self.uplink, 0x0010, 0x0080),
+            (self.dcm, 0x0040, 0x1000),
+            (self.fec.map(|fec| fec == FEC::LDPC), 0x0080, 0x2000),
+            (self.ldpc_extra, 0x0100, 0x4000),
+            (self.stbc, 0x0200, 0x8000),
+        ];
+        for &(value, known, flag) in bits.iter() {
+            if let Some(value) = value {
+                data1 |= known;
+                data3 |= flag_if(value, flag);
+            }
+        }
+
+        if let Some(index) = self.index {
+            data1 |= 0x0020;
+            data3 |= u16::from(index & 0x0f) << 8;
+        }
+
+        if format == HEFormat::Trig {
+            for (i, spatial_reuse) in self.spatial_reuse.iter().enumerate() {
+                if let Some(spatial_reuse) = spatial_reuse {
+                    data1 |= 0x0400 << i;
+                    data4 |= u16::from(spatial_reuse & 0x0f) << (i * 4);
+                }
+            }
+        } else {
+            if let Some(spatial_reuse) = self.spatial_reuse[0] {
+                data1 |= 0x0400;
+                data4 |= u16::from(spatial_reuse & 0x0f);
+            }
+            if let Some(sta_id) = self.sta_id.filter(|_| format == HEFormat::MU) {
+                data1 |= 0x0800;
+                data4 |= (sta_id & 0x07ff) << 4;
+            }
+        }
+
+        if let Some(bw) = self.bw {
+            data1 |= 0x4000;
+            data5 |= u16::from(bw.value()?);
+        }
+
+        if let Some(doppler) = self.doppler {
+            data1 |= 0x8000;
+            data6 |= flag_if(doppler, 0x0010);
+        }
+
+        if let Some(secondary_80) = self.secondary_80 {
+            data2 |= 0x0001 | flag_if(secondary_80, 0x8000);
+        }
+
+        if let Some(gi) = self.gi {
+            data2 |= 0x0002;
+            data5 |= u16::from(gi.value()) << 4;
+        }
+
+        if let Some(ltf_size) = self.ltf_size {
+            data5 |= u16::from(ltf_size.value()) << 6;
+        }
+
+        if let Some(ltf_symbols) = self.ltf_symbols {
+            data2 |= 0x0004;
+            data5 |= match ltf_symbols {
+                1 => 0,
+                2 => 1,
+                4 => 2,
+                6 => 3,
+                8 => 4,
+                _ => return Err(Error::InvalidFormat),
+            } << 8;
+        }
+
+        if let Some(pre_fec_padding) = self.pre_fec_padding {
+            data2 |= 0x0008;
+            data5 |= u16::from(pre_fec_padding & 0x03) << 12;
+        }
+
+        if let Some(beamformed) = self.beamformed {
+            data2 |= 0x0010;
+            data5 |= flag_if(beamformed, 0x4000);
+        }
+
+        if let Some(pe_disambiguity) = self.pe_disambiguity {
+            data2 |= 0x0020;
+            data5 |= flag_if(pe_disambiguity, 0x8000);
+        }
+
+        if let Some(txop) = self.txop {
+            data2 |= 0x0040;
+            data6 |= u16::from(txop & 0x7f) << 8;
+        }
+
+        if let Some(midamble_periodicity) = self.midamble_periodicity {
+            data2 |= 0x0080;
+            data6 |= flag_if(midamble_periodicity == 20, 0x8000);
+        }
+
+        if let Some(ru_offset) = self.ru_offset {
+            data2 |= 0x4000;
+            data2 |= u16::from(ru_offset & 0x3f) << 8;
+        }
+
+        data6 |= u16::from(self.nsts.unwrap_or(0) & 0x0f);
+
+        for data in [data1, data2, data3, data4, data5, data6].iter() {
+            writer.write_u16::<LE>(*data)?;
+        }
+        Ok(12)
+    }
+}
+
+/// The IEEE 802.11ax (HE) multi user PPDU information, from the HE-SIG-A and
+/// HE-SIG-B fields.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct HEMU {
+    /// The MCS index of the HE-SIG-B field.
+    pub sigb_mcs: Option<u8>,
+    /// Whether the HE-SIG-B field used dual carrier modulation.
+    pub sigb_dcm: Option<bool>,
+    /// Whether the HE-SIG-B field was compressed.
+    pub sigb_compression: Option<bool>,
+    /// The number of HE-SIG-B symbols, or the number of MU-MIMO users if the
+    /// HE-SIG-B field was compressed.
+    pub sigb_symbols_or_users: Option<u8>,
+    /// The bandwidth from the HE-SIG-A field, 160 MHz includes 80+80 MHz.
+    pub bw: Option<Bandwidth>,
+    /// The preamble puncturing from the HE-SIG-A field.
+    pub preamble_puncturing: Option<u8>,
+    /// The RU allocation subfields of content channel 1.
+    pub ru_channel1: Option<[u8; 4]>,
+    /// The RU allocation subfields of content channel 2.
+    pub ru_channel2: Option<[u8; 4]>,
+    /// Whether the center 26-tone RU is used, reported in content channel 1.
+    pub center_ru_channel1: Option<bool>,
+    /// Whether the center 26-tone RU is used, reported in content channel 2.
+    pub center_ru_channel2: Option<bool>,
+}
+
+impl Field for HEMU {
+    fn from_bytes(input: &[u8]) -> Result<HEMU> {
+        let mut reader = Reader::new(input);
+        let mut he_mu = HEMU {
+            ..Default::default()
+        };
+
+        let flags1 = reader.read_u16()?;
+        let flags2 = reader.read_u16()?;
+        let mut ru_channel1 = [0; 4];
+        reader.read_exact(&mut ru_channel1)?;
+        let mut ru_channel2 = [0; 4];
+        reader.read_exact(&mut ru_channel2)?;
+
+        if flags1.is_flag_set(0x0010) {
+            he_mu.sigb_mcs = Some(flags1.bits_as_int(0, 4) as u8);
+        }
+
+        if flags1.is_flag_set(0x0040) {
+            he_mu.sigb_dcm = Some(flags1.is_flag_set(0x0020));
+        }
+
+        if flags1.is_flag_set(0x0080) {
+            he_mu.center_ru_channel2 = Some(flags2.is_flag_set(0x0800));
+        }
+
+        if flags1.is_flag_set(0x0100) {
+            he_mu.ru_channel1 = Some(ru_channel1);
+        }
+
+        if flags1.is_flag_set(0x0200) {
+            he_mu.ru_channel2 = Some(ru_channel2);
+        }
+
+        if flags1.is_flag_set(0x1000) {
+            he_mu.center_ru_channel1 = Some(flags1.is_flag_set(0x2000));
+        }
+
+        if flags1.is_flag_set(0x4000) {
+            he_mu.sigb_compression = Some(flags2.is_flag_set(0x0008));
+        }
+
+        if flags1.is_flag_set(0x8000) {
+            he_mu.sigb_symbols_or_users = Some(flags2.bits_as_int(4, 4) as u8 + 1);
+        }
+
+        if flags2.is_flag_set(0x0004) {
+            he_mu.bw = Some(match flags2.bits_as_int(0, 2) {
+                0 => Bandwidth::new(0)?,
+                1 => Bandwidth::new(1)?,
+                2 => Bandwidth::new(4)?,
+                _ => Bandwidth::new(11)?,
+            });
+        }
+
+        if flags2.is_flag_set(0x0400) {
+            he_mu.preamble_puncturing = Some(flags2.bits_as_int(8, 2) as u8);
+        }
+
+        Ok(he_mu)
+    }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        let mut flags1 = 0;
+        let mut flags2 = 0;
+
+        if let Some(sigb_mcs) = self.sigb_mcs {
+            flags1 |= 0x0010 | u16::from(sigb_mcs & 0x0f);
+        }
+
+        if let Some(sigb_dcm) = self.sigb_dcm {
+            flags1 |= 0x0040 | flag_if(sigb_dcm, 0x0020);
+        }
+
+        if let Some(center_ru) = self.center_ru_channel2 {
+            flags1 |= 0x0080;
+            flags2 |= flag_if(center_ru, 0x0800);
+        }
+
+        if self.ru_channel1.is_some() {
+            flags1 |= 0x0100;
+        }
+
+        if self.ru_channel2.is_some() {
+            flags1 |= 0x0200;
+        }
+
+        if let Some(center_ru) = self.center_ru_channel1 {
+            flags1 |= 0x1000 | flag_if(center_ru, 0x2000);
+        }
+
+        if let Some(sigb_compression) = self.sigb_compression {
+            flags1 |= 0x4000;
+            flags2 |= flag_if(sigb_compression, 0x0008);
+        }
+
+        if let Some(symbols_or_users) = self.sigb_symbols_or_users {
+            flags1 |= 0x8000;
+            flags2 |= u16::from(symbols_or_users.wrapping_sub(1) & 0x0f) << 4;
+        }
+
+        if let Some(bw) = self.bw {
+            flags2 |= 0x0004;
+            flags2 |= match bw.bandwidth {
+                20 => 0,
+                40 => 1,
+                80 => 2,
+                160 => 3,
+                _ => return Err(Error::InvalidFormat),
+            };
+        }
+
+        if let Some(preamble_puncturing) = self.preamble_puncturing {
+            flags2 |= 0x0400 | u16::from(preamble_puncturing & 0x03) << 8;
+        }
+
+        writer.write_u16::<LE>(flags1)?;
+        writer.write_u16::<LE>(flags2)?;
+        writer.write_all(&self.ru_channel1.unwrap_or_default())?;
+        writer.write_all(&self.ru_channel2.unwrap_or_default())?;
+        Ok(12)
+    }
+}
+
+impl HE {
+    /// Returns the number of spatial streams, derived from the number of
+    /// space-time streams and whether STBC was used.
+    pub fn nss(&self) -> Option<u8> {
+        let nsts = self.nsts?;
+        Some(if self.stbc == Some(true) {
+            nsts / 2
+        } else {
+            nsts
+        })
+    }
+
+    /// Returns the data rate in Mbps, see [he_rate](ext/fn.he_rate.html), or
+    /// `None` unless the index, bandwidth, guard interval and number of
+    /// space-time streams are known and the combination is valid. DCM is
+    /// assumed unused if unknown.
+    pub fn datarate(&self) -> Option<f32> {
+        let (index, bw, gi, nss) = (self.index?, self.bw?, self.gi?, self.nss()?);
+        he_rate(index, bw, gi, nss, self.dcm == Some(true)).ok()
+    }
+}
+
+impl HEMU {
+    /// Returns the number of RU allocation subfields in each content channel
+    /// for the bandwidth.
+    #[cfg(feature = "alloc")]
+    fn ru_subfields(&self) -> usize {
+        match self.bw.map(|bw| bw.bandwidth) {
+            Some(20) | Some(40) => 1,
+            Some(80) => 2,
+            _ => 4,
+        }
+    }
+
+    /// Returns an iterator over the decoded RU allocation subfields that are
+    /// known and used by the bandwidth, yielding the content channel (1 or 2)
+    /// and the allocation.
+    #[cfg(feature = "alloc")]
+    pub fn ru_allocations(&self) -> impl Iterator<Item = Result<(u8, RUAllocation)>> {
+        let count = self.ru_subfields();
+        // A 20 MHz PPDU only has a single content channel
+        let channel2 = self
+            .ru_channel2
+            .filter(|_| self.bw.map(|bw| bw.bandwidth) != Some(20));
+        let channel1 = self
+            .ru_channel1
+            .into_iter()
+            .flatten()
+            .take(count)
+            .map(|v| (1, v));
+        let channel2 = channel2.into_iter().flatten().take(count).map(|v| (2, v));
+        channel1
+            .chain(channel2)
+            .map(|(channel, value)| Ok((channel, RUAllocation::new(value)?)))
+    }
+
+    /// Returns the HE-SIG-B user fields described by the RU allocation
+    /// subfields, in the order they appear in each content channel.
+    #[cfg(feature = "alloc")]
+    pub fn users(&self) -> Result<Vec<HEMUUser>> {
+        let mut users = Vec::new();
+        let mut positions = [0u8; 2];
+
+        let mut push = |channel: u8, size, mu_mimo| -> Result<()> {
+            let position = &mut positions[usize::from(channel - 1)];
+            users.try_reserve(1)?;
+            users.push(HEMUUser {
+                channel,
+                position: *position,
+                size,
+                mu_mimo,
+            });
+            *position = position.wrapping_add(1);
+            Ok(())
+        };
+
+        for allocation in self.ru_allocations() {
+            let (channel, allocation) = allocation?;
+            for unit in allocation.units {
+                for _ in 0..unit.users {
+                    push(channel, unit.size, unit.users > 1)?;
+                }
+            }
+        }
+
+        // The user field of the center 26-tone RU follows all the others
+        if self.center_ru_channel1 == Some(true) {
+            push(1, RUSize::RU26, false)?;
+        }
+        if self.center_ru_channel2 == Some(true) {
+            push(2, RUSize::RU26, false)?;
+        }
+
+        Ok(users)
+    }
+}
+
+/// The HE-SIG-B user field of another user in an IEEE 802.11ax (HE) multi
+/// user PPDU.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct HEMUOtherUser {
+    /// The position of the user field within the content channel.
+    pub position: Option<u8>,
+    /// The STA-ID of the user.
+    pub sta_id: Option<u16>,
+    /// Number of space-time streams, for non-MU-MIMO users.
+    pub nsts: Option<u8>,
+    /// Whether the user was beamformed, for non-MU-MIMO users.
+    pub beamformed: Option<bool>,
+    /// The spatial configuration, for MU-MIMO users.
+    pub spatial_config: Option<u8>,
+    /// The 802.11ax MCS index.
+    pub index: Option<u8>,
+    /// Whether dual carrier modulation was used.
+    pub dcm: Option<bool>,
+    /// The FEC type.
+    pub fec: Option<FEC>,
+}
+
+impl Field for HEMUOtherUser {
+    fn from_bytes(input: &[u8]) -> Result<HEMUOtherUser> {
+        let mut reader = Reader::new(input);
+        let mut user = HEMUOtherUser {
+            ..Default::default()
+        };
+
+        let per_user1 = reader.read_u16()?;
+        let per_user2 = reader.read_u16()?;
+        let position = reader.read_u8()?;
+        let known = reader.read_u8()?;
+
+        if known.is_flag_set(0x01) {
+            user.position = Some(position);
+        }
+
+        if known.is_flag_set(0x02) {
+            user.sta_id = Some(per_user1.bits_as_int(0, 11));
+        }
+
+        if known.is_flag_set(0x04) {
+            user.nsts = Some(per_user1.bits_as_int(11, 3) as u8 + 1);
+        }
+
+        if known.is_flag_set(0x08) {
+            user.beamformed = Some(per_user1.is_flag_set(0x4000));
+        }
+
+        if known.is_flag_set(0x10) {
+            user.spatial_config = Some(per_user1.bits_as_int(11, 4) as u8);
+        }
+
+        if known.is_flag_set(0x20) {
+            user.index = Some(per_user2.bits_as_int(0, 4) as u8);
+        }
+
+        if known.is_flag_set(0x40) {
+            user.dcm = Some(per_user2.is_flag_set(0x0010));
+        }
+
+        if known.is_flag_set(0x80) {
+            user.fec = Some(if per_user2.is_flag_set(0x0020) {
+                FEC::LDPC
+            } else {
+                FEC::BCC
+            });
+        }
+
+        Ok(user)
+    }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        let mut per_user1 = 0;
+        let mut per_user2 = 0;
+        let mut known = 0;
+
+        if self.position.is_some() {
+            known |= 0x01;
+        }
+
+        if let Some(sta_id) = self.sta_id {
+            known |= 0x02;
+            per_user1 |= sta_id & 0x07ff;
+        }
+
+        if let Some(nsts) = self.nsts {
+            known |= 0x04;
+            per_user1 |= u16::from(nsts.wrapping_sub(1) & 0x07) << 11;
+        }
+
+        if let Some(beamformed) = self.beamformed {
+            known |= 0x08;
+            per_user1 |= flag_if(beamformed, 0x4000);
+        }
+
+        if let Some(spatial_config) = self.spatial_config {
+            known |= 0x10;
+            per_user1 |= u16::from(spatial_config & 0x0f) << 11;
+        }
+
+        if let Some(index) = self.index {
+            known |= 0x20;
+            per_user2 |= u16::from(index & 0x0f);
+        }
+
+        if let Some(dcm) = self.dcm {
+            known |= 0x40;
+            per_user2 |= flag_if(dcm, 0x0010);
+        }
+
+        if let Some(fec) = self.fec {
+            known |= 0x80;
+            per_user2 |= flag_if(fec == FEC::LDPC, 0x0020);
+        }
+
+        writer.write_u16::<LE>(per_user1)?;
+        writer.write_u16::<LE>(per_user2)?;
+        writer.write_u8(self.position.unwrap_or(0))?;
+        writer.write_u8(known)?;
+        Ok(6)
+    }
+}
+
+/// The legacy signal field (L-SIG) of the PPDU preamble.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct LSig {
+    /// The RATE subfield, as encoded in the L-SIG.
+    pub rate: Option<u8>,
+    /// The LENGTH subfield, the length of the PSDU in bytes.
+    pub length: Option<u16>,
+}
+
+impl LSig {
+    /// Returns the legacy data rate in Mbps decoded from the RATE subfield.
+    pub fn datarate(&self) -> Option<f32> {
+        Some(match self.rate? {
+            0b1011 => 6.0,
+            0b1111 => 9.0,
+            0b1010 => 12.0,
+            0b1110 => 18.0,
+            0b1001 => 24.0,
+            0b1101 => 36.0,
+            0b1000 => 48.0,
+            0b1100 => 54.0,
+            _ => return None,
+        })
+    }
 }
+
+impl Field for LSig {
+    fn from_bytes(input: &[u8]) -> Result<LSig> {
+        let mut reader = Reader::new(input);
+        let mut l_sig = LSig {
+            ..Default::default()
+        };
+
+        let known = reader.read_u16()?;
+        let data = reader.read_u16()?;
+
+        if known.is_flag_set(0x0001) {
+            l_sig.rate = Some(data.bits_as_int(0, 4) as u8);
+        }
+
+        if known.is_flag_set(0x0002) {
+            l_sig.length = Some(data.bits_as_int(4, 12));
+        }
+
+        Ok(l_sig)
+    }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        let mut known = 0;
+        let mut data = 0;
+
+        if let Some(rate) = self.rate {
+            known |= 0x0001;
+            data |= u16::from(rate & 0x0f);
+        }
+
+        if let Some(length) = self.length {
+            known |= 0x0002;
+            data |= (length & 0x0fff) << 4;
+        }
+
+        writer.write_u16::<LE>(known)?;
+        writer.write_u16::<LE>(data)?;
+        Ok(4)
+    }
+}
+
+/// The IEEE 802.11be universal signal field (U-SIG), carried in a TLV.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct USig {
+    /// The PHY version identifier, 0 for 802.11be.
+    pub phy_version: Option<u8>,
+    /// The bandwidth.
+    pub bw: Option<EHTBandwidth>,
+    /// Whether the PPDU was sent uplink.
+    pub uplink: Option<bool>,
+    /// The BSS color.
+    pub bss_color: Option<u8>,
+    /// The TXOP duration.
+    pub txop: Option<u8>,
+    /// Whether the U-SIG CRC check failed.
+    pub bad_crc: bool,
+    /// Whether the validate bits were set as required, if they were checked.
+    pub validate_ok: Option<bool>,
+    /// The remaining, PPDU type specific, U-SIG bits.
+    pub value: u32,
+    /// The mask of the remaining U-SIG bits that are known.
+    pub mask: u32,
+}
+
+impl Field for USig {
+    fn from_bytes(input: &[u8]) -> Result<USig> {
+        let mut reader = Reader::new(input);
+        let mut u_sig = USig {
+            ..Default::default()
+        };
+
+        let common = reader.read_u32()?;
+        u_sig.value = reader.read_u32()?;
+        u_sig.mask = reader.read_u32()?;
+
+        if common.is_flag_set(0x0000_0001) {
+            u_sig.phy_version = Some(common.bits_as_int(12, 3) as u8);
+        }
+
+        if common.is_flag_set(0x0000_0002) {
+            u_sig.bw = Some(EHTBandwidth::new(common.bits_as_int(15, 3) as u8)?);
+        }
+
+        if common.is_flag_set(0x0000_0004) {
+            u_sig.uplink = Some(common.is_flag_set(0x0004_0000));
+        }
+
+        if common.is_flag_set(0x0000_0008) {
+            u_sig.bss_color = Some(common.bits_as_int(19, 6) as u8);
+        }
+
+        if common.is_flag_set(0x0000_0010) {
+            u_sig.txop = Some(common.bits_as_int(25, 7) as u8);
+        }
+
+        u_sig.bad_crc = common.is_flag_set(0x0000_0020);
+
+        if common.is_flag_set(0x0000_0040) {
+            u_sig.validate_ok = Some(common.is_flag_set(0x0000_0080));
+        }
+
+        Ok(u_sig)
+    }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        let mut common = flag_if(self.bad_crc, 0x0000_0020);
+
+        if let Some(phy_version) = self.phy_version {
+            common |= 0x0000_0001 | u32::from(phy_version & 0x07) << 12;
+        }
+
+        if let Some(bw) = self.bw {
+            common |= 0x0000_0002 | u32::from(bw.value()) << 15;
+        }
+
+        if let Some(uplink) = self.uplink {
+            common |= 0x0000_0004 | flag_if(uplink, 0x0004_0000);
+        }
+
+        if let Some(bss_color) = self.bss_color {
+            common |= 0x0000_0008 | u32::from(bss_color & 0x3f) << 19;
+        }
+
+        if let Some(txop) = self.txop {
+            common |= 0x0000_0010 | u32::from(txop & 0x7f) << 25;
+        }
+
+        if let Some(validate_ok) = self.validate_ok {
+            common |= 0x0000_0040 | flag_if(validate_ok, 0x0000_0080);
+        }
+
+        writer.write_u32::<LE>(common)?;
+        writer.write_u32::<LE>(self.value)?;
+        writer.write_u32::<LE>(self.mask)?;
+        Ok(12)
+    }
+}
+
+/// The IEEE 802.11be (EHT) PPDU information, carried in a TLV.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct EHT {
+    /// The spatial reuse value.
+    pub spatial_reuse: Option<u8>,
+    /// The guard interval.
+    pub gi: Option<HEGuardInterval>,
+    /// The size of the long training field symbols.
+    pub ltf_size: Option<LTFSize>,
+    /// The number of long training field symbols.
+    pub ltf_symbols: Option<u8>,
+    /// Whether the LDPC encoding process resulted in an extra symbol segment.
+    pub ldpc_extra: Option<bool>,
+    /// The pre-FEC padding factor.
+    pub pre_fec_padding: Option<u8>,
+    /// The packet extension disambiguity.
+    pub pe_disambiguity: Option<bool>,
+    /// The size of the RU or MRU the data was received on, as encoded in the
+    /// EHT field.
+    pub ru_size: Option<u8>,
+    /// The index of the RU or MRU the data was received on.
+    pub ru_index: Option<u8>,
+    /// The position of the primary 80 MHz channel within the bandwidth.
+    pub primary_80: Option<u8>,
+    /// Number of spatial streams, for single user PPDUs.
+    pub nss: Option<u8>,
+    /// The frame was transmitted/received using beamforming, for single user
+    /// PPDUs.
+    pub beamformed: Option<bool>,
+    /// The number of non-OFDMA users.
+    pub non_ofdma_users: Option<u8>,
+    /// The 9-bit RU allocation subfields of content channels 1 and 2, in the
+    /// order they appear in the EHT-SIG field.
+    pub ru_allocations: [[Option<u16>; 8]; 2],
+    /// The users described by the user info words. Without the `alloc`
+    /// feature, decode them from the data with
+    /// [EHTUsers](ext/struct.EHTUsers.html).
+    #[cfg(feature = "alloc")]
+    pub users: Vec<EHTUser>,
+}
+
+impl Field for EHT {
+    fn from_bytes(input: &[u8]) -> Result<EHT> {
+        let mut reader = Reader::new(input);
+        let mut eht = EHT {
+            ..Default::default()
+        };
+
+        let known = reader.read_u32()?;
+        let mut data = [0; 9];
+        reader.read_u32_into(&mut data)?;
+
+        if known.is_flag_set(0x0000_0002) {
+            eht.spatial_reuse = Some(data[0].bits_as_int(3, 4) as u8);
+        }
+
+        if known.is_flag_set(0x0000_0004) {
+            eht.gi = Some(HEGuardInterval::new(data[0].bits_as_int(7, 2) as u8)?);
+            eht.ltf_size = LTFSize::new(data[0].bits_as_int(9, 2) as u8 + 1);
+        }
+
+        if known.is_flag_set(0x0000_0010) {
+            eht.ltf_symbols = Some(match data[0].bits_as_int(11, 3) {
+                0 => 1,
+                1 => 2,
+                2 => 4,
+                3 => 6,
+                4 => 8,
+                _ => return Err(Error::InvalidFormat),
+            });
+        }
+
+        if known.is_flag_set(0x0000_0020) {
+            eht.ldpc_extra = Some(data[0].is_flag_set(0x0000_4000));
+        }
+
+        if known.is_flag_set(0x0000_0040) {
+            eht.pre_fec_padding = Some(data[0].bits_as_int(15, 2) as u8);
+        }
+
+        if known.is_flag_set(0x0000_0080) {
+            eht.pe_disambiguity = Some(data[0].is_flag_set(0x0002_0000));
+        }
+
+        if known.is_flag_set(0x0002_0000) {
+            eht.nss = Some(data[7].bits_as_int(12, 4) as u8);
+        }
+
+        if known.is_flag_set(0x0004_0000) {
+            eht.beamformed = Some(data[7].is_flag_set(0x0001_0000));
+        }
+
+        if known.is_flag_set(0x0008_0000) {
+            eht.non_ofdma_users = Some(data[7].bits_as_int(17, 3) as u8);
+        }
+
+        if known.is_flag_set(0x0040_0000) {
+            eht.ru_size = Some(data[1].bits_as_int(0, 5) as u8);
+        }
+
+        if known.is_flag_set(0x0080_0000) {
+            eht.ru_index = Some(data[1].bits_as_int(5, 8) as u8);
+        }
+
+        if known.is_flag_set(0x0200_0000) {
+            eht.primary_80 = Some(data[1].bits_as_int(30, 2) as u8);
+        }
+
+        for (channel, subfields) in EHT_RU_ALLOCATIONS.iter().enumerate() {
+            for (i, &(word, shift)) in subfields.iter().enumerate() {
+                // Each subfield is followed by its known bit
+                let word = data[word];
+                if word.is_bit_set(shift + 9) {
+                    eht.ru_allocations[channel][i] = Some(word.bits_as_int(shift, 9) as u16);
+                }
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        {
+            let users = EHTUsers::new(&input[40.min(input.len())..]);
+            eht.users.try_reserve(users.len())?;
+            eht.users.extend(users);
+        }
+
+        Ok(eht)
+    }
+
+    fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        let mut known = 0;
+        let mut data = [0u32; 9];
+
+        if let Some(spatial_reuse) = self.spatial_reuse {
+            known |= 0x0000_0002;
+            data[0] |= u32::from(spatial_reuse & 0x0f) << 3;
+        }
+
+        // The guard interval and LTF size share a known bit
+        if let Some(gi) = self.gi {
+            known |= 0x0000_0004;
+            data[0] |= u32::from(gi.value()) << 7;
+        }
+
+        if let Some(ltf_size) = self.ltf_size {
+            data[0] |= u32::from(ltf_size.value() - 1) << 9;
+        }
+
+        if let Some(ltf_symbols) = self.ltf_symbols {
+            known |= 0x0000_0010;
+            data[0] |= match ltf_symbols {
+                1 => 0,
+                2 => 1,
+                4 => 2,
+                6 => 3,
+                8 => 4,
+                _ => return Err(Error::InvalidFormat),
+            } << 11;
+        }
+
+        if let Some(ldpc_extra) = self.ldpc_extra {
+            known |= 0x0000_0020;
+            data[0] |= flag_if(ldpc_extra, 0x0000_4000);
+        }
+
+        if let Some(pre_fec_padding) = self.pre_fec_padding {
+            known |= 0x0000_0040;
+            data[0] |= u32::from(pre_fec_padding & 0x03) << 15;
+        }
+
+        if let Some(pe_disambiguity) = self.pe_disambiguity {
+            known |= 0x0000_0080;
+            data[0] |= flag_if(pe_disambiguity, 0x0002_0000);
+        }
+
+        if let Some(nss) = self.nss {
+            known |= 0x0002_0000;
+            data[7] |= u32::from(nss & 0x0f) << 12;
+        }
+
+        if let Some(beamformed) = self.beamformed {
+            known |= 0x0004_0000;
+            data[7] |= flag_if(beamformed, 0x0001_0000);
+        }
+
+        if let Some(non_ofdma_users) = self.non_ofdma_users {
+            known |= 0x0008_0000;
+            data[7] |= u32::from(non_ofdma_users & 0x07) << 17;
+        }
+
+        if let Some(ru_size) = self.ru_size {
+            known |= 0x0040_0000;
+            data[1] |= u32::from(ru_size & 0x1f);
+        }
+
+        if let Some(ru_index) = self.ru_index {
+            known |= 0x0080_0000;
+            data[1] |= u32::from(ru_index) << 5;
+        }
+
+        if let Some(primary_80) = self.primary_80 {
+            known |= 0x0200_0000;
+            data[1] |= u32::from(primary_80 & 0x03) << 30;
+        }
+
+        for (channel, subfields) in EHT_RU_ALLOCATIONS.iter().enumerate() {
+            for (i, &(word, shift)) in subfields.iter().enumerate() {
+                if let Some(allocation) = self.ru_allocations[channel][i] {
+                    data[word] |= (u32::from(allocation) & 0x01ff | 0x0200) << shift;
+                }
+            }
+        }
+
+        writer.write_u32::<LE>(known)?;
+        for word in data.iter() {
+            writer.write_u32::<LE>(*word)?;
+        }
+        #[cfg(feature = "alloc")]
+        {
+            for user in self.users.iter() {
+                writer.write_u32::<LE>(user.value())?;
+            }
+            Ok(40 + 4 * self.users.len())
+        }
+        #[cfg(not(feature = "alloc"))]
+        Ok(40)
+    }
+}
+
+/// The data word and bit offset of each RU allocation subfield in the EHT
+/// field, for content channels 1 and 2.
+const EHT_RU_ALLOCATIONS: [[(usize, u8); 8]; 2] = [
+    [
+        (1, 13),
+        (2, 10),
+        (3, 0),
+        (3, 20),
+        (4, 10),
+        (5, 0),
+        (5, 20),
+        (6, 10),
+    ],
+    [
+        (2, 0),
+        (2, 20),
+        (3, 10),
+        (4, 0),
+        (4, 20),
+        (5, 10),
+        (6, 0),
+        (6, 20),
+    ],
+];
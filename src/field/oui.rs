@@ -0,0 +1,34 @@
+//! The manufacturers of well known OUIs, enabled with the `oui-names`
+//! feature.
+
+/// The manufacturer of each OUI, sorted by OUI.
+pub(crate) const MANUFACTURERS: &[([u8; 3], &str)] = &[
+    ([0x00, 0x03, 0x7f], "Atheros Communications"),
+    ([0x00, 0x0c, 0x43], "Ralink Technology"),
+    ([0x00, 0x0c, 0xe7], "MediaTek"),
+    ([0x00, 0x0f, 0xac], "IEEE 802.11"),
+    ([0x00, 0x10, 0x18], "Broadcom"),
+    ([0x00, 0x17, 0xf2], "Apple"),
+    ([0x00, 0x1b, 0x21], "Intel"),
+    ([0x00, 0x26, 0x86], "Quantenna Communications"),
+    ([0x00, 0x40, 0x96], "Cisco Systems"),
+    ([0x00, 0x50, 0xf2], "Microsoft"),
+    ([0x00, 0x90, 0x4c], "Epigram"),
+    ([0x00, 0xa0, 0xc6], "Qualcomm"),
+    ([0x00, 0xe0, 0x4c], "Realtek Semiconductor"),
+    ([0x50, 0x6f, 0x9a], "Wi-Fi Alliance"),
+    ([0x8c, 0xfd, 0xf0], "Qualcomm"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::Oui;
+
+    #[test]
+    fn manufacturers() {
+        assert!(MANUFACTURERS.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(Oui([0x00, 0x10, 0x18]).manufacturer(), Some("Broadcom"));
+        assert_eq!(Oui([0xf6, 0x54, 0x25]).manufacturer(), None);
+    }
+}
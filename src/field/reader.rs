@@ -0,0 +1,95 @@
+//! Little endian reads directly from a byte slice.
+
+use crate::{io, Error, Result};
+
+/// Reads little endian values from the start of a byte slice, advancing past
+/// each value read.
+///
+/// Reading past the end fails with the same `UnexpectedEof` error as reading
+/// from a `std::io::Cursor`.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Returns a reader at the start of the data.
+    pub(crate) fn new(data: &'a [u8]) -> Reader<'a> {
+        Reader { data, position: 0 }
+    }
+
+    /// Returns the number of bytes read so far.
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the next `N` bytes.
+    fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let bytes = self
+            .data
+            .get(self.position..self.position + N)
+            .ok_or_else(|| Error::ParseError(io::ErrorKind::UnexpectedEof.into()))?;
+        self.position += N;
+        let mut array = [0; N];
+        array.copy_from_slice(bytes);
+        Ok(array)
+    }
+
+    /// Fills the buffer with the next bytes.
+    pub(crate) fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        let bytes = self
+            .data
+            .get(self.position..self.position + buf.len())
+            .ok_or_else(|| Error::ParseError(io::ErrorKind::UnexpectedEof.into()))?;
+        self.position += buf.len();
+        buf.copy_from_slice(bytes);
+        Ok(())
+    }
+
+    pub(crate) fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    pub(crate) fn read_i8(&mut self) -> Result<i8> {
+        Ok(self.take::<1>()?[0] as i8)
+    }
+
+    pub(crate) fn read_u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take()?))
+    }
+
+    pub(crate) fn read_u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    /// Fills the buffer with the next `u32` values.
+    pub(crate) fn read_u32_into(&mut self, buf: &mut [u32]) -> Result<()> {
+        for value in buf {
+            *value = self.read_u32()?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn read_u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads() {
+        let mut reader = Reader::new(&[1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(reader.read_u8().unwrap(), 1);
+        assert_eq!(reader.read_u16().unwrap(), 0x0302);
+        assert_eq!(reader.read_u32().unwrap(), 0x0706_0504);
+        assert_eq!(reader.position(), 7);
+        match reader.read_i8().unwrap_err() {
+            Error::ParseError(e) => assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof),
+            e => panic!("Error not ParseError: {:?}", e),
+        }
+    }
+}
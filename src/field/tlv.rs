@@ -0,0 +1,208 @@
+//! Type-length-value (TLV) encoded Radiotap fields.
+//!
+//! When the TLV present bit (28) is set, the fixed fields are followed by a
+//! list of TLV records, each a little endian `u16` type and `u16` length
+//! followed by the data, padded to a multiple of 4 bytes. The types of fields
+//! that are also defined by a present bit are the same as their present bit.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::{convert::TryFrom, iter::FusedIterator};
+
+use byteorder::LE;
+
+#[cfg(feature = "alloc")]
+use crate::field::{Field, Oui, VendorData};
+use crate::{
+    field::{from_bytes, Kind, USig, Value, EHT as EHTField},
+    io::{Write, WriteBytesExt},
+    Error, Result,
+};
+
+/// The TLV type of a vendor namespace.
+pub const VENDOR_NAMESPACE: u16 = 30;
+/// The TLV type of the 802.11ah S1G field.
+pub const S1G: u16 = 32;
+/// The TLV type of the 802.11be U-SIG field.
+pub const U_SIG: u16 = 33;
+/// The TLV type of the 802.11be EHT field.
+pub const EHT: u16 = 34;
+
+/// A decoded TLV record.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Tlv<'a> {
+    /// A field that is also defined by a present bit, with the same contents.
+    Field(Value),
+    /// The 802.11be U-SIG field.
+    USig(USig),
+    /// The 802.11be EHT field.
+    EHT(EHTField),
+    /// A record this crate does not know how to decode.
+    Raw {
+        /// The TLV type.
+        kind: u16,
+        /// The TLV data, without padding.
+        data: &'a [u8],
+    },
+}
+
+impl<'a> Tlv<'a> {
+    /// Decodes the data of a TLV record of the given type.
+    pub fn new(kind: u16, data: &'a [u8]) -> Result<Tlv<'a>> {
+        match kind {
+            U_SIG => return Ok(Tlv::USig(from_bytes(data)?)),
+            EHT => return Ok(Tlv::EHT(from_bytes(data)?)),
+            _ => {}
+        }
+        match Value::from_bytes(Kind::TLV(Some(kind)), data) {
+            Ok(value) => Ok(Tlv::Field(value)),
+            Err(Error::UnsupportedField) => Ok(Tlv::Raw { kind, data }),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Writes a field as a TLV record of the given type, padded to a multiple of
+/// 4 bytes, returning the number of bytes written.
+#[cfg(feature = "alloc")]
+pub fn unparse<T, W>(kind: u16, field: &T, writer: &mut W) -> Result<usize>
+where
+    T: Field,
+    W: Write,
+{
+    let mut data = Vec::new();
+    field.unparse(&mut data)?;
+    unparse_data(kind, &data, writer)
+}
+
+/// Writes a TLV record of the given type and data, padded to a multiple of 4
+/// bytes, returning the number of bytes written.
+pub fn unparse_data<W: Write>(kind: u16, data: &[u8], writer: &mut W) -> Result<usize> {
+    let length = u16::try_from(data.len()).map_err(|_| Error::InvalidLength)?;
+    let padding = (4 - data.len() % 4) % 4;
+
+    writer.write_u16::<LE>(kind)?;
+    writer.write_u16::<LE>(length)?;
+    writer.write_all(data)?;
+    writer.write_all(&[0; 3][..padding])?;
+    Ok(4 + data.len() + padding)
+}
+
+/// Writes a vendor namespace as a TLV record, returning the number of bytes
+/// written.
+///
+/// The data of a vendor namespace TLV starts with the OUI, the sub namespace,
+/// and a vendor defined data type and reserved field, both written as 0.
+#[cfg(feature = "alloc")]
+pub fn unparse_vendor<W: Write>(vendor: &VendorData, writer: &mut W) -> Result<usize> {
+    let mut data = Vec::new();
+    data.try_reserve(8 + vendor.data.len())?;
+    data.extend_from_slice(&vendor.oui.0);
+    data.extend_from_slice(&[vendor.sub_namespace, 0, 0, 0, 0]);
+    data.extend_from_slice(&vendor.data);
+    unparse_data(VENDOR_NAMESPACE, &data, writer)
+}
+
+/// Decodes the data of a vendor namespace TLV record, the inverse of
+/// [unparse_vendor](fn.unparse_vendor.html). The vendor data type is ignored.
+#[cfg(feature = "alloc")]
+pub fn parse_vendor(data: &[u8]) -> Result<VendorData> {
+    if data.len() < 8 {
+        return Err(Error::IncompleteError);
+    }
+    let mut vendor = VendorData {
+        oui: Oui([data[0], data[1], data[2]]),
+        sub_namespace: data[3],
+        present: Vec::new(),
+        data: Vec::new(),
+    };
+    vendor.data.try_reserve(data.len() - 8)?;
+    vendor.data.extend_from_slice(&data[8..]);
+    Ok(vendor)
+}
+
+/// An iterator over TLV records, yielding the type and data of each.
+#[derive(Clone, Debug)]
+pub struct Tlvs<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Tlvs<'a> {
+    /// Returns an iterator over the TLV records in the given data, which must
+    /// start at the first record.
+    pub fn new(data: &'a [u8]) -> Tlvs<'a> {
+        Tlvs { data }
+    }
+}
+
+impl<'a> Iterator for Tlvs<'a> {
+    type Item = Result<(u16, &'a [u8])>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let data = self.data;
+        if data.len() < 4 {
+            self.data = &[];
+            return Some(Err(Error::IncompleteError));
+        }
+
+        let kind = u16::from_le_bytes([data[0], data[1]]);
+        let length = usize::from(u16::from_le_bytes([data[2], data[3]]));
+        let end = 4 + length;
+        if end > data.len() {
+            self.data = &[];
+            return Some(Err(Error::IncompleteError));
+        }
+
+        // The padding after the last record may be missing
+        let padded = ((end + 3) & !3).min(data.len());
+        self.data = &data[padded..];
+        Some(Ok((kind, &data[4..end])))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Every record has a header of 4 bytes, except for a truncated last
+        // one
+        let length = self.data.len();
+        (length.min(1), Some(length.div_ceil(4)))
+    }
+}
+
+impl FusedIterator for Tlvs<'_> {}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::field::AntennaSignal;
+
+    #[test]
+    fn tlvs() {
+        let data = [5, 0, 1, 0, 214, 0, 0, 0, 99, 0, 2, 0, 1, 2];
+        let tlvs: Vec<_> = Tlvs::new(&data)
+            .map(|tlv| {
+                let (kind, data) = tlv.unwrap();
+                Tlv::new(kind, data).unwrap()
+            })
+            .collect();
+        assert_eq!(
+            tlvs,
+            [
+                Tlv::Field(Value::AntennaSignal(AntennaSignal { value: -42 })),
+                Tlv::Raw {
+                    kind: 99,
+                    data: &[1, 2]
+                },
+            ]
+        );
+
+        let mut tlvs = Tlvs::new(&data[..10]);
+        assert!(tlvs.next().unwrap().is_ok());
+        assert!(matches!(tlvs.next(), Some(Err(Error::IncompleteError))));
+        assert!(tlvs.next().is_none());
+    }
+}
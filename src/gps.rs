@@ -0,0 +1,244 @@
+//! Correlation of captures with an external GPS track, for site surveys and
+//! wardriving.
+//!
+//! A [Track](struct.Track.html) is built from timestamped positions, either
+//! directly or by reading NMEA 0183 `RMC` sentences as logged by most GPS
+//! receivers. Positions between two samples are linearly interpolated. Other
+//! formats such as GPX are easily converted to
+//! [Sample](struct.Sample.html)s by the caller.
+//!
+//! All times are in seconds since the Unix epoch, the same as pcap
+//! timestamps.
+
+use alloc::vec::Vec;
+
+/// A geographic position.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Position {
+    /// The latitude in degrees, positive for north.
+    pub latitude: f64,
+    /// The longitude in degrees, positive for east.
+    pub longitude: f64,
+    /// The altitude in meters above mean sea level.
+    pub altitude: Option<f64>,
+}
+
+/// A position at a point in time.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Sample {
+    /// The time in seconds since the Unix epoch.
+    pub time: f64,
+    /// The position at that time.
+    pub position: Position,
+}
+
+/// A GPS track that can be queried for the position at any time it covers.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Track {
+    samples: Vec<Sample>,
+    max_gap: Option<f64>,
+}
+
+impl Track {
+    /// Returns a new track from the given samples, in any order.
+    pub fn new(mut samples: Vec<Sample>) -> Track {
+        samples.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Track {
+            samples,
+            max_gap: None,
+        }
+    }
+
+    /// Returns a new track from NMEA 0183 sentences, one per line. Only `RMC`
+    /// sentences with a valid fix are used, all other lines are ignored.
+    pub fn from_nmea(input: &str) -> Track {
+        Track::new(input.lines().filter_map(parse_rmc).collect())
+    }
+
+    /// Sets the largest gap in seconds between two samples over which a
+    /// position is interpolated. Times within a larger gap have no position.
+    pub fn max_gap(mut self, max_gap: f64) -> Track {
+        self.max_gap = Some(max_gap);
+        self
+    }
+
+    /// Returns the samples in the track, ordered by time.
+    pub fn samples(&self) -> &[Sample] {
+        &self.samples
+    }
+
+    /// Returns the interpolated position at the given time, or `None` if the
+    /// time is not covered by the track.
+    pub fn position_at(&self, time: f64) -> Option<Position> {
+        let index = self.samples.partition_point(|sample| sample.time < time);
+        let after = self.samples.get(index)?;
+        if after.time == time {
+            return Some(after.position);
+        }
+        let before = self.samples.get(index.checked_sub(1)?)?;
+
+        let gap = after.time - before.time;
+        if self.max_gap.is_some_and(|max_gap| gap > max_gap) {
+            return None;
+        }
+
+        let t = (time - before.time) / gap;
+        let lerp = |a: f64, b: f64| a + (b - a) * t;
+        let (a, b) = (before.position, after.position);
+        Some(Position {
+            latitude: lerp(a.latitude, b.latitude),
+            longitude: lerp(a.longitude, b.longitude),
+            altitude: match (a.altitude, b.altitude) {
+                (Some(a), Some(b)) => Some(lerp(a, b)),
+                _ => None,
+            },
+        })
+    }
+
+    /// Attaches the position to each item of a stream of timestamped captures.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::{
+    ///     gps::{Position, Sample, Track},
+    ///     Radiotap,
+    /// };
+    ///
+    /// let track = Track::new(vec![
+    ///     Sample { time: 10.0, position: Position { latitude: 1.0, ..Default::default() } },
+    ///     Sample { time: 20.0, position: Position { latitude: 2.0, ..Default::default() } },
+    /// ]);
+    ///
+    /// let captures = vec![(15.0, Radiotap::default()), (25.0, Radiotap::default())];
+    /// let latitudes: Vec<_> = track
+    ///     .correlate(captures)
+    ///     .map(|(_, position)| position.map(|p| p.latitude))
+    ///     .collect();
+    /// assert_eq!(latitudes, [Some(1.5), None]);
+    /// ```
+    pub fn correlate<'a, I, T>(
+        &'a self,
+        captures: I,
+    ) -> impl Iterator<Item = (T, Option<Position>)> + 'a
+    where
+        I: IntoIterator<Item = (f64, T)>,
+        I::IntoIter: 'a,
+    {
+        captures
+            .into_iter()
+            .map(move |(time, capture)| (capture, self.position_at(time)))
+    }
+}
+
+/// Parses an NMEA 0183 `RMC` sentence into a sample, returning `None` if the
+/// sentence is not `RMC`, is malformed, or has no valid fix.
+fn parse_rmc(line: &str) -> Option<Sample> {
+    let line = line.trim().strip_prefix('$')?;
+    let line = match line.split_once('*') {
+        Some((data, checksum)) => {
+            let checksum = u8::from_str_radix(checksum, 16).ok()?;
+            if data.bytes().fold(0, |acc, byte| acc ^ byte) != checksum {
+                return None;
+            }
+            data
+        }
+        None => line,
+    };
+
+    let fields: Vec<_> = line.split(',').collect();
+    if fields.len() < 10 || !fields[0].ends_with("RMC") || fields[2] != "A" {
+        return None;
+    }
+
+    let time = fields[1];
+    let date = fields[9];
+    if time.len() < 6 || date.len() != 6 {
+        return None;
+    }
+    let hour: f64 = time.get(0..2)?.parse().ok()?;
+    let minute: f64 = time.get(2..4)?.parse().ok()?;
+    let second: f64 = time.get(4..)?.parse().ok()?;
+    let day: i64 = date.get(0..2)?.parse().ok()?;
+    let month: i64 = date.get(2..4)?.parse().ok()?;
+    let year: i64 = date.get(4..6)?.parse().ok()?;
+    let year = if year < 80 { 2000 + year } else { 1900 + year };
+
+    let days = days_from_civil(year, month, day) as f64;
+    Some(Sample {
+        time: days * 86400.0 + hour * 3600.0 + minute * 60.0 + second,
+        position: Position {
+            latitude: parse_coordinate(fields[3], fields[4], 'S')?,
+            longitude: parse_coordinate(fields[5], fields[6], 'W')?,
+            altitude: None,
+        },
+    })
+}
+
+/// Parses an NMEA coordinate in the `dddmm.mmmm` format into degrees.
+fn parse_coordinate(value: &str, hemisphere: &str, negative: char) -> Option<f64> {
+    let value: f64 = value.parse().ok()?;
+    let degrees = (value - value % 100.0) / 100.0 + (value % 100.0) / 60.0;
+    if hemisphere.starts_with(negative) {
+        Some(-degrees)
+    } else {
+        Some(degrees)
+    }
+}
+
+/// Returns the number of days since the Unix epoch for a date in the proleptic
+/// Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nmea() {
+        let track = Track::from_nmea(
+            "$GPRMC,123519,A,4807.038,N,01131.000,E,022.4,084.4,230394,003.1,W*6A\n\
+             $GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47\n\
+             $GPRMC,123521,A,4807.048,N,01131.010,W,022.4,084.4,230394,003.1,W\n\
+             $GPRMC,123522,V,4807.048,N,01131.010,E,022.4,084.4,230394,003.1,W\n\
+             $GPRMC,123523,A,4807.048,N,01131.010,E,022.4,084.4,230394,003.1,W*00\n",
+        )
+        .max_gap(5.0);
+
+        assert_eq!(track.samples().len(), 2);
+        assert_eq!(track.samples()[0].time, 764_426_119.0);
+        assert!((track.samples()[0].position.latitude - 48.1173).abs() < 1e-9);
+        assert!(track.samples()[1].position.longitude < 0.0);
+
+        let position = track.position_at(764_426_120.0).unwrap();
+        assert!((position.latitude - 48.117_383_333).abs() < 1e-6);
+        assert_eq!(position.altitude, None);
+        assert_eq!(track.position_at(764_426_118.0), None);
+        assert_eq!(track.position_at(764_426_122.0), None);
+
+        // Non-ASCII dates and times are skipped
+        let track = Track::from_nmea(
+            "$GPRMC,123519,A,4807.038,N,01131.000,E,022.4,084.4,1é234,003.1,W\n\
+             $GPRMC,1é3519,A,4807.038,N,01131.000,E,022.4,084.4,230394,003.1,W\n",
+        );
+        assert!(track.samples().is_empty());
+    }
+
+    #[test]
+    fn max_gap() {
+        let sample = |time| Sample {
+            time,
+            position: Position::default(),
+        };
+        let track = Track::new(vec![sample(20.0), sample(0.0)]);
+        assert!(track.position_at(10.0).is_some());
+        assert!(track.max_gap(5.0).position_at(10.0).is_none());
+    }
+}
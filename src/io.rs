@@ -0,0 +1,173 @@
+//! The writer used to serialize captures.
+//!
+//! With the `std` feature this is `std::io`. Without it, a minimal
+//! replacement with the same names is provided, implemented for `Vec<u8>` and
+//! `&mut [u8]`, so the same code writes captures on `no_std` targets.
+
+use byteorder::ByteOrder;
+
+#[cfg(feature = "std")]
+pub use std::io::{sink, Error, ErrorKind, Result, Sink, Write};
+
+#[cfg(not(feature = "std"))]
+pub use self::core_io::*;
+
+#[cfg(not(feature = "std"))]
+mod core_io {
+    #[cfg(feature = "alloc")]
+    use alloc::vec::Vec;
+    use core::{fmt, result};
+
+    /// The kind of an [Error](struct.Error.html).
+    #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+    #[non_exhaustive]
+    pub enum ErrorKind {
+        /// The data ended before a complete value could be read.
+        UnexpectedEof,
+        /// The writer could not accept all of the data.
+        WriteZero,
+        /// Any other error.
+        Other,
+    }
+
+    /// An error returned by a [Write](trait.Write.html) implementation.
+    #[derive(Debug)]
+    pub struct Error {
+        kind: ErrorKind,
+    }
+
+    impl Error {
+        /// Returns the kind of the error.
+        pub fn kind(&self) -> ErrorKind {
+            self.kind
+        }
+    }
+
+    impl From<ErrorKind> for Error {
+        fn from(kind: ErrorKind) -> Error {
+            Error { kind }
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self.kind {
+                ErrorKind::UnexpectedEof => f.write_str("failed to fill whole buffer"),
+                ErrorKind::WriteZero => f.write_str("failed to write whole buffer"),
+                ErrorKind::Other => f.write_str("other error"),
+            }
+        }
+    }
+
+    impl core::error::Error for Error {}
+
+    pub type Result<T> = result::Result<T, Error>;
+
+    /// A sink of bytes, with the subset of `std::io::Write` used by this
+    /// crate.
+    pub trait Write {
+        /// Writes some of the buffer, returning how many bytes were written.
+        fn write(&mut self, buf: &[u8]) -> Result<usize>;
+
+        /// Flushes any buffered data.
+        fn flush(&mut self) -> Result<()>;
+
+        /// Writes the whole buffer.
+        fn write_all(&mut self, mut buf: &[u8]) -> Result<()> {
+            while !buf.is_empty() {
+                match self.write(buf)? {
+                    0 => return Err(ErrorKind::WriteZero.into()),
+                    n => buf = &buf[n..],
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl<W: Write + ?Sized> Write for &mut W {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            (**self).write(buf)
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            (**self).flush()
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl Write for Vec<u8> {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            self.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Write for &mut [u8] {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            let n = buf.len().min(self.len());
+            let (a, b) = core::mem::take(self).split_at_mut(n);
+            a.copy_from_slice(&buf[..n]);
+            *self = b;
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// A writer discarding all data, see [sink](fn.sink.html).
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct Sink;
+
+    /// Returns a writer discarding all data.
+    pub fn sink() -> Sink {
+        Sink
+    }
+
+    impl Write for Sink {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+}
+
+/// Writes integers to a [Write](trait.Write.html) in a given byte order, like
+/// `byteorder::WriteBytesExt` which is only available with `std`.
+pub(crate) trait WriteBytesExt: Write {
+    fn write_u8(&mut self, n: u8) -> Result<()> {
+        self.write_all(&[n])
+    }
+
+    fn write_i8(&mut self, n: i8) -> Result<()> {
+        self.write_all(&[n as u8])
+    }
+
+    fn write_u16<B: ByteOrder>(&mut self, n: u16) -> Result<()> {
+        let mut buf = [0; 2];
+        B::write_u16(&mut buf, n);
+        self.write_all(&buf)
+    }
+
+    fn write_u32<B: ByteOrder>(&mut self, n: u32) -> Result<()> {
+        let mut buf = [0; 4];
+        B::write_u32(&mut buf, n);
+        self.write_all(&buf)
+    }
+
+    fn write_u64<B: ByteOrder>(&mut self, n: u64) -> Result<()> {
+        let mut buf = [0; 8];
+        B::write_u64(&mut buf, n);
+        self.write_all(&buf)
+    }
+}
+
+impl<W: Write + ?Sized> WriteBytesExt for W {}
@@ -0,0 +1,309 @@
+//! Export of Radiotap captures as JSON, using the field names of the
+//! Wireshark Radiotap dissector.
+
+use alloc::string::String;
+use core::fmt::{self, Display, Write};
+
+use crate::{
+    field::{ext::*, *},
+    Radiotap,
+};
+
+impl Radiotap {
+    /// Returns the Radiotap capture as a JSON object, using the field names of
+    /// the Wireshark Radiotap dissector.
+    ///
+    /// The object is flat and its keys are Wireshark display filter names
+    /// such as `radiotap.dbm_antsignal`, so the output can be indexed next to
+    /// that of `tshark`. Flags are booleans, and enumerations are written as
+    /// their Radiotap encoding, like Wireshark does. Fields without a well
+    /// known Wireshark name, such as the HE and EHT fields, vendor namespaces
+    /// and additional radiotap namespaces, are not written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::{field::*, RadiotapBuilder};
+    ///
+    /// let radiotap = RadiotapBuilder::new()
+    ///     .rate(Rate::from_mbps(6.0).unwrap())
+    ///     .antenna_signal(AntennaSignal { value: -42 })
+    ///     .done();
+    /// assert_eq!(
+    ///     radiotap.to_json(),
+    ///     r#"{"radiotap.version":0,"radiotap.length":10,"radiotap.datarate":6,"radiotap.dbm_antsignal":-42}"#
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut object = Object::new();
+        object.write(self);
+        object.finish()
+    }
+}
+
+/// A JSON object being written.
+struct Object {
+    out: String,
+    empty: bool,
+}
+
+impl Object {
+    fn new() -> Object {
+        Object {
+            out: String::from("{"),
+            empty: true,
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.out.push('}');
+        self.out
+    }
+
+    /// Adds a member, the names used are plain ASCII so they are not escaped.
+    fn field<T: Display>(&mut self, name: &str, value: T) {
+        if !self.empty {
+            self.out.push(',');
+        }
+        self.empty = false;
+        // Writing to a String cannot fail
+        let _ = write!(self.out, "\"{}\":{}", name, value);
+    }
+
+    fn field_opt<T: Display>(&mut self, name: &str, value: Option<T>) {
+        if let Some(value) = value {
+            self.field(name, value);
+        }
+    }
+
+    fn write(&mut self, radiotap: &Radiotap) {
+        self.field("radiotap.version", radiotap.header.version);
+        self.field("radiotap.length", radiotap.header.length);
+
+        if let Some(tsft) = radiotap.tsft {
+            self.field("radiotap.mactime", tsft.value);
+        }
+        if let Some(flags) = radiotap.flags {
+            self.flags(&flags);
+        }
+        if let Some(rate) = radiotap.rate {
+            self.field("radiotap.datarate", Float(rate.as_mbps()));
+        }
+        if let Some(channel) = radiotap.channel {
+            self.field("radiotap.channel.freq", channel.freq);
+            self.channel_flags(&channel.flags);
+        }
+        if let Some(fhss) = radiotap.fhss {
+            self.field("radiotap.fhss.hopset", fhss.hopset);
+            self.field("radiotap.fhss.pattern", fhss.pattern);
+        }
+        if let Some(signal) = radiotap.antenna_signal {
+            self.field("radiotap.dbm_antsignal", signal.value);
+        }
+        if let Some(noise) = radiotap.antenna_noise {
+            self.field("radiotap.dbm_antnoise", noise.value);
+        }
+        if let Some(quality) = radiotap.lock_quality {
+            self.field("radiotap.quality", quality.value);
+        }
+        if let Some(attenuation) = radiotap.tx_attenuation {
+            self.field("radiotap.txattenuation", attenuation.value);
+        }
+        if let Some(attenuation) = radiotap.tx_attenuation_db {
+            self.field("radiotap.db_txattenuation", attenuation.value);
+        }
+        if let Some(power) = radiotap.tx_power {
+            self.field("radiotap.dbm_tx_power", power.value);
+        }
+        if let Some(antenna) = radiotap.antenna {
+            self.field("radiotap.antenna", antenna.value);
+        }
+        if let Some(signal) = radiotap.antenna_signal_db {
+            self.field("radiotap.db_antsignal", signal.value);
+        }
+        if let Some(noise) = radiotap.antenna_noise_db {
+            self.field("radiotap.db_antnoise", noise.value);
+        }
+        if let Some(rx_flags) = radiotap.rx_flags {
+            self.field("radiotap.rxflags.badplcp", rx_flags.bad_plcp);
+        }
+        if let Some(tx_flags) = radiotap.tx_flags {
+            self.field("radiotap.txflags.fail", tx_flags.fail);
+            self.field("radiotap.txflags.cts", tx_flags.cts);
+            self.field("radiotap.txflags.rts", tx_flags.rts);
+            self.field("radiotap.txflags.noack", tx_flags.no_ack);
+            self.field("radiotap.txflags.noseq", tx_flags.no_seq);
+        }
+        if let Some(retries) = radiotap.rts_retries {
+            self.field("radiotap.rts_retries", retries.value);
+        }
+        if let Some(retries) = radiotap.data_retries {
+            self.field("radiotap.data_retries", retries.value);
+        }
+        if let Some(xchannel) = radiotap.xchannel {
+            self.xchannel(&xchannel);
+        }
+        if let Some(mcs) = radiotap.mcs {
+            self.mcs(&mcs);
+        }
+        if let Some(ampdu) = radiotap.ampdu_status {
+            self.field("radiotap.ampdu.reference", ampdu.reference);
+            self.field_opt("radiotap.ampdu.flags.is_zerolen", ampdu.zero_length);
+            self.field_opt("radiotap.ampdu.flags.last", ampdu.last);
+            self.field_opt("radiotap.ampdu.delim_crc", ampdu.delimiter_crc);
+        }
+        if let Some(vht) = radiotap.vht {
+            self.vht(&vht);
+        }
+        if let Some(timestamp) = radiotap.timestamp {
+            self.field("radiotap.timestamp.ts", timestamp.timestamp);
+            self.field("radiotap.timestamp.unit", timestamp.unit.value());
+            self.field("radiotap.timestamp.samplingpos", timestamp.position.value());
+            self.field_opt("radiotap.timestamp.accuracy", timestamp.accuracy);
+        }
+    }
+
+    fn flags(&mut self, flags: &Flags) {
+        self.field("radiotap.flags.cfp", flags.cfp);
+        self.field("radiotap.flags.preamble", flags.preamble);
+        self.field("radiotap.flags.wep", flags.wep);
+        self.field("radiotap.flags.frag", flags.fragmentation);
+        self.field("radiotap.flags.fcs", flags.fcs);
+        self.field("radiotap.flags.datapad", flags.data_pad);
+        self.field("radiotap.flags.badfcs", flags.bad_fcs);
+        self.field("radiotap.flags.shortgi", flags.sgi);
+    }
+
+    fn channel_flags(&mut self, flags: &ChannelFlags) {
+        self.field("radiotap.channel.flags.turbo", flags.turbo);
+        self.field("radiotap.channel.flags.cck", flags.cck);
+        self.field("radiotap.channel.flags.ofdm", flags.ofdm);
+        self.field("radiotap.channel.flags.2ghz", flags.ghz2);
+        self.field("radiotap.channel.flags.5ghz", flags.ghz5);
+        self.field("radiotap.channel.flags.passive", flags.passive);
+        self.field("radiotap.channel.flags.dynamic", flags.dynamic);
+        self.field("radiotap.channel.flags.gfsk", flags.gfsk);
+    }
+
+    fn xchannel(&mut self, xchannel: &XChannel) {
+        let flags = &xchannel.flags;
+        self.field("radiotap.xchannel.flags.turbo", flags.turbo);
+        self.field("radiotap.xchannel.flags.cck", flags.cck);
+        self.field("radiotap.xchannel.flags.ofdm", flags.ofdm);
+        self.field("radiotap.xchannel.flags.2ghz", flags.ghz2);
+        self.field("radiotap.xchannel.flags.5ghz", flags.ghz5);
+        self.field("radiotap.xchannel.flags.passive", flags.passive);
+        self.field("radiotap.xchannel.flags.dynamic", flags.dynamic);
+        self.field("radiotap.xchannel.flags.gfsk", flags.gfsk);
+        self.field("radiotap.xchannel.flags.gsm", flags.gsm);
+        self.field("radiotap.xchannel.flags.sturbo", flags.sturbo);
+        self.field("radiotap.xchannel.flags.half", flags.half);
+        self.field("radiotap.xchannel.flags.quarter", flags.quarter);
+        self.field("radiotap.xchannel.flags.ht20", flags.ht20);
+        self.field("radiotap.xchannel.flags.ht40u", flags.ht40u);
+        self.field("radiotap.xchannel.flags.ht40d", flags.ht40d);
+        self.field("radiotap.xchannel.freq", xchannel.freq);
+        self.field("radiotap.xchannel.channel", xchannel.channel);
+        self.field("radiotap.xchannel.max_power", xchannel.max_power);
+    }
+
+    fn mcs(&mut self, mcs: &MCS) {
+        self.field_opt("radiotap.mcs.bw", mcs.bw.and_then(|bw| bw.value().ok()));
+        self.field_opt("radiotap.mcs.index", mcs.index);
+        self.field_opt("radiotap.mcs.gi", mcs.gi.map(gi_value));
+        self.field_opt(
+            "radiotap.mcs.format",
+            mcs.format
+                .map(|format| (format == HTFormat::Greenfield) as u8),
+        );
+        self.field_opt("radiotap.mcs.fec", mcs.fec.map(fec_value));
+        self.field_opt("radiotap.mcs.stbc", mcs.stbc);
+        self.field_opt("radiotap.mcs.ness", mcs.ness);
+    }
+
+    fn vht(&mut self, vht: &VHT) {
+        self.field_opt("radiotap.vht.stbc", vht.stbc);
+        self.field_opt("radiotap.vht.txop_ps_not_allowed", vht.txop_ps);
+        self.field_opt("radiotap.vht.gi", vht.gi.map(gi_value));
+        self.field_opt("radiotap.vht.sgi_nsym_da", vht.sgi_nsym_da);
+        self.field_opt("radiotap.vht.ldpc_extra_ofdm_symbol", vht.ldpc_extra);
+        self.field_opt("radiotap.vht.beamformed", vht.beamformed);
+        self.field_opt("radiotap.vht.bw", vht.bw.and_then(|bw| bw.value().ok()));
+        self.field_opt("radiotap.vht.gid", vht.group_id);
+        self.field_opt("radiotap.vht.partial_aid", vht.partial_aid);
+        for (i, user) in vht.users.iter().enumerate() {
+            if let Some(user) = user {
+                self.user("radiotap.vht.mcs", i, user.index);
+                self.user("radiotap.vht.nss", i, user.nss);
+                self.user("radiotap.vht.coding", i, fec_value(user.fec));
+                if let Some(datarate) = user.datarate {
+                    self.user("radiotap.vht.datarate", i, Float(datarate));
+                }
+            }
+        }
+    }
+
+    /// Adds a member of a VHT user, whose name is suffixed with the index of
+    /// the user.
+    fn user<T: Display>(&mut self, name: &str, index: usize, value: T) {
+        let mut indexed = String::new();
+        let _ = write!(indexed, "{}.{}", name, index);
+        self.field(&indexed, value);
+    }
+}
+
+/// A floating point value, written as `null` if it is not finite.
+struct Float(f32);
+
+impl Display for Float {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0.is_finite() {
+            write!(f, "{}", self.0)
+        } else {
+            f.write_str("null")
+        }
+    }
+}
+
+fn gi_value(gi: GuardInterval) -> u8 {
+    (gi == GuardInterval::Short) as u8
+}
+
+fn fec_value(fec: FEC) -> u8 {
+    (fec == FEC::LDPC) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RadiotapBuilder;
+
+    #[test]
+    fn vht() {
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        let json = radiotap.to_json();
+
+        assert!(json.starts_with(r#"{"radiotap.version":0,"radiotap.length":56,"#));
+        assert!(json.contains(r#""radiotap.mactime":2593857465,"#));
+        assert!(json.contains(r#""radiotap.channel.freq":5500,"#));
+        assert!(json.contains(r#""radiotap.channel.flags.5ghz":true,"#));
+        assert!(json.contains(r#""radiotap.dbm_antsignal":-43,"#));
+        assert!(json.contains(r#""radiotap.vht.bw":4,"#));
+        assert!(json.ends_with(r#""radiotap.vht.mcs.0":7,"radiotap.vht.nss.0":3,"radiotap.vht.coding.0":1,"radiotap.vht.datarate.0":877.5}"#));
+    }
+
+    #[test]
+    fn tx_power() {
+        let radiotap = RadiotapBuilder::new()
+            .tx_power(TxPower { value: 10 })
+            .done();
+        assert!(radiotap
+            .to_json()
+            .ends_with(r#""radiotap.dbm_tx_power":10}"#));
+    }
+}
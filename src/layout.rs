@@ -0,0 +1,261 @@
+//! The byte layout of a Radiotap capture, useful for debugging drivers that
+//! emit bloated or misaligned captures.
+
+use alloc::{format, string::String, vec::Vec};
+use core::fmt;
+
+use crate::{
+    field::{tlv::Tlv, Kind, Value},
+    Radiotap, RadiotapIterator, Result,
+};
+
+/// Describes how the length of a Radiotap capture decomposes into present
+/// words, fields, and padding.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Layout {
+    /// The number of present words.
+    pub present_words: usize,
+    /// The layout of each field, in the order they appear in the capture.
+    pub fields: Vec<FieldLayout>,
+    /// The total number of padding bytes inserted to align fields.
+    pub padding: usize,
+    /// The number of bytes after the last field that are still within the
+    /// length specified in the header.
+    pub trailing: usize,
+}
+
+/// The position of a single field within a Radiotap capture.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct FieldLayout {
+    /// The type of field.
+    pub kind: Kind,
+    /// The offset of the field from the start of the capture.
+    pub offset: usize,
+    /// The size of the field, for vendor namespaces this includes the
+    /// namespace header.
+    pub size: usize,
+    /// The number of padding bytes before the field.
+    pub padding: usize,
+}
+
+/// What a range of bytes of a capture holds, see
+/// [Annotation](struct.Annotation.html).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Part {
+    /// The version, padding and length of the header.
+    Header,
+    /// A present word, by index.
+    Present(usize),
+    /// A field, including the header of vendor namespaces and TLVs.
+    Field(Kind),
+    /// Padding inserted to align the next field.
+    Padding,
+    /// Bytes within the length of the header after the last field that could
+    /// be located.
+    Trailing,
+}
+
+/// A range of bytes of a capture and what it holds.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Annotation {
+    /// The offset of the range from the start of the capture.
+    pub offset: usize,
+    /// The number of bytes in the range.
+    pub size: usize,
+    /// What the range holds.
+    pub part: Part,
+    /// A description of the range, with the decoded value of fields.
+    pub description: String,
+}
+
+/// The annotated bytes of a capture, returned by
+/// [Radiotap::explain](../struct.Radiotap.html#method.explain).
+///
+/// Displaying it renders a hexdump with the annotation of each range next to
+/// its bytes.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Explanation<'a> {
+    data: &'a [u8],
+    annotations: Vec<Annotation>,
+}
+
+impl<'a> Explanation<'a> {
+    /// Returns the annotations, in the order of their ranges, which cover the
+    /// whole capture without overlapping.
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.annotations
+    }
+
+    /// Returns the annotation of the byte at the given offset.
+    pub fn at(&self, offset: usize) -> Option<&Annotation> {
+        self.annotations
+            .iter()
+            .find(|a| a.offset <= offset && offset < a.offset + a.size)
+    }
+}
+
+impl fmt::Display for Explanation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for annotation in &self.annotations {
+            let data = &self.data[annotation.offset..annotation.offset + annotation.size];
+            for (i, row) in data.chunks(ROW).enumerate() {
+                write!(f, "{:04x} ", annotation.offset + i * ROW)?;
+                for byte in row {
+                    write!(f, " {:02x}", byte)?;
+                }
+                if i == 0 {
+                    let width = 3 * (ROW - row.len());
+                    write!(
+                        f,
+                        "{:width$}  {}",
+                        "",
+                        annotation.description,
+                        width = width
+                    )?;
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The number of bytes in each row of the hexdump.
+const ROW: usize = 8;
+
+impl Radiotap {
+    /// Returns the annotated bytes of the Radiotap capture at the start of
+    /// the input, describing the header, each present word, field and
+    /// padding.
+    ///
+    /// This helps reverse engineering driver quirks or finding where the
+    /// output of a builder differs from a capture.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::{field::Kind, layout::Part, Radiotap};
+    ///
+    /// let capture = [0, 0, 12, 0, 0x14, 0, 0, 0, 12, 0, 1, 2];
+    /// let explanation = Radiotap::explain(&capture).unwrap();
+    /// assert_eq!(explanation.at(9).unwrap().part, Part::Padding);
+    /// assert_eq!(explanation.at(10).unwrap().part, Part::Field(Kind::FHSS));
+    /// assert_eq!(
+    ///     explanation.to_string(),
+    ///     "0000  00 00 0c 00              version 0, length 12\n\
+    ///      0004  14 00 00 00              present word 0: 0x00000014\n\
+    ///      0008  0c                       Rate(Rate { raw: 12 })\n\
+    ///      0009  00                       padding\n\
+    ///      000a  01 02                    FHSS(FHSS { hopset: 1, pattern: 2 })\n"
+    /// );
+    /// ```
+    pub fn explain(input: &[u8]) -> Result<Explanation<'_>> {
+        let iterator = RadiotapIterator::from_bytes(input)?;
+        let header = &iterator.header;
+        let layout = iterator.layout()?;
+        let data = &input[..header.length];
+
+        let mut annotations = Vec::new();
+        annotations.try_reserve(2 + header.present.words().len() + 2 * layout.fields.len())?;
+        annotations.push(Annotation {
+            offset: 0,
+            size: 4,
+            part: Part::Header,
+            description: format!("version {}, length {}", header.version, header.length),
+        });
+        for (i, word) in header.present.words().iter().enumerate() {
+            annotations.push(Annotation {
+                offset: 4 + 4 * i,
+                size: 4,
+                part: Part::Present(i),
+                description: format!("present word {}: {:#010x}", i, word),
+            });
+        }
+
+        for field in &layout.fields {
+            if field.padding > 0 {
+                annotations.push(Annotation {
+                    offset: field.offset - field.padding,
+                    size: field.padding,
+                    part: Part::Padding,
+                    description: String::from("padding"),
+                });
+            }
+            let data = &data[field.offset..field.offset + field.size];
+            annotations.push(Annotation {
+                offset: field.offset,
+                size: field.size,
+                part: Part::Field(field.kind),
+                description: describe(field.kind, data),
+            });
+        }
+
+        if layout.trailing > 0 {
+            annotations.push(Annotation {
+                offset: header.length - layout.trailing,
+                size: layout.trailing,
+                part: Part::Trailing,
+                description: String::from("trailing"),
+            });
+        }
+
+        Ok(Explanation { data, annotations })
+    }
+}
+
+/// Returns the description of a field, its decoded value if possible.
+fn describe(kind: Kind, data: &[u8]) -> String {
+    match kind {
+        Kind::TLV(_) => {
+            let (kind, data) = (u16::from_le_bytes([data[0], data[1]]), &data[4..]);
+            match Tlv::new(kind, data) {
+                Ok(Tlv::Raw { kind, data }) => format!("TLV {}: {} bytes", kind, data.len()),
+                Ok(tlv) => format!("TLV {}: {:?}", kind, tlv),
+                Err(e) => format!("TLV {}: {}", kind, e),
+            }
+        }
+        Kind::VendorNamespace(Some(vns)) => format!(
+            "vendor namespace {} {}: {} bytes",
+            vns.oui, vns.sub_namespace, vns.skip_length
+        ),
+        kind => match Value::from_bytes(kind, data) {
+            Ok(value) => format!("{:?}", value),
+            Err(e) => format!("{:?}: {}", kind, e),
+        },
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explain() {
+        let frame = [
+            0, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+        let explanation = Radiotap::explain(&frame).unwrap();
+        let annotations = explanation.annotations();
+
+        // The annotations cover the whole capture in order
+        let mut end = 0;
+        for annotation in annotations {
+            assert_eq!(annotation.offset, end);
+            end += annotation.size;
+        }
+        assert_eq!(end, frame.len());
+
+        assert_eq!(annotations[1].part, Part::Present(0));
+        assert_eq!(annotations[4].part, Part::Present(3));
+        assert!(matches!(
+            explanation.at(30).unwrap().part,
+            Part::Field(Kind::VendorNamespace(Some(_)))
+        ));
+        assert!(explanation.at(39).is_none());
+        assert!(explanation.to_string().ends_with(
+            "001e  ff ff ff ff 02 00 de ad  vendor namespace ff:ff:ff 255: 2 bytes\n\
+             0026  04                       Rate(Rate { raw: 4 })\n"
+        ));
+    }
+}
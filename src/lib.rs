@@ -40,19 +40,157 @@
 //!     }
 //! }
 //! ```
+//!
+//! # `no_std`
+//!
+//! Disabling the default `std` feature builds the crate for `no_std` targets
+//! with an allocator, enabled with the `alloc` feature. Captures are then
+//! written to the minimal `Write` trait of the [io](io/index.html) module
+//! instead of `std::io::Write`.
+//!
+//! Without the `alloc` feature no allocator is needed at all. The
+//! [Radiotap](struct.Radiotap.html) struct and the modules built on it are not
+//! available, but captures are still decoded with the
+//! [RadiotapIterator](struct.RadiotapIterator.html), whose present flags are a
+//! fixed-size array and which returns vendor namespaces as slices, and the
+//! [RadiotapView](view/struct.RadiotapView.html). Receive chains are iterated
+//! with [chains](struct.RadiotapIterator.html#method.chains).
+//!
+//! The `defmt` feature implements `defmt::Format` for the
+//! [Radiotap](struct.Radiotap.html) struct, the [Header](field/struct.Header.html)
+//! and all field types, so firmware can log parsed captures with `defmt`.
+//!
+//! The `serde` feature implements `Serialize` and `Deserialize` for the same
+//! types, so parsed captures can be stored in any serde format. The
+//! [PresentFlags](field/struct.PresentFlags.html) are a list of the raw
+//! present words.
+//!
+//! The `arbitrary` feature implements `arbitrary::Arbitrary` for them too, so
+//! fuzz targets can generate structured captures to write and parse back.
+//!
+//! The `pcap` feature adds a reader of pcap and pcapng capture files in the
+//! [pcap](pcap/index.html) module, and the `live-capture` feature captures
+//! frames from a monitor mode interface with libpcap in the
+//! [live](live/index.html) module.
+//!
+//! The `python` feature adds Python bindings with PyO3 in the
+//! [python](python/index.html) module.
+//!
+//! The `wasm` feature adds JavaScript bindings with wasm-bindgen in the
+//! [wasm](wasm/index.html) module, for builds targeting
+//! `wasm32-unknown-unknown`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "alloc")]
+pub mod airtime;
+#[cfg(feature = "rayon")]
+mod batch;
+mod bits;
+#[cfg(feature = "bytes")]
+mod buf;
+#[cfg(feature = "alloc")]
+mod builder;
+#[cfg(feature = "alloc")]
+pub mod cache;
+#[cfg(feature = "config")]
+pub mod config;
+#[cfg(feature = "alloc")]
+pub mod decoder;
+#[cfg(feature = "alloc")]
+mod display;
+#[cfg(feature = "fallible-iterator")]
+pub mod fallible;
+pub mod fcs;
 pub mod field;
+#[cfg(feature = "alloc")]
+pub mod gps;
+pub mod io;
+#[cfg(feature = "alloc")]
+mod json;
+#[cfg(feature = "alloc")]
+pub mod layout;
+#[cfg(feature = "live-capture")]
+pub mod live;
+#[cfg(feature = "alloc")]
+mod parser;
+pub mod payload;
+#[cfg(feature = "pcap")]
+pub mod pcap;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "zerocopy")]
+pub mod raw;
+#[cfg(feature = "alloc")]
+pub mod summary;
+#[cfg(feature = "alloc")]
+mod template;
+#[cfg(feature = "tshark")]
+pub mod tshark;
+#[cfg(feature = "alloc")]
+pub mod txstatus;
+pub mod util;
+#[cfg(feature = "alloc")]
+pub mod validate;
+#[cfg(feature = "alloc")]
+pub mod vendor;
+pub mod view;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{collections::TryReserveError, vec, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::{array, convert::TryFrom, iter, time::Duration};
+use core::{iter::FusedIterator, mem, result};
+#[cfg(any(feature = "config", feature = "tshark"))]
+use std::string::String;
 
-use std::{io::Cursor, result};
+#[cfg(feature = "alloc")]
+use byteorder::LE;
 
-use crate::field::*;
+#[cfg(feature = "rayon")]
+pub use crate::batch::parse_batch;
+#[cfg(feature = "alloc")]
+pub use crate::{
+    builder::{BuildError, RadiotapBuilder},
+    parser::{Duplicates, Limits, RadiotapParser, RadiotapParserBuilder},
+    template::Template,
+};
+
+use crate::field::{
+    ext::AntennaChain,
+    tlv::{Tlv, Tlvs},
+    *,
+};
+#[cfg(feature = "alloc")]
+use crate::{
+    bits::BitOps,
+    io::{Write, WriteBytesExt},
+    layout::{FieldLayout, Layout},
+    payload::{FrameParser, FrameResult, Payload},
+    summary::Summary,
+    util::{
+        channel::Band,
+        power::{Db, Dbm},
+    },
+    validate::Violation,
+    vendor::Vendor,
+};
 
 /// All errors returned and used by the radiotap module.
+///
+/// Some variants only exist with their feature enabled, so matches on this
+/// enum need a wildcard arm.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum Error {
     /// The internal cursor on the data returned an IO error.
     #[error(transparent)]
-    ParseError(#[from] std::io::Error),
+    ParseError(#[from] io::Error),
 
     /// The given data is not a complete Radiotap capture.
     #[error("incomplete radiotap capture")]
@@ -73,21 +211,190 @@ pub enum Error {
     /// Unsupported Radiotap field.
     #[error("unsupported radiotap field")]
     UnsupportedField,
+
+    /// A field of unknown size is present, so none of the fields after it can
+    /// be located. The fields before it are still available.
+    #[error("unknown radiotap field {bit} blocks parsing the fields after it")]
+    UnknownFieldBlocksParsing {
+        /// The present bit of the field, counting from the start of its
+        /// radiotap namespace.
+        bit: u8,
+    },
+
+    /// Memory could not be allocated while parsing.
+    #[cfg(feature = "alloc")]
+    #[error(transparent)]
+    AllocationError(#[from] TryReserveError),
+
+    /// The capture has a link type other than Radiotap, see
+    /// [is_radiotap_linktype](fn.is_radiotap_linktype.html).
+    #[error("unsupported link type {}, expected IEEE802_11_RADIOTAP (127)", LinkTypeName(*.0))]
+    UnsupportedLinkType(u32),
+
+    /// The header specification could not be loaded.
+    #[cfg(feature = "config")]
+    #[error("invalid header specification: {0}")]
+    InvalidConfig(String),
+
+    /// The tshark JSON to import could not be loaded, see
+    /// [from_tshark_json](struct.Radiotap.html#method.from_tshark_json).
+    #[cfg(feature = "tshark")]
+    #[error("invalid tshark JSON: {0}")]
+    InvalidTsharkJson(String),
+
+    /// The capture exceeds one of the [Limits](struct.Limits.html) of the
+    /// parser, named by the value.
+    #[error("radiotap capture exceeds the {0} limit")]
+    LimitExceeded(&'static str),
+
+    /// The live capture returned an error.
+    #[cfg(feature = "live-capture")]
+    #[error(transparent)]
+    CaptureError(#[from] ::pcap::Error),
+
+    /// The capture does not follow the specification, returned by
+    /// [parse_strict](struct.Radiotap.html#method.parse_strict).
+    #[cfg(feature = "alloc")]
+    #[error("radiotap capture violates the specification: {}", validate::ViolationList(.0))]
+    Violations(Vec<Violation>),
+}
+
+/// How a consumer of a stream of captures should react to an
+/// [Error](enum.Error.html).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ErrorCategory {
+    /// The capture is malformed, skip it and continue with the next one.
+    Skip,
+    /// The capture boundaries can no longer be trusted, resynchronize the
+    /// stream before continuing.
+    Resync,
+    /// The error is not caused by the capture itself, stop processing.
+    Abort,
+}
+
+impl Error {
+    /// Returns how a consumer of a stream of captures should react to this
+    /// error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Error::ParseError(e) if e.kind() == io::ErrorKind::UnexpectedEof => ErrorCategory::Skip,
+            Error::ParseError(_) => ErrorCategory::Abort,
+            Error::IncompleteError
+            | Error::InvalidFormat
+            | Error::UnsupportedField
+            | Error::UnknownFieldBlocksParsing { .. }
+            | Error::LimitExceeded(_) => ErrorCategory::Skip,
+            #[cfg(feature = "alloc")]
+            Error::Violations(_) => ErrorCategory::Skip,
+            Error::InvalidLength | Error::UnsupportedVersion => ErrorCategory::Resync,
+            Error::UnsupportedLinkType(_) => ErrorCategory::Abort,
+            #[cfg(feature = "live-capture")]
+            Error::CaptureError(_) => ErrorCategory::Abort,
+            #[cfg(feature = "alloc")]
+            Error::AllocationError(_) => ErrorCategory::Abort,
+            #[cfg(feature = "config")]
+            Error::InvalidConfig(_) => ErrorCategory::Abort,
+            #[cfg(feature = "tshark")]
+            Error::InvalidTsharkJson(_) => ErrorCategory::Abort,
+        }
+    }
+
+    /// Returns whether processing can continue after this error.
+    pub fn is_recoverable(&self) -> bool {
+        self.category() != ErrorCategory::Abort
+    }
+}
+
+/// A field that could not be parsed by
+/// [parse_lenient](struct.Radiotap.html#method.parse_lenient).
+#[derive(Debug)]
+pub struct FieldError {
+    /// The kind of the field, or `None` if the field could not be located.
+    pub kind: Option<Kind>,
+    /// The reason the field could not be parsed.
+    pub error: Error,
 }
 
 type Result<T> = result::Result<T, Error>;
 
-/// A trait to align an offset to particular word size, usually 1, 2, 4, or 8.
-trait Align {
-    /// Aligns the offset to `align` size.
-    fn align(&mut self, align: u64);
+/// The link type of plain 802.11 frames without a Radiotap header, also
+/// `DLT_IEEE802_11`.
+pub const LINKTYPE_IEEE802_11: u32 = 105;
+
+/// The link type of 802.11 frames with a Prism header.
+pub const LINKTYPE_IEEE802_11_PRISM: u32 = 119;
+
+/// The link type of 802.11 frames with a Radiotap header, also
+/// `DLT_IEEE802_11_RADIO`.
+pub const LINKTYPE_IEEE802_11_RADIOTAP: u32 = 127;
+
+/// The link type of 802.11 frames with an AVS header.
+pub const LINKTYPE_IEEE802_11_AVS: u32 = 163;
+
+/// Returns whether the frames of a capture with the given link type, or DLT
+/// value, start with a Radiotap header.
+///
+/// # Examples
+///
+/// ```
+/// use radiotap::{is_radiotap_linktype, LINKTYPE_IEEE802_11};
+///
+/// assert!(is_radiotap_linktype(127));
+/// assert!(!is_radiotap_linktype(LINKTYPE_IEEE802_11));
+/// ```
+pub const fn is_radiotap_linktype(linktype: u32) -> bool {
+    linktype == LINKTYPE_IEEE802_11_RADIOTAP
+}
+
+/// Formats a link type with its name, if it is an 802.11 link type.
+struct LinkTypeName(u32);
+
+impl core::fmt::Display for LinkTypeName {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let name = match self.0 {
+            LINKTYPE_IEEE802_11 => "IEEE802_11, plain 802.11 frames without a radiotap header",
+            LINKTYPE_IEEE802_11_PRISM => "IEEE802_11_PRISM",
+            LINKTYPE_IEEE802_11_AVS => "IEEE802_11_AVS",
+            _ => return write!(f, "{}", self.0),
+        };
+        write!(f, "{} ({})", self.0, name)
+    }
+}
+
+/// Aligns an offset to `align` size.
+fn align(offset: usize, align: u64) -> usize {
+    let align = align as usize;
+    (offset + align - 1) & !(align - 1)
+}
+
+/// A 64-bit FNV-1a hasher, used for fingerprints because unlike the standard
+/// library hashers its output is specified.
+#[cfg(feature = "alloc")]
+struct Fnv64(u64);
+
+#[cfg(feature = "alloc")]
+impl Fnv64 {
+    fn new() -> Fnv64 {
+        Fnv64(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn hash(state: u64, bytes: &[u8]) -> u64 {
+        bytes.iter().fold(state, |state, &byte| {
+            (state ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+    }
 }
 
-impl<T> Align for Cursor<T> {
-    /// Aligns the Cursor position to `align` size.
-    fn align(&mut self, align: u64) {
-        let p = self.position();
-        self.set_position((p + align - 1) & !(align - 1));
+#[cfg(feature = "alloc")]
+impl Write for Fnv64 {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 = Fnv64::hash(self.0, buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
     }
 }
 
@@ -105,18 +412,294 @@ impl<'a> RadiotapIterator<'a> {
     }
 
     pub fn parse(input: &'a [u8]) -> Result<(RadiotapIterator<'a>, &'a [u8])> {
-        let header: Header = from_bytes(input)?;
+        RadiotapIterator::parse_header(input, false)
+    }
+
+    /// Returns the unparsed capture and remaining data like
+    /// [parse](#method.parse), but accepts any header version, which is
+    /// recorded in the header.
+    pub fn parse_any_version(input: &'a [u8]) -> Result<(RadiotapIterator<'a>, &'a [u8])> {
+        RadiotapIterator::parse_header(input, true)
+    }
+
+    /// Returns the unparsed capture and remaining data like
+    /// [parse](#method.parse), after checking that the link type of the
+    /// capture the input came from is Radiotap, see
+    /// [is_radiotap_linktype](fn.is_radiotap_linktype.html).
+    pub fn parse_with_linktype(
+        linktype: u32,
+        input: &'a [u8],
+    ) -> Result<(RadiotapIterator<'a>, &'a [u8])> {
+        if !is_radiotap_linktype(linktype) {
+            return Err(Error::UnsupportedLinkType(linktype));
+        }
+        RadiotapIterator::parse(input)
+    }
+
+    pub(crate) fn parse_header(
+        input: &'a [u8],
+        any_version: bool,
+    ) -> Result<(RadiotapIterator<'a>, &'a [u8])> {
+        let header = Header::parse(input, any_version)?;
         let (data, rest) = input.split_at(header.length);
         Ok((RadiotapIterator { header, data }, rest))
     }
+
+    /// Returns an iterator over the decoded fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::{field::Value, RadiotapIterator, TypedField};
+    ///
+    /// let capture = [0, 0, 10, 0, 0x24, 0, 0, 0, 12, 0xd6];
+    /// let iterator = RadiotapIterator::from_bytes(&capture).unwrap();
+    /// for field in iterator.typed() {
+    ///     match field.unwrap() {
+    ///         TypedField::Value(Value::Rate(rate)) => assert_eq!(rate.as_mbps(), 6.0),
+    ///         TypedField::Value(Value::AntennaSignal(signal)) => assert_eq!(signal.value, -42),
+    ///         field => panic!("unexpected field: {:?}", field),
+    ///     }
+    /// }
+    /// ```
+    pub fn typed(&'a self) -> TypedIterator<'a> {
+        TypedIterator {
+            inner: self.into_iter(),
+        }
+    }
+
+    /// Returns an iterator over the receive chains reported in repeated
+    /// radiotap namespaces, like [Radiotap::chains](struct.Radiotap.html#method.chains)
+    /// but without allocating.
+    pub fn chains(&'a self) -> Chains<'a> {
+        Chains {
+            inner: self.into_iter(),
+            chain: AntennaChain::default(),
+            first: true,
+            done: false,
+        }
+    }
+
+    /// Returns the data of the first field of the given type in the first
+    /// radiotap namespace, without decoding any field. A present bit and a
+    /// TLV record of the same type both match.
+    ///
+    /// Returns `None` if the field is not present, or is after a field of
+    /// unknown size.
+    pub fn find_field(&'a self, kind: Kind) -> Result<Option<&'a [u8]>> {
+        for result in self {
+            let (k, data) = match result {
+                Ok(field) => field,
+                Err(Error::UnknownFieldBlocksParsing { .. }) => break,
+                Err(e) => return Err(e),
+            };
+            match k {
+                Kind::RadiotapNamespace => break,
+                Kind::TLV(Some(bit)) if bit < 28 && Kind::new(bit as u8)? == kind => {
+                    return Ok(Some(data))
+                }
+                k if k == kind => return Ok(Some(data)),
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns how the length of the capture decomposes into present words,
+    /// fields, and padding.
+    #[cfg(feature = "alloc")]
+    pub fn layout(&self) -> Result<Layout> {
+        let mut fields = Vec::new();
+        let mut padding = 0;
+        let mut end = self.header.size;
+
+        for result in self {
+            let (kind, data) = match result {
+                Ok(field) => field,
+                Err(Error::UnknownFieldBlocksParsing { .. }) => break,
+                Err(e) => return Err(e),
+            };
+
+            // Namespace resets have no data
+            if kind == Kind::RadiotapNamespace {
+                continue;
+            }
+
+            let mut offset = data.as_ptr() as usize - self.data.as_ptr() as usize;
+            let mut size = data.len();
+            // Vendor namespace and TLV data is returned without their header
+            if let Kind::VendorNamespace(_) | Kind::TLV(_) = kind {
+                offset -= kind.size();
+                size += kind.size();
+            }
+
+            fields.try_reserve(1)?;
+            fields.push(FieldLayout {
+                kind,
+                offset,
+                size,
+                padding: offset - end,
+            });
+            padding += offset - end;
+            end = offset + size;
+        }
+
+        Ok(Layout {
+            present_words: (self.header.size - 4) / 4,
+            fields,
+            padding,
+            trailing: self.header.length - end,
+        })
+    }
+}
+
+/// A decoded Radiotap field, yielded by a
+/// [TypedIterator](struct.TypedIterator.html).
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum TypedField<'a> {
+    /// A field defined by a present bit, also when written as a TLV record.
+    Value(Value),
+    /// The 802.11be U-SIG field.
+    USig(USig),
+    /// The 802.11be EHT field.
+    EHT(EHT),
+    /// A vendor namespace and its data.
+    VendorNamespace(VendorNamespace, &'a [u8]),
+    /// The start of a new radiotap namespace.
+    RadiotapNamespace,
+    /// A field this crate does not know how to decode, and its data.
+    Unknown(Kind, &'a [u8]),
+}
+
+impl<'a> TypedField<'a> {
+    /// Decodes the data of a field of the given kind, as yielded by a
+    /// [RadiotapIterator](struct.RadiotapIterator.html).
+    pub fn new(kind: Kind, data: &'a [u8]) -> Result<TypedField<'a>> {
+        Ok(match kind {
+            Kind::RadiotapNamespace => TypedField::RadiotapNamespace,
+            Kind::VendorNamespace(Some(vns)) => TypedField::VendorNamespace(vns, data),
+            Kind::TLV(Some(tlv)) => match Tlv::new(tlv, data)? {
+                Tlv::Field(value) => TypedField::Value(value),
+                Tlv::USig(u_sig) => TypedField::USig(u_sig),
+                Tlv::EHT(eht) => TypedField::EHT(eht),
+                _ => TypedField::Unknown(kind, data),
+            },
+            kind => match Value::from_bytes(kind, data) {
+                Ok(value) => TypedField::Value(value),
+                Err(Error::UnsupportedField) => TypedField::Unknown(kind, data),
+                Err(e) => return Err(e),
+            },
+        })
+    }
+}
+
+/// An iterator over the decoded fields of a
+/// [RadiotapIterator](struct.RadiotapIterator.html), see
+/// [typed](struct.RadiotapIterator.html#method.typed).
+#[derive(Debug, Clone)]
+pub struct TypedIterator<'a> {
+    inner: RadiotapIteratorIntoIter<'a>,
+}
+
+impl<'a> Iterator for TypedIterator<'a> {
+    type Item = Result<TypedField<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|field| field.and_then(|(kind, data)| TypedField::new(kind, data)))
+    }
+}
+
+/// An iterator over the receive chains of a
+/// [RadiotapIterator](struct.RadiotapIterator.html), see
+/// [chains](struct.RadiotapIterator.html#method.chains).
+#[derive(Debug, Clone)]
+pub struct Chains<'a> {
+    inner: RadiotapIteratorIntoIter<'a>,
+    /// The chain of the current radiotap namespace so far.
+    chain: AntennaChain,
+    first: bool,
+    done: bool,
+}
+
+impl Chains<'_> {
+    /// Returns the chain of the current radiotap namespace, if it is one.
+    fn take(&mut self) -> Option<AntennaChain> {
+        let chain = mem::take(&mut self.chain);
+        let first = mem::replace(&mut self.first, false);
+        // The first namespace holds the combined signal unless it names an
+        // antenna
+        let is_chain = if first {
+            chain.antenna.is_some()
+        } else {
+            chain != AntennaChain::default()
+        };
+        Some(chain).filter(|_| is_chain)
+    }
+
+    /// Adds a field to the current chain, returning the previous chain when
+    /// a new radiotap namespace starts.
+    fn field(&mut self, kind: Kind, data: &[u8]) -> Result<Option<AntennaChain>> {
+        let kind = match kind {
+            Kind::TLV(Some(kind)) if kind < 28 => Kind::new(kind as u8)?,
+            kind => kind,
+        };
+        match kind {
+            Kind::RadiotapNamespace => return Ok(self.take()),
+            Kind::Antenna => self.chain.antenna = Some(from_bytes::<Antenna>(data)?.value),
+            Kind::AntennaSignal => {
+                self.chain.signal = Some(from_bytes::<AntennaSignal>(data)?.value)
+            }
+            Kind::AntennaNoise => self.chain.noise = Some(from_bytes::<AntennaNoise>(data)?.value),
+            _ => {}
+        }
+        Ok(None)
+    }
+}
+
+impl Iterator for Chains<'_> {
+    type Item = Result<AntennaChain>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let result = match self.inner.next() {
+                Some(Ok((kind, data))) => self.field(kind, data),
+                Some(Err(Error::UnknownFieldBlocksParsing { .. })) | None => {
+                    self.done = true;
+                    Ok(self.take())
+                }
+                Some(Err(e)) => Err(e),
+            };
+            match result {
+                Ok(Some(chain)) => return Some(Ok(chain)),
+                Ok(None) => {}
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        None
+    }
 }
 
+impl FusedIterator for Chains<'_> {}
+
 /// An iterator over Radiotap fields.
+///
+/// The number of fields is not known up front, because the TLV records are
+/// only counted while iterating and some errors end the iteration early, so
+/// only bounds are given by `size_hint`.
 #[doc(hidden)]
 #[derive(Debug, Clone)]
 pub struct RadiotapIteratorIntoIter<'a> {
-    present: Vec<Kind>,
-    cursor: Cursor<&'a [u8]>,
+    present: PresentKinds,
+    data: &'a [u8],
+    /// The offset of the next field, before alignment.
+    position: usize,
+    tlvs: Option<Tlvs<'a>>,
 }
 
 impl<'a> IntoIterator for &'a RadiotapIterator<'a> {
@@ -124,10 +707,12 @@ impl<'a> IntoIterator for &'a RadiotapIterator<'a> {
     type Item = Result<(Kind, &'a [u8])>;
 
     fn into_iter(self) -> Self::IntoIter {
-        let present = self.header.present.iter().rev().cloned().collect();
-        let mut cursor = Cursor::new(self.data);
-        cursor.set_position(self.header.size as u64);
-        RadiotapIteratorIntoIter { present, cursor }
+        RadiotapIteratorIntoIter {
+            present: self.header.present.iter(),
+            data: self.data,
+            position: self.header.size,
+            tlvs: None,
+        }
     }
 }
 
@@ -136,10 +721,19 @@ impl<'a> IntoIterator for RadiotapIterator<'a> {
     type Item = Result<(Kind, &'a [u8])>;
 
     fn into_iter(self) -> Self::IntoIter {
-        let present = self.header.present.iter().rev().cloned().collect();
-        let mut cursor = Cursor::new(self.data);
-        cursor.set_position(self.header.size as u64);
-        RadiotapIteratorIntoIter { present, cursor }
+        RadiotapIteratorIntoIter {
+            present: self.header.present.iter(),
+            data: self.data,
+            position: self.header.size,
+            tlvs: None,
+        }
+    }
+}
+
+impl<'a> RadiotapIteratorIntoIter<'a> {
+    /// Stops the iteration over the present fields.
+    fn stop(&mut self) {
+        self.present = PresentFlags::default().iter();
     }
 }
 
@@ -147,22 +741,43 @@ impl<'a> Iterator for RadiotapIteratorIntoIter<'a> {
     type Item = Result<(Kind, &'a [u8])>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.present.pop() {
+        if let Some(tlvs) = &mut self.tlvs {
+            return tlvs
+                .next()
+                .map(|tlv| tlv.map(|(kind, data)| (Kind::TLV(Some(kind)), data)));
+        }
+
+        match self.present.next() {
+            // The TLV records follow all the other fields, up to the end of the
+            // capture
+            Some(Kind::TLV(None)) => {
+                self.stop();
+                let start = align(self.position, Kind::TLV(None).align()).min(self.data.len());
+                self.tlvs = Some(Tlvs::new(&self.data[start..]));
+                self.next()
+            }
             Some(mut kind) => {
-                // Align the cursor to the current field's needed alignment.
-                self.cursor.align(kind.align());
+                // We don't know where this field ends, so no later field can be
+                // located either
+                if !kind.is_sized() {
+                    self.stop();
+                    return Some(Err(Error::UnknownFieldBlocksParsing { bit: kind.bit() }));
+                }
 
-                let mut start = self.cursor.position() as usize;
+                // Align the position to the current field's needed alignment.
+                self.position = align(self.position, kind.align());
+
+                let mut start = self.position;
                 let mut end = start + kind.size();
 
                 // The header lied about how long the body was
-                if end > self.cursor.get_ref().len() {
+                if end > self.data.len() {
                     Some(Err(Error::IncompleteError))
                 } else {
                     // Switching to a vendor namespace, and we don't know how to handle
                     // so we just return the entire vendor namespace section
                     if kind == Kind::VendorNamespace(None) {
-                        match VendorNamespace::from_bytes(&self.cursor.get_ref()[start..end]) {
+                        match VendorNamespace::from_bytes(&self.data[start..end]) {
                             Ok(vns) => {
                                 start += kind.size();
                                 end += vns.skip_length as usize;
@@ -170,31 +785,100 @@ impl<'a> Iterator for RadiotapIteratorIntoIter<'a> {
                             }
                             Err(e) => return Some(Err(e)),
                         }
+                        // The vendor data extends past the end of the capture
+                        if end > self.data.len() {
+                            self.stop();
+                            return Some(Err(Error::IncompleteError));
+                        }
                     }
-                    let data = &self.cursor.get_ref()[start..end];
-                    self.cursor.set_position(end as u64);
+                    let data = &self.data[start..end];
+                    self.position = end;
                     Some(Ok((kind, data)))
                 }
             }
             None => None,
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if let Some(tlvs) = &self.tlvs {
+            return tlvs.size_hint();
+        }
+        // Every field yields an item, except for the TLV records which yield
+        // as many items as fit in the remaining data
+        let remaining = self.present.len();
+        let mut upcoming = self.present.clone();
+        let (lower, tlvs) = match upcoming.next() {
+            None => return (0, Some(0)),
+            Some(Kind::TLV(None)) => (0, true),
+            Some(_) => (1, upcoming.any(|kind| kind == Kind::TLV(None))),
+        };
+        let mut upper = remaining;
+        if tlvs {
+            upper += self.data.len().saturating_sub(self.position).div_ceil(4);
+        }
+        (lower, Some(upper))
+    }
 }
 
+impl FusedIterator for RadiotapIteratorIntoIter<'_> {}
+
 impl Default for Header {
     fn default() -> Header {
         Header {
             version: 0,
             length: 8,
-            present: Vec::new(),
+            present: PresentFlags::default(),
             size: 8,
         }
     }
 }
 
+/// Returns the present words of each vendor namespace in the header, without
+/// the bits switching between words and namespaces.
+#[cfg(feature = "alloc")]
+fn vendor_present_words(present: &PresentFlags) -> Vec<Vec<u32>> {
+    let mut namespaces: Vec<Vec<u32>> = Vec::new();
+    let mut vendor_namespace = false;
+    for &word in present.words() {
+        if let (true, Some(namespace)) = (vendor_namespace, namespaces.last_mut()) {
+            namespace.push(word & 0x1fff_ffff);
+        }
+        if word.is_bit_set(29) {
+            vendor_namespace = false;
+        } else if word.is_bit_set(30) {
+            vendor_namespace = true;
+            namespaces.push(Vec::new());
+        }
+    }
+    namespaces
+}
+
+/// Returns the present word of a radiotap namespace after the first, with a
+/// bit for each field defined by a present bit.
+#[cfg(feature = "alloc")]
+fn namespace_word(namespace: &[Value]) -> u32 {
+    namespace
+        .iter()
+        .map(Value::kind)
+        .filter(|kind| kind.bit() < 28)
+        .fold(0, |present, kind| present | 1 << kind.bit())
+}
+
+/// Returns the field of a radiotap namespace after the first that is written
+/// for a present bit, the first one of the kind.
+#[cfg(feature = "alloc")]
+fn namespace_value(namespace: &[Value], kind: Kind) -> Option<&Value> {
+    namespace.iter().find(|value| value.kind() == kind)
+}
+
 /// Represents a parsed Radiotap capture, including the parsed header and all
 /// fields as Option members.
+#[cfg(feature = "alloc")]
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Radiotap {
     pub header: Header,
     pub tsft: Option<TSFT>,
@@ -220,126 +904,2550 @@ pub struct Radiotap {
     pub ampdu_status: Option<AMPDUStatus>,
     pub vht: Option<VHT>,
     pub timestamp: Option<Timestamp>,
+    pub he: Option<HE>,
+    pub he_mu: Option<HEMU>,
+    pub he_mu_other_user: Option<HEMUOtherUser>,
+    pub l_sig: Option<LSig>,
+    pub u_sig: Option<USig>,
+    pub eht: Option<EHT>,
+    /// The vendor namespaces decoded by a built-in decoder, see the
+    /// [vendor](vendor/index.html) module.
+    // Decoded from the vendor namespaces, so not generated on its own
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub vendor: Vec<Vendor>,
+    /// The raw vendor namespaces, written by [unparse](#method.unparse).
+    pub vendor_namespaces: Vec<VendorData>,
+    /// The fields of each radiotap namespace after the first, in the order
+    /// they appear. The other members only hold the fields of the first
+    /// namespace. Written by [unparse](#method.unparse) after the vendor
+    /// namespaces.
+    pub namespaces: Vec<Vec<Value>>,
+    /// The undecoded bytes of the fields, only kept when parsing with the
+    /// [raw](struct.RadiotapParserBuilder.html#method.raw) option, see
+    /// [get_raw](#method.get_raw).
+    // Kept from the capture, so not generated on its own
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub raw: Vec<RawField>,
+}
+
+/// A field held in a member of [Radiotap](struct.Radiotap.html), so it can be
+/// accessed generically with [get](struct.Radiotap.html#method.get) and
+/// [set](struct.Radiotap.html#method.set).
+#[cfg(feature = "alloc")]
+pub trait RadiotapField: PresentField {
+    /// Returns the member holding the field.
+    fn member(radiotap: &Radiotap) -> &Option<Self>;
+
+    /// Returns the mutable member holding the field.
+    fn member_mut(radiotap: &mut Radiotap) -> &mut Option<Self>;
+}
+
+macro_rules! radiotap_fields {
+    ($($field:ident => $member:ident,)*) => {
+        $(
+            #[cfg(feature = "alloc")]
+            impl RadiotapField for $field {
+                fn member(radiotap: &Radiotap) -> &Option<$field> {
+                    &radiotap.$member
+                }
+
+                fn member_mut(radiotap: &mut Radiotap) -> &mut Option<$field> {
+                    &mut radiotap.$member
+                }
+            }
+        )*
+
+        /// The number of members holding a field.
+        #[cfg(feature = "alloc")]
+        const MEMBERS: usize = [$(stringify!($member)),*].len();
+
+        /// Yields the fields that are set as [Value](field/enum.Value.html)s,
+        /// in the order they are written by
+        /// [unparse](struct.Radiotap.html#method.unparse).
+        ///
+        /// Vendor namespaces and the fields of other radiotap namespaces are not
+        /// included, they are kept in their own members.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use radiotap::{field::{Kind, Value}, Radiotap};
+        ///
+        /// let radiotap = Radiotap::from_bytes(&[0, 0, 10, 0, 0x24, 0, 0, 0, 12, 0xd6]).unwrap();
+        /// let kinds: Vec<_> = radiotap.into_iter().map(|value| value.kind()).collect();
+        /// assert_eq!(kinds, [Kind::Rate, Kind::AntennaSignal]);
+        /// ```
+        #[cfg(feature = "alloc")]
+        impl IntoIterator for Radiotap {
+            type Item = Value;
+            type IntoIter = RadiotapIntoIter;
+
+            fn into_iter(self) -> RadiotapIntoIter {
+                let values = [$(self.$member.map(Value::$field)),*];
+                RadiotapIntoIter {
+                    inner: IntoIterator::into_iter(values).flatten(),
+                }
+            }
+        }
+    };
+}
+
+/// An iterator over the fields of a [Radiotap](struct.Radiotap.html), see its
+/// `IntoIterator` implementation.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct RadiotapIntoIter {
+    inner: iter::Flatten<array::IntoIter<Option<Value>, MEMBERS>>,
 }
 
+#[cfg(feature = "alloc")]
+impl Iterator for RadiotapIntoIter {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FusedIterator for RadiotapIntoIter {}
+
+radiotap_fields! {
+    TSFT => tsft,
+    Flags => flags,
+    Rate => rate,
+    Channel => channel,
+    FHSS => fhss,
+    AntennaSignal => antenna_signal,
+    AntennaNoise => antenna_noise,
+    LockQuality => lock_quality,
+    TxAttenuation => tx_attenuation,
+    TxAttenuationDb => tx_attenuation_db,
+    TxPower => tx_power,
+    Antenna => antenna,
+    AntennaSignalDb => antenna_signal_db,
+    AntennaNoiseDb => antenna_noise_db,
+    RxFlags => rx_flags,
+    TxFlags => tx_flags,
+    RTSRetries => rts_retries,
+    DataRetries => data_retries,
+    XChannel => xchannel,
+    MCS => mcs,
+    AMPDUStatus => ampdu_status,
+    VHT => vht,
+    Timestamp => timestamp,
+    HE => he,
+    HEMU => he_mu,
+    HEMUOtherUser => he_mu_other_user,
+    LSig => l_sig,
+    USig => u_sig,
+    EHT => eht,
+}
+
+#[cfg(feature = "alloc")]
 impl Radiotap {
+    /// The fields excluded from the [fingerprint](#method.fingerprint) because
+    /// they change with every capture.
+    pub const VOLATILE_KINDS: &'static [Kind] = &[Kind::TSFT, Kind::AMPDUStatus, Kind::Timestamp];
+
+    /// The maximum length of a capture with a single present word, holding
+    /// every field defined by the radiotap namespace and no TLVs or vendor
+    /// namespaces. This is the most [unparse](#method.unparse) writes for
+    /// such a capture.
+    pub const MAX_FIXED_HEADER_LEN: usize = field::MAX_FIXED_HEADER_LEN;
+
+    /// The maximum length of any Radiotap capture, as the length in the
+    /// header is a `u16`.
+    pub const MAX_LEN: usize = u16::MAX as usize;
+
     /// Returns the parsed [Radiotap](struct.Radiotap.html) from an input byte
     /// array.
     pub fn from_bytes(input: &[u8]) -> Result<Radiotap> {
         Ok(Radiotap::parse(input)?.0)
     }
 
+    /// Returns the receive chains reported in repeated radiotap namespaces,
+    /// in the order they appear.
+    ///
+    /// Each radiotap namespace after the first with an antenna, signal, or
+    /// noise field is a chain. The first namespace is only a chain if it has
+    /// an antenna field, otherwise it holds the combined signal of all chains.
+    /// The chains are read from the members, so they follow any change to
+    /// them.
+    pub fn chains(&self) -> impl Iterator<Item = AntennaChain> + '_ {
+        let first = self.antenna.map(|antenna| AntennaChain {
+            antenna: Some(antenna.value),
+            signal: self.antenna_signal.map(|signal| signal.value),
+            noise: self.antenna_noise.map(|noise| noise.value),
+        });
+        let others = self.namespaces.iter().filter_map(|namespace| {
+            let mut chain = AntennaChain::default();
+            for value in namespace {
+                match value {
+                    Value::Antenna(antenna) => chain.antenna = Some(antenna.value),
+                    Value::AntennaSignal(signal) => chain.signal = Some(signal.value),
+                    Value::AntennaNoise(noise) => chain.noise = Some(noise.value),
+                    _ => {}
+                }
+            }
+            Some(chain).filter(|chain| *chain != AntennaChain::default())
+        });
+        first.into_iter().chain(others)
+    }
+
+    /// Returns the signal to noise ratio.
+    ///
+    /// The combined antenna signal and noise in dBm are used if both are
+    /// present, then the antenna signal and noise in dB, which share the same
+    /// reference. Otherwise the highest ratio of the receive chains with both
+    /// a signal and a noise is returned.
+    pub fn snr(&self) -> Option<Db> {
+        if let (Some(signal), Some(noise)) = (self.antenna_signal, self.antenna_noise) {
+            return Some(signal.dbm() - noise.dbm());
+        }
+        if let (Some(signal), Some(noise)) = (self.antenna_signal_db, self.antenna_noise_db) {
+            return Some(signal.db() - noise.db());
+        }
+        self.chains()
+            .filter_map(|chain| Some(Dbm::from(chain.signal?) - Dbm::from(chain.noise?)))
+            .fold(None, |max: Option<Db>, snr| match max {
+                Some(max) if max >= snr => Some(max),
+                _ => Some(snr),
+            })
+    }
+
+    /// Returns the length of the Radiotap capture at the start of the input,
+    /// which is where the payload starts, without parsing any fields.
+    ///
+    /// Only the version and the length are validated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::Radiotap;
+    ///
+    /// let capture = [0, 0, 9, 0, 4, 0, 0, 0, 12, 0x80, 0];
+    /// let length = Radiotap::peek_length(&capture).unwrap();
+    /// assert_eq!(&capture[length..], &[0x80, 0]);
+    /// ```
+    pub fn peek_length(input: &[u8]) -> Result<usize> {
+        if input.len() < 8 {
+            return Err(Error::IncompleteError);
+        }
+        if input[0] != 0 {
+            return Err(Error::UnsupportedVersion);
+        }
+        let length = usize::from(u16::from_le_bytes([input[2], input[3]]));
+        if length < 8 || input.len() < length {
+            return Err(Error::InvalidLength);
+        }
+        Ok(length)
+    }
+
     /// Returns the parsed [Radiotap](struct.Radiotap.html) and remaining data
     /// from an input byte array.
     pub fn parse(input: &[u8]) -> Result<(Radiotap, &[u8])> {
-        let (iterator, rest) = RadiotapIterator::parse(input)?;
-
-        let mut radiotap = Radiotap {
-            header: iterator.header.clone(),
-            ..Default::default()
-        };
+        let (radiotap, _, rest) = RadiotapParser::new().parse(input)?;
+        Ok((radiotap, rest))
+    }
 
-        for result in &iterator {
-            let (field_kind, data) = result?;
-
-            match field_kind {
-                Kind::TSFT => radiotap.tsft = from_bytes_some(data)?,
-                Kind::Flags => radiotap.flags = from_bytes_some(data)?,
-                Kind::Rate => radiotap.rate = from_bytes_some(data)?,
-                Kind::Channel => radiotap.channel = from_bytes_some(data)?,
-                Kind::FHSS => radiotap.fhss = from_bytes_some(data)?,
-                Kind::AntennaSignal => radiotap.antenna_signal = from_bytes_some(data)?,
-                Kind::AntennaNoise => radiotap.antenna_noise = from_bytes_some(data)?,
-                Kind::LockQuality => radiotap.lock_quality = from_bytes_some(data)?,
-                Kind::TxAttenuation => radiotap.tx_attenuation = from_bytes_some(data)?,
-                Kind::TxAttenuationDb => radiotap.tx_attenuation_db = from_bytes_some(data)?,
-                Kind::TxPower => radiotap.tx_power = from_bytes_some(data)?,
-                Kind::Antenna => radiotap.antenna = from_bytes_some(data)?,
-                Kind::AntennaSignalDb => radiotap.antenna_signal_db = from_bytes_some(data)?,
-                Kind::AntennaNoiseDb => radiotap.antenna_noise_db = from_bytes_some(data)?,
-                Kind::RxFlags => radiotap.rx_flags = from_bytes_some(data)?,
-                Kind::TxFlags => radiotap.tx_flags = from_bytes_some(data)?,
-                Kind::RTSRetries => radiotap.rts_retries = from_bytes_some(data)?,
-                Kind::DataRetries => radiotap.data_retries = from_bytes_some(data)?,
-                Kind::XChannel => radiotap.xchannel = from_bytes_some(data)?,
-                Kind::MCS => radiotap.mcs = from_bytes_some(data)?,
-                Kind::AMPDUStatus => radiotap.ampdu_status = from_bytes_some(data)?,
-                Kind::VHT => radiotap.vht = from_bytes_some(data)?,
-                Kind::Timestamp => radiotap.timestamp = from_bytes_some(data)?,
-                _ => {}
-            }
+    /// Returns the parsed [Radiotap](struct.Radiotap.html) and remaining data
+    /// like [parse](#method.parse), after checking that the link type of the
+    /// capture the input came from is Radiotap, see
+    /// [is_radiotap_linktype](fn.is_radiotap_linktype.html).
+    ///
+    /// Plain 802.11 frames, link type 105, are otherwise often misread as a
+    /// Radiotap header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::{Error, Radiotap, LINKTYPE_IEEE802_11, LINKTYPE_IEEE802_11_RADIOTAP};
+    ///
+    /// let capture = [0, 0, 9, 0, 4, 0, 0, 0, 12];
+    /// assert!(Radiotap::parse_with_linktype(LINKTYPE_IEEE802_11_RADIOTAP, &capture).is_ok());
+    /// assert!(matches!(
+    ///     Radiotap::parse_with_linktype(LINKTYPE_IEEE802_11, &capture),
+    ///     Err(Error::UnsupportedLinkType(105))
+    /// ));
+    /// ```
+    pub fn parse_with_linktype(linktype: u32, input: &[u8]) -> Result<(Radiotap, &[u8])> {
+        if !is_radiotap_linktype(linktype) {
+            return Err(Error::UnsupportedLinkType(linktype));
         }
+        Radiotap::parse(input)
+    }
 
-        Ok((radiotap, rest))
+    /// Returns the parsed [Radiotap](struct.Radiotap.html), the errors of the
+    /// fields that could not be parsed, and the remaining data from an input
+    /// byte array.
+    ///
+    /// Unlike [parse](#method.parse), a malformed field does not fail the
+    /// whole capture, it is left unset and all the other fields are still
+    /// parsed. If a field cannot be located, the fields after it are lost.
+    /// Errors in the header and errors that are not
+    /// [recoverable](enum.Error.html#method.is_recoverable) still fail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::{field::Kind, Radiotap};
+    ///
+    /// // The HE field has an invalid number of LTF symbols
+    /// let capture = [
+    ///     0, 0, 22, 0, 0x20, 0, 0x80, 0, 0xd6, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 5, 0, 0,
+    /// ];
+    /// assert!(Radiotap::parse(&capture).is_err());
+    ///
+    /// let (radiotap, errors, _) = Radiotap::parse_lenient(&capture).unwrap();
+    /// assert_eq!(radiotap.antenna_signal.unwrap().value, -42);
+    /// assert_eq!(radiotap.he, None);
+    /// assert_eq!(errors[0].kind, Some(Kind::HE));
+    /// ```
+    pub fn parse_lenient(input: &[u8]) -> Result<(Radiotap, Vec<FieldError>, &[u8])> {
+        RadiotapParser::builder().lenient(true).done().parse(input)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Parses the input into this [Radiotap](struct.Radiotap.html), returning
+    /// the remaining data.
+    ///
+    /// All fields are cleared first, but the memory of the vendor namespaces,
+    /// and radiotap namespaces is kept, so a capture loop reusing the
+    /// same struct does not allocate for every capture. If parsing fails, the
+    /// fields are left partially set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::Radiotap;
+    ///
+    /// let mut radiotap = Radiotap::default();
+    /// for capture in [[0, 0, 9, 0, 4, 0, 0, 0, 12], [0, 0, 9, 0, 32, 0, 0, 0, 0xd6]].iter() {
+    ///     radiotap.parse_into(capture).unwrap();
+    /// }
+    /// assert_eq!(radiotap.rate, None);
+    /// assert_eq!(radiotap.antenna_signal.unwrap().value, -42);
+    /// ```
+    pub fn parse_into<'a>(&mut self, input: &'a [u8]) -> Result<&'a [u8]> {
+        let (_, rest) = RadiotapParser::new().parse_into(self, input)?;
+        Ok(rest)
+    }
 
-    #[test]
-    fn good_vendor() {
-        let frame = [
-            0, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
-            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+    /// Returns the kinds of the fields that are set, in the order of their
+    /// present bits followed by the fields carried in TLV records.
+    ///
+    /// Unlike the present words of the header, this reflects changes made to
+    /// the members after parsing. Vendor namespaces and the fields of other
+    /// radiotap namespaces are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::{
+    ///     field::{AntennaSignal, Kind, TSFT},
+    ///     Radiotap,
+    /// };
+    ///
+    /// let mut radiotap = Radiotap::default();
+    /// radiotap.set(AntennaSignal { value: -42 });
+    /// radiotap.set(TSFT { value: 1 });
+    /// assert_eq!(
+    ///     radiotap.present().collect::<Vec<_>>(),
+    ///     [Kind::TSFT, Kind::AntennaSignal]
+    /// );
+    /// assert!(radiotap.contains(Kind::TSFT));
+    /// assert!(!radiotap.contains(Kind::VHT));
+    /// ```
+    pub fn present(&self) -> impl Iterator<Item = Kind> {
+        let fields = [
+            (self.tsft.is_some(), Kind::TSFT),
+            (self.flags.is_some(), Kind::Flags),
+            (self.rate.is_some(), Kind::Rate),
+            (self.channel.is_some(), Kind::Channel),
+            (self.fhss.is_some(), Kind::FHSS),
+            (self.antenna_signal.is_some(), Kind::AntennaSignal),
+            (self.antenna_noise.is_some(), Kind::AntennaNoise),
+            (self.lock_quality.is_some(), Kind::LockQuality),
+            (self.tx_attenuation.is_some(), Kind::TxAttenuation),
+            (self.tx_attenuation_db.is_some(), Kind::TxAttenuationDb),
+            (self.tx_power.is_some(), Kind::TxPower),
+            (self.antenna.is_some(), Kind::Antenna),
+            (self.antenna_signal_db.is_some(), Kind::AntennaSignalDb),
+            (self.antenna_noise_db.is_some(), Kind::AntennaNoiseDb),
+            (self.rx_flags.is_some(), Kind::RxFlags),
+            (self.tx_flags.is_some(), Kind::TxFlags),
+            (self.rts_retries.is_some(), Kind::RTSRetries),
+            (self.data_retries.is_some(), Kind::DataRetries),
+            (self.xchannel.is_some(), Kind::XChannel),
+            (self.mcs.is_some(), Kind::MCS),
+            (self.ampdu_status.is_some(), Kind::AMPDUStatus),
+            (self.vht.is_some(), Kind::VHT),
+            (self.timestamp.is_some(), Kind::Timestamp),
+            (self.he.is_some(), Kind::HE),
+            (self.he_mu.is_some(), Kind::HEMU),
+            (self.he_mu_other_user.is_some(), Kind::HEMUOtherUser),
+            (self.l_sig.is_some(), Kind::LSig),
+            (self.u_sig.is_some(), USig::KIND),
+            (self.eht.is_some(), EHT::KIND),
         ];
+        IntoIterator::into_iter(fields).filter_map(|(present, kind)| Some(kind).filter(|_| present))
+    }
 
-        assert_eq!(
-            Radiotap::from_bytes(&frame).unwrap().rate.unwrap(),
-            Rate { value: 2.0 }
-        );
+    /// Returns whether the field of the given kind is set, see
+    /// [present](#method.present).
+    pub fn contains(&self, kind: Kind) -> bool {
+        self.present().any(|present| present == kind)
     }
 
-    #[test]
-    fn bad_version() {
-        let frame = [
-            1, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
-            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
-        ];
+    /// Returns a [RadiotapBuilder](struct.RadiotapBuilder.html) starting from
+    /// the fields of this capture, to change some of them and write it again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::{field::TxPower, Radiotap};
+    ///
+    /// let radiotap = Radiotap::from_bytes(&[0, 0, 10, 0, 4, 4, 0, 0, 12, 10]).unwrap();
+    /// let radiotap = radiotap.to_builder().tx_power(TxPower { value: 20 }).done();
+    /// assert_eq!(radiotap.to_bytes().unwrap(), [0, 0, 10, 0, 4, 4, 0, 0, 12, 20]);
+    /// ```
+    pub fn to_builder(&self) -> RadiotapBuilder {
+        RadiotapBuilder::from_radiotap(self.clone())
+    }
 
-        match Radiotap::from_bytes(&frame).unwrap_err() {
-            Error::UnsupportedVersion => {}
-            e => panic!("Error not UnsupportedVersion: {:?}", e),
-        };
+    /// Returns the undecoded bytes of the field of the given kind, without
+    /// padding or a TLV header.
+    ///
+    /// The bytes are only kept when parsing with the
+    /// [raw](struct.RadiotapParserBuilder.html#method.raw) option, and only
+    /// for the fields of the first radiotap namespace, including TLV records
+    /// of unknown types. They are not updated when the members are changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::{field::Kind, RadiotapParser};
+    ///
+    /// let parser = RadiotapParser::builder().raw(true).done();
+    /// let (radiotap, _, _) = parser.parse(&[0, 0, 10, 0, 0x24, 0, 0, 0, 12, 0xd6]).unwrap();
+    /// assert_eq!(radiotap.get_raw(Kind::AntennaSignal), Some(&[0xd6][..]));
+    /// assert_eq!(radiotap.get_raw(Kind::TSFT), None);
+    /// ```
+    pub fn get_raw(&self, kind: Kind) -> Option<&[u8]> {
+        self.raw
+            .iter()
+            .find(|field| field.kind == kind)
+            .map(|field| &field.data[..])
     }
 
-    #[test]
-    fn bad_header_length() {
-        let frame = [
-            0, 0, 40, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
-            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
-        ];
+    /// Returns the field of the given type, if it is present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::{
+    ///     field::{AntennaSignal, Rate},
+    ///     Radiotap,
+    /// };
+    ///
+    /// let mut radiotap = Radiotap::default();
+    /// radiotap.set(AntennaSignal { value: -42 });
+    /// assert_eq!(radiotap.get::<AntennaSignal>().unwrap().value, -42);
+    /// assert_eq!(radiotap.get::<Rate>(), None);
+    /// ```
+    pub fn get<T: RadiotapField>(&self) -> Option<&T> {
+        T::member(self).as_ref()
+    }
 
-        match Radiotap::from_bytes(&frame).unwrap_err() {
-            Error::InvalidLength => {}
-            e => panic!("Error not InvalidLength: {:?}", e),
-        };
+    /// Sets the field of the given type, returning the previous field.
+    pub fn set<T: RadiotapField>(&mut self, field: T) -> Option<T> {
+        T::member_mut(self).replace(field)
     }
 
-    #[test]
-    fn bad_actual_length() {
-        let frame = [
-            0, 0, 39, 0, 47, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
-            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
-        ];
+    /// Decodes a single field of a capture, skipping over the others, see
+    /// [RadiotapIterator::find_field](struct.RadiotapIterator.html#method.find_field).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::{field::AntennaSignal, Radiotap};
+    ///
+    /// let capture = [0, 0, 10, 0, 0x24, 0, 0, 0, 12, 0xd6];
+    /// let signal = Radiotap::get_one::<AntennaSignal>(&capture).unwrap();
+    /// assert_eq!(signal.unwrap().value, -42);
+    /// ```
+    pub fn get_one<T: PresentField>(input: &[u8]) -> Result<Option<T>> {
+        let iterator = RadiotapIterator::from_bytes(input)?;
+        iterator.find_field(T::KIND)?.map(T::from_bytes).transpose()
+    }
 
-        match Radiotap::from_bytes(&frame).unwrap_err() {
-            Error::IncompleteError => {}
-            e => panic!("Error not IncompleteError: {:?}", e),
+    /// Resets all fields, keeping the memory allocated by the vectors.
+    pub(crate) fn clear(&mut self) {
+        let mut vendor = mem::take(&mut self.vendor);
+        let mut vendor_namespaces = mem::take(&mut self.vendor_namespaces);
+        let mut namespaces = mem::take(&mut self.namespaces);
+        let mut raw = mem::take(&mut self.raw);
+        vendor.clear();
+        vendor_namespaces.clear();
+        namespaces.clear();
+        raw.clear();
+        *self = Radiotap {
+            vendor,
+            vendor_namespaces,
+            namespaces,
+            raw,
+            ..Default::default()
         };
     }
 
-    #[test]
-    fn bad_vendor() {
-        let frame = [
-            0, 0, 34, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
-            160, 0, 227, 5, 0, 0, 255, 255, 255, 255,
-        ];
+    /// Returns the parsed [Radiotap](struct.Radiotap.html) and the
+    /// [Payload](payload/struct.Payload.html) following it, which accounts
+    /// for the FCS and padding described by the flags.
+    pub fn parse_with_payload(input: &[u8]) -> Result<(Radiotap, Payload<'_>)> {
+        let (radiotap, rest) = Radiotap::parse(input)?;
+        let payload = Payload::new(rest, radiotap.flags);
+        Ok((radiotap, payload))
+    }
 
-        match Radiotap::from_bytes(&frame).unwrap_err() {
-            Error::IncompleteError => {}
-            e => panic!("Error not IncompleteError: {:?}", e),
-        };
+    /// Returns the data following the Radiotap capture without the 4 byte
+    /// FCS if the flags say the frame includes one, or untouched otherwise,
+    /// see [Payload::frame](payload/struct.Payload.html#method.frame).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::Radiotap;
+    ///
+    /// // An ACK frame with an FCS
+    /// let capture = [
+    ///     0, 0, 9, 0, 2, 0, 0, 0, 0x10, 0xd4, 0, 0, 0, 2, 4, 6, 8, 10, 12, 0x4b, 0xea, 0x19, 0xd5,
+    /// ];
+    /// let (radiotap, rest) = Radiotap::parse(&capture).unwrap();
+    /// assert_eq!(radiotap.strip_fcs(rest), &capture[9..19]);
+    /// assert_eq!(Radiotap::default().strip_fcs(rest), rest);
+    /// ```
+    pub fn strip_fcs<'a>(&self, data: &'a [u8]) -> &'a [u8] {
+        Payload::new(data, self.flags).frame()
+    }
+
+    /// Returns the parsed [Radiotap](struct.Radiotap.html) and the 802.11
+    /// frame following it, parsed by another crate with the given
+    /// [FrameParser](payload/trait.FrameParser.html).
+    ///
+    /// The parser is given the frame without the FCS, and without the padding
+    /// after the MAC header if the flags say there is some, see
+    /// [Payload::unpadded_frame](payload/struct.Payload.html#method.unpadded_frame).
+    /// An error parsing the frame is returned in the inner result, so the
+    /// Radiotap capture is still available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::Radiotap;
+    ///
+    /// // An ACK frame with an FCS
+    /// let capture = [
+    ///     0, 0, 9, 0, 2, 0, 0, 0, 0x10, 0xd4, 0, 0, 0, 2, 4, 6, 8, 10, 12, 0x4b, 0xea, 0x19, 0xd5,
+    /// ];
+    /// let receiver = |frame: &[u8]| frame.get(4..10).ok_or("too short").map(<[u8]>::to_vec);
+    /// let (_, frame) = Radiotap::parse_with_frame(&capture, receiver).unwrap();
+    /// assert_eq!(frame, Ok(vec![2, 4, 6, 8, 10, 12]));
+    /// ```
+    pub fn parse_with_frame<P: FrameParser>(
+        input: &[u8],
+        parser: P,
+    ) -> Result<(Radiotap, FrameResult<P>)> {
+        let (radiotap, payload) = Radiotap::parse_with_payload(input)?;
+        let frame = parser.parse_frame(&payload.unpadded_frame());
+        Ok((radiotap, frame))
+    }
+
+    /// Returns the parsed [Radiotap](struct.Radiotap.html) and remaining data
+    /// like [parse](#method.parse), but accepts any header version, which is
+    /// recorded in the `header` member.
+    pub fn parse_any_version(input: &[u8]) -> Result<(Radiotap, &[u8])> {
+        let (radiotap, _, rest) = RadiotapParser::builder()
+            .any_version(true)
+            .done()
+            .parse(input)?;
+        Ok((radiotap, rest))
+    }
+
+    /// Returns the parsed [Radiotap](struct.Radiotap.html) and remaining data
+    /// from an input byte array, failing with
+    /// [Violations](enum.Error.html#variant.Violations) if the capture does
+    /// not strictly follow the specification, see
+    /// [validate](validate/fn.validate.html).
+    pub fn parse_strict(input: &[u8]) -> Result<(Radiotap, &[u8])> {
+        let (radiotap, _, rest) = RadiotapParser::builder().strict(true).done().parse(input)?;
+        Ok((radiotap, rest))
+    }
+
+    /// Parses all fields with the options of the parser.
+    pub(crate) fn parse_fields<'a>(
+        radiotap: &mut Radiotap,
+        (iterator, rest): (RadiotapIterator<'a>, &'a [u8]),
+        parser: &RadiotapParser,
+    ) -> Result<(Vec<FieldError>, &'a [u8])> {
+        let lenient = parser.lenient;
+        let mut errors = Vec::new();
+        // The kinds set so far, by present bit or by TLV type after them
+        let mut seen = 0u128;
+        let vendor_present = vendor_present_words(&iterator.header.present);
+
+        let limits = parser.limits;
+        if iterator.header.present.words().len() > limits.present_words {
+            return Err(Error::LimitExceeded("present words"));
+        }
+
+        for (step, result) in (&iterator).into_iter().enumerate() {
+            if step >= limits.fields {
+                return Err(Error::LimitExceeded("fields"));
+            }
+            let (kind, data) = match result {
+                Ok(field) => field,
+                // Keep all the fields before the one blocking the iteration
+                Err(Error::UnknownFieldBlocksParsing { bit }) => {
+                    if lenient {
+                        errors.try_reserve(1)?;
+                        errors.push(FieldError {
+                            kind: Some(Kind::Unknown(bit)),
+                            error: Error::UnknownFieldBlocksParsing { bit },
+                        });
+                    }
+                    break;
+                }
+                Err(error) if lenient && error.is_recoverable() => {
+                    errors.try_reserve(1)?;
+                    errors.push(FieldError { kind: None, error });
+                    break;
+                }
+                Err(e) => return Err(e),
+            };
+
+            let kind = match kind {
+                // TLVs of fields defined by a present bit have the same contents
+                Kind::TLV(Some(kind)) if kind < 28 => Kind::new(kind as u8)?,
+                kind => kind,
+            };
+
+            if let Kind::VendorNamespace(_) | Kind::TLV(Some(tlv::VENDOR_NAMESPACE)) = kind {
+                if data.len() > limits.vendor_length {
+                    return Err(Error::LimitExceeded("vendor length"));
+                }
+            }
+
+            // Only the fields of the first radiotap namespace have a member
+            // that can be set twice
+            let duplicate = match kind {
+                Kind::RadiotapNamespace
+                | Kind::VendorNamespace(_)
+                | Kind::TLV(Some(tlv::VENDOR_NAMESPACE))
+                | Kind::Unknown(_) => false,
+                _ if !radiotap.namespaces.is_empty() => false,
+                kind => {
+                    let bit = match kind {
+                        Kind::TLV(Some(kind)) => 32 + u32::from(kind),
+                        kind => u32::from(kind.bit()),
+                    };
+                    let mask = 1u128.checked_shl(bit).unwrap_or(0);
+                    let duplicate = seen & mask != 0;
+                    seen |= mask;
+                    duplicate
+                }
+            };
+
+            let first_namespace = radiotap.namespaces.is_empty();
+            let result = match (duplicate, parser.duplicates) {
+                (true, Duplicates::KeepFirst) => Ok(()),
+                (true, Duplicates::Reject) => {
+                    Err(Error::Violations(vec![Violation::DuplicateField { kind }]))
+                }
+                _ => radiotap
+                    .parse_field(kind, data, &vendor_present, parser.vendor)
+                    .and_then(|()| match kind {
+                        // The raw vendor namespaces are always kept
+                        Kind::RadiotapNamespace
+                        | Kind::VendorNamespace(_)
+                        | Kind::TLV(Some(tlv::VENDOR_NAMESPACE)) => Ok(()),
+                        _ if parser.raw && first_namespace => radiotap.keep_raw(kind, data),
+                        _ => Ok(()),
+                    }),
+            };
+            match result {
+                Ok(()) => {}
+                Err(error) if lenient && error.is_recoverable() => {
+                    errors.try_reserve(1)?;
+                    errors.push(FieldError {
+                        kind: Some(kind),
+                        error,
+                    });
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        radiotap.header = iterator.header;
+
+        Ok((errors, rest))
+    }
+
+    /// Parses a single field into its member, `vendor_present` holds the
+    /// present words of the vendor namespaces, and `decode_vendor` whether they
+    /// are decoded by the built-in decoders.
+    fn parse_field(
+        &mut self,
+        field_kind: Kind,
+        data: &[u8],
+        vendor_present: &[Vec<u32>],
+        decode_vendor: bool,
+    ) -> Result<()> {
+        // Fields of later radiotap namespaces do not replace the first ones
+        match (field_kind, self.namespaces.last_mut()) {
+            (Kind::RadiotapNamespace, _) => {
+                self.namespaces.try_reserve(1)?;
+                self.namespaces.push(Vec::new());
+                return Ok(());
+            }
+            (kind, Some(namespace)) if kind.bit() < 28 => {
+                match Value::from_bytes(kind, data) {
+                    Ok(value) => {
+                        namespace.try_reserve(1)?;
+                        namespace.push(value);
+                    }
+                    Err(Error::UnsupportedField) => {}
+                    Err(e) => return Err(e),
+                }
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        match field_kind {
+            Kind::TSFT => self.tsft = from_bytes_some(data)?,
+            Kind::Flags => self.flags = from_bytes_some(data)?,
+            Kind::Rate => self.rate = from_bytes_some(data)?,
+            Kind::Channel => self.channel = from_bytes_some(data)?,
+            Kind::FHSS => self.fhss = from_bytes_some(data)?,
+            Kind::AntennaSignal => self.antenna_signal = from_bytes_some(data)?,
+            Kind::AntennaNoise => self.antenna_noise = from_bytes_some(data)?,
+            Kind::LockQuality => self.lock_quality = from_bytes_some(data)?,
+            Kind::TxAttenuation => self.tx_attenuation = from_bytes_some(data)?,
+            Kind::TxAttenuationDb => self.tx_attenuation_db = from_bytes_some(data)?,
+            Kind::TxPower => self.tx_power = from_bytes_some(data)?,
+            Kind::Antenna => self.antenna = from_bytes_some(data)?,
+            Kind::AntennaSignalDb => self.antenna_signal_db = from_bytes_some(data)?,
+            Kind::AntennaNoiseDb => self.antenna_noise_db = from_bytes_some(data)?,
+            Kind::RxFlags => self.rx_flags = from_bytes_some(data)?,
+            Kind::TxFlags => self.tx_flags = from_bytes_some(data)?,
+            Kind::RTSRetries => self.rts_retries = from_bytes_some(data)?,
+            Kind::DataRetries => self.data_retries = from_bytes_some(data)?,
+            Kind::XChannel => self.xchannel = from_bytes_some(data)?,
+            Kind::MCS => self.mcs = from_bytes_some(data)?,
+            Kind::AMPDUStatus => self.ampdu_status = from_bytes_some(data)?,
+            Kind::VHT => self.vht = from_bytes_some(data)?,
+            Kind::Timestamp => self.timestamp = from_bytes_some(data)?,
+            Kind::HE => self.he = from_bytes_some(data)?,
+            Kind::HEMU => self.he_mu = from_bytes_some(data)?,
+            Kind::HEMUOtherUser => self.he_mu_other_user = from_bytes_some(data)?,
+            Kind::LSig => self.l_sig = from_bytes_some(data)?,
+            Kind::TLV(Some(tlv::U_SIG)) => self.u_sig = from_bytes_some(data)?,
+            Kind::TLV(Some(tlv::EHT)) => self.eht = from_bytes_some(data)?,
+            Kind::VendorNamespace(Some(vns)) => {
+                let present = vendor_present.get(self.vendor_namespaces.len());
+                self.vendor_namespaces.try_reserve(1)?;
+                self.vendor_namespaces.push(VendorData {
+                    oui: vns.oui,
+                    sub_namespace: vns.sub_namespace,
+                    present: present.cloned().unwrap_or_default(),
+                    data: data.to_vec(),
+                });
+                if decode_vendor {
+                    if let Some(vendor) = vendor::decode(&vns, data)? {
+                        self.vendor.try_reserve(1)?;
+                        self.vendor.push(vendor);
+                    }
+                }
+            }
+            Kind::TLV(Some(tlv::VENDOR_NAMESPACE)) => {
+                let vendor = tlv::parse_vendor(data)?;
+                let vns = VendorNamespace {
+                    oui: vendor.oui,
+                    sub_namespace: vendor.sub_namespace,
+                    skip_length: vendor.data.len() as u16,
+                };
+                self.vendor_namespaces.try_reserve(1)?;
+                self.vendor_namespaces.push(vendor);
+                if decode_vendor {
+                    let data = &self.vendor_namespaces[self.vendor_namespaces.len() - 1].data;
+                    if let Some(decoded) = vendor::decode(&vns, data)? {
+                        self.vendor.try_reserve(1)?;
+                        self.vendor.push(decoded);
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Keeps the undecoded bytes of a field, replacing those of an earlier
+    /// occurrence of the same kind.
+    fn keep_raw(&mut self, kind: Kind, data: &[u8]) -> Result<()> {
+        let mut bytes = Vec::new();
+        bytes.try_reserve(data.len())?;
+        bytes.extend_from_slice(data);
+        match self.raw.iter_mut().find(|field| field.kind == kind) {
+            Some(field) => field.data = bytes,
+            None => {
+                self.raw.try_reserve(1)?;
+                self.raw.push(RawField { kind, data: bytes });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the kinds of the fields that are set, in the order they are
+    /// written by [unparse](#method.unparse).
+    fn present_kinds(&self) -> Vec<Kind> {
+        let mut kinds: Vec<_> = self
+            .present()
+            .filter(|kind| !matches!(kind, Kind::TLV(_)))
+            .collect();
+        if self.has_tlvs() {
+            kinds.push(Kind::TLV(None));
+        } else {
+            // Vendor namespaces are written as TLV records if there are any
+            kinds.extend(
+                self.vendor_namespaces
+                    .iter()
+                    .map(|_| Kind::VendorNamespace(None)),
+            );
+        }
+        kinds
+    }
+
+    /// Returns whether any fields are written as TLV records.
+    fn has_tlvs(&self) -> bool {
+        self.u_sig.is_some() || self.eht.is_some()
+    }
+
+    /// Returns the present words written by [unparse](#method.unparse).
+    ///
+    /// The first radiotap namespace is followed by the vendor namespaces,
+    /// unless they are written as TLV records, and then by the other radiotap
+    /// namespaces. Each namespace but the last switches to the next one. The
+    /// TLV records follow all other fields, so their bit is set in the last
+    /// radiotap namespace.
+    fn unparsed_words(&self) -> Vec<u32> {
+        let first = self
+            .present_kinds()
+            .iter()
+            .filter(|kind| !matches!(kind, Kind::VendorNamespace(_) | Kind::TLV(_)))
+            .fold(0u32, |present, kind| present | 1 << kind.bit());
+        let mut words = vec![first];
+        let switch = |words: &mut Vec<u32>, bit: u32| {
+            if let Some(word) = words.last_mut() {
+                *word |= 1 << 31 | 1 << bit;
+            }
+        };
+
+        if !self.has_tlvs() {
+            for vendor in &self.vendor_namespaces {
+                switch(&mut words, 30);
+                match vendor.present.split_last() {
+                    Some((last, rest)) => {
+                        words.extend(rest.iter().map(|word| word & 0x1fff_ffff | 1 << 31));
+                        words.push(last & 0x1fff_ffff);
+                    }
+                    None => words.push(0),
+                }
+            }
+        }
+        for namespace in &self.namespaces {
+            switch(&mut words, 29);
+            words.push(namespace_word(namespace));
+        }
+
+        if let (true, Some(word)) = (self.has_tlvs(), words.last_mut()) {
+            *word |= 1 << 28;
+        }
+        words
+    }
+
+    /// Returns the header describing the fields that are set, as written by
+    /// [unparse](#method.unparse).
+    fn unparsed_header(&self) -> Header {
+        // Present words that do not fit are rejected by unparse
+        let mut present = PresentFlags::default();
+        for word in self.unparsed_words() {
+            if present.push(word).is_err() {
+                break;
+            }
+        }
+
+        let size = 4 + 4 * present.words().len();
+        // Fields that fail to unparse also fail when the header is written
+        let length = self
+            .unparse_fields(&present, size, &mut io::sink())
+            .unwrap_or(size);
+
+        Header {
+            version: 0,
+            length,
+            size,
+            present,
+        }
+    }
+
+    /// Returns the header written by [unparse](#method.unparse), or the error
+    /// writing the capture would fail with.
+    fn checked_header(&self) -> Result<Header> {
+        let present =
+            PresentFlags::new(&self.unparsed_words()).map_err(|_| Error::InvalidLength)?;
+        let size = 4 + 4 * present.words().len();
+        let length = self.unparse_fields(&present, size, &mut io::sink())?;
+        u16::try_from(length).map_err(|_| Error::InvalidLength)?;
+
+        Ok(Header {
+            version: 0,
+            length,
+            size,
+            present,
+        })
+    }
+
+    /// Writes the Radiotap capture, returning the number of bytes written.
+    ///
+    /// The header is derived from the fields that are set, the `header` member
+    /// is ignored.
+    pub fn unparse<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        let header = self.checked_header()?;
+        self.unparse_with_header(&header, writer)
+    }
+
+    /// Writes the Radiotap capture with the header returned by
+    /// [checked_header](#method.checked_header).
+    fn unparse_with_header<W: Write>(&self, header: &Header, writer: &mut W) -> Result<usize> {
+        writer.write_u8(header.version)?;
+        writer.write_u8(0)?;
+        // The length is checked to fit when the header is built
+        writer.write_u16::<LE>(header.length as u16)?;
+        for &word in header.present.words() {
+            writer.write_u32::<LE>(word)?;
+        }
+
+        self.unparse_fields(&header.present, header.size, writer)
+    }
+
+    /// Writes the fields of the present words after the header, with the
+    /// padding from `offset`, returning the offset of the end of the capture.
+    fn unparse_fields<W: Write>(
+        &self,
+        present: &PresentFlags,
+        mut offset: usize,
+        writer: &mut W,
+    ) -> Result<usize> {
+        let mut vendor = self.vendor_namespaces.iter();
+        let mut namespaces = self.namespaces.iter();
+        let mut namespace = None;
+        for kind in present {
+            let padding = align(offset, kind.align()) - offset;
+            writer.write_all(&[0; 8][..padding])?;
+            offset += padding;
+            offset += match (kind, namespace) {
+                (Kind::RadiotapNamespace, _) => {
+                    namespace = namespaces.next();
+                    0
+                }
+                (Kind::VendorNamespace(_), _) => {
+                    vendor.next().map_or(Ok(0), |v| v.unparse(writer))?
+                }
+                (Kind::TLV(None), _) => self.unparse_tlvs(writer)?,
+                (kind, Some(namespace)) => {
+                    namespace_value(namespace, kind).map_or(Ok(0), |v| v.unparse(writer))?
+                }
+                (kind, None) => self.unparse_field(kind, writer)?,
+            };
+        }
+        Ok(offset)
+    }
+
+    /// Returns the Radiotap capture as bytes, see [unparse](#method.unparse).
+    ///
+    /// The buffer is allocated once, with the exact length of the capture.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::{field::*, RadiotapBuilder};
+    ///
+    /// let radiotap = RadiotapBuilder::new().rate(Rate::from_mbps(6.0).unwrap()).done();
+    /// let bytes = radiotap.to_bytes().unwrap();
+    /// assert_eq!(bytes, [0, 0, 9, 0, 4, 0, 0, 0, 12]);
+    /// ```
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let header = self.checked_header()?;
+        let mut bytes = Vec::new();
+        bytes.try_reserve_exact(header.length)?;
+        self.unparse_with_header(&header, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Returns the Radiotap capture as bytes, the same as
+    /// [to_bytes](#method.to_bytes).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::{field::*, RadiotapBuilder};
+    ///
+    /// let radiotap = RadiotapBuilder::new().rate(Rate::from_mbps(6.0).unwrap()).done();
+    /// assert_eq!(radiotap.to_vec().unwrap(), [0, 0, 9, 0, 4, 0, 0, 0, 12]);
+    /// ```
+    pub fn to_vec(&self) -> Result<Vec<u8>> {
+        self.to_bytes()
+    }
+
+    /// Writes the Radiotap capture to the start of the buffer, returning the
+    /// number of bytes written, see [unparse](#method.unparse).
+    ///
+    /// If the capture does not fit, nothing is written and an IO error of kind
+    /// `WriteZero` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::{field::*, RadiotapBuilder};
+    ///
+    /// let radiotap = RadiotapBuilder::new().rate(Rate::from_mbps(6.0).unwrap()).done();
+    /// let mut buf = [0; 16];
+    /// assert_eq!(radiotap.unparse_into(&mut buf).unwrap(), 9);
+    /// assert_eq!(&buf[..9], &[0, 0, 9, 0, 4, 0, 0, 0, 12]);
+    /// assert!(radiotap.unparse_into(&mut buf[..8]).is_err());
+    /// ```
+    pub fn unparse_into(&self, buf: &mut [u8]) -> Result<usize> {
+        let header = self.checked_header()?;
+        if buf.len() < header.length {
+            return Err(io::Error::from(io::ErrorKind::WriteZero).into());
+        }
+        self.unparse_with_header(&header, &mut &mut buf[..])
+    }
+
+    /// Writes the Radiotap capture like [unparse](#method.unparse), but with
+    /// a single write of the whole capture instead of one per field and
+    /// padding, which is faster on unbuffered writers such as sockets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::{field::*, RadiotapBuilder};
+    ///
+    /// let radiotap = RadiotapBuilder::new().rate(Rate::from_mbps(6.0).unwrap()).done();
+    /// let mut bytes = Vec::new();
+    /// assert_eq!(radiotap.unparse_buffered(&mut bytes).unwrap(), 9);
+    /// assert_eq!(bytes, radiotap.to_bytes().unwrap());
+    /// ```
+    pub fn unparse_buffered<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        let bytes = self.to_bytes()?;
+        writer.write_all(&bytes)?;
+        Ok(bytes.len())
+    }
+
+    /// Writes a single field if it is set, returning the number of bytes
+    /// written.
+    fn unparse_field<W: Write>(&self, kind: Kind, writer: &mut W) -> Result<usize> {
+        Ok(match kind {
+            Kind::TSFT => unparse_some(&self.tsft, writer)?,
+            Kind::Flags => unparse_some(&self.flags, writer)?,
+            Kind::Rate => unparse_some(&self.rate, writer)?,
+            Kind::Channel => unparse_some(&self.channel, writer)?,
+            Kind::FHSS => unparse_some(&self.fhss, writer)?,
+            Kind::AntennaSignal => unparse_some(&self.antenna_signal, writer)?,
+            Kind::AntennaNoise => unparse_some(&self.antenna_noise, writer)?,
+            Kind::LockQuality => unparse_some(&self.lock_quality, writer)?,
+            Kind::TxAttenuation => unparse_some(&self.tx_attenuation, writer)?,
+            Kind::TxAttenuationDb => unparse_some(&self.tx_attenuation_db, writer)?,
+            Kind::TxPower => unparse_some(&self.tx_power, writer)?,
+            Kind::Antenna => unparse_some(&self.antenna, writer)?,
+            Kind::AntennaSignalDb => unparse_some(&self.antenna_signal_db, writer)?,
+            Kind::AntennaNoiseDb => unparse_some(&self.antenna_noise_db, writer)?,
+            Kind::RxFlags => unparse_some(&self.rx_flags, writer)?,
+            Kind::TxFlags => unparse_some(&self.tx_flags, writer)?,
+            Kind::RTSRetries => unparse_some(&self.rts_retries, writer)?,
+            Kind::DataRetries => unparse_some(&self.data_retries, writer)?,
+            Kind::XChannel => unparse_some(&self.xchannel, writer)?,
+            Kind::MCS => unparse_some(&self.mcs, writer)?,
+            Kind::AMPDUStatus => unparse_some(&self.ampdu_status, writer)?,
+            Kind::VHT => unparse_some(&self.vht, writer)?,
+            Kind::Timestamp => unparse_some(&self.timestamp, writer)?,
+            Kind::HE => unparse_some(&self.he, writer)?,
+            Kind::HEMU => unparse_some(&self.he_mu, writer)?,
+            Kind::HEMUOtherUser => unparse_some(&self.he_mu_other_user, writer)?,
+            Kind::LSig => unparse_some(&self.l_sig, writer)?,
+            Kind::TLV(None) => self.unparse_tlvs(writer)?,
+            _ => 0,
+        })
+    }
+
+    /// Writes the fields that are only carried in TLVs as TLV records,
+    /// returning the number of bytes written.
+    fn unparse_tlvs<W: Write>(&self, writer: &mut W) -> Result<usize> {
+        let mut written = 0;
+        if let Some(u_sig) = &self.u_sig {
+            written += tlv::unparse(tlv::U_SIG, u_sig, writer)?;
+        }
+        if let Some(eht) = &self.eht {
+            written += tlv::unparse(tlv::EHT, eht, writer)?;
+        }
+        for vendor in &self.vendor_namespaces {
+            written += tlv::unparse_vendor(vendor, writer)?;
+        }
+        Ok(written)
+    }
+
+    /// Returns a stable 64-bit fingerprint of the field values, excluding the
+    /// [VOLATILE_KINDS](#associatedconstant.VOLATILE_KINDS) that differ between
+    /// otherwise identical captures.
+    ///
+    /// The fingerprint only depends on the decoded values, not on padding or
+    /// the order of the fields in the capture, and does not change between
+    /// platforms or releases.
+    ///
+    /// An error is returned if a field holds a value that cannot be written,
+    /// see [unparse](#method.unparse).
+    pub fn fingerprint(&self) -> Result<u64> {
+        self.fingerprint_excluding(Radiotap::VOLATILE_KINDS)
+    }
+
+    /// Returns a stable 64-bit fingerprint of the field values, excluding the
+    /// given kinds of fields.
+    pub fn fingerprint_excluding(&self, excluded: &[Kind]) -> Result<u64> {
+        let mut hasher = Fnv64::new();
+        let mut vendor = self.vendor_namespaces.iter();
+        for kind in self.present_kinds() {
+            let vendor = match kind {
+                Kind::VendorNamespace(_) => vendor.next(),
+                _ => None,
+            };
+            if excluded.contains(&kind) {
+                continue;
+            }
+            hasher.0 = Fnv64::hash(hasher.0, &[kind.bit()]);
+            match vendor {
+                Some(vendor) => vendor.unparse(&mut hasher)?,
+                None => self.unparse_field(kind, &mut hasher)?,
+            };
+        }
+        for namespace in &self.namespaces {
+            hasher.0 = Fnv64::hash(hasher.0, &[Kind::RadiotapNamespace.bit()]);
+            let present = PresentFlags::new(&[namespace_word(namespace)])?;
+            for kind in &present {
+                if excluded.contains(&kind) {
+                    continue;
+                }
+                hasher.0 = Fnv64::hash(hasher.0, &[kind.bit()]);
+                if let Some(value) = namespace_value(namespace, kind) {
+                    value.unparse(&mut hasher)?;
+                }
+            }
+        }
+        Ok(hasher.0)
+    }
+
+    /// Returns a [Summary](summary/struct.Summary.html) of the values most
+    /// applications need, such as the frequency, signal, and data rate.
+    pub fn summary(&self) -> Summary {
+        Summary::new(self)
+    }
+
+    /// Returns the frequency band the frame was transmitted or received on.
+    ///
+    /// The band is derived from the frequency of the XChannel or Channel
+    /// field, or from their 2 GHz and 5 GHz flags if the frequency is not in
+    /// a known band.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::{util::channel::Band, Radiotap};
+    ///
+    /// let capture = [0, 0, 12, 0, 8, 0, 0, 0, 0x43, 0x17, 0x40, 0x01];
+    /// let radiotap = Radiotap::from_bytes(&capture).unwrap();
+    /// assert_eq!(radiotap.band(), Some(Band::Band6GHz));
+    /// ```
+    pub fn band(&self) -> Option<Band> {
+        let (freq, ghz2, ghz5) = match (self.xchannel, self.channel) {
+            (Some(xchannel), _) => (xchannel.freq, xchannel.flags.ghz2, xchannel.flags.ghz5),
+            (None, Some(channel)) => (channel.freq, channel.flags.ghz2, channel.flags.ghz5),
+            (None, None) => return None,
+        };
+        match (Band::from_freq(freq), ghz2, ghz5) {
+            (Some(band), _, _) => Some(band),
+            (None, true, false) => Some(Band::Band2GHz),
+            (None, false, true) => Some(Band::Band5GHz),
+            _ => None,
+        }
+    }
+
+    /// Returns the estimated time a frame with a PSDU of `psdu_len` bytes
+    /// occupies the medium, including the preamble, see
+    /// [airtime::estimate](airtime/fn.estimate.html).
+    ///
+    /// `None` is returned if the data rate is not known, from the HE, VHT, or
+    /// MCS field, or the legacy rate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radiotap::{field::Rate, Radiotap};
+    /// use std::time::Duration;
+    ///
+    /// let radiotap = Radiotap {
+    ///     rate: Some(Rate::from_mbps(6.0).unwrap()),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(radiotap.airtime(100), Some(Duration::from_micros(160)));
+    /// ```
+    pub fn airtime(&self, psdu_len: usize) -> Option<Duration> {
+        airtime::estimate(self, psdu_len)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::field::ext::*;
+    use crate::util::power::{Db, Dbm};
+
+    #[test]
+    fn good_vendor() {
+        let frame = [
+            0, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+
+        // The rate is repeated in the radiotap namespace after the vendor one
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(radiotap.rate.unwrap(), Rate::from_mbps(1.0).unwrap());
+        assert_eq!(
+            radiotap.namespaces,
+            [vec![Value::Rate(Rate::from_mbps(2.0).unwrap())]]
+        );
+        assert_eq!(radiotap.vendor_namespaces[0].present, [0, 0]);
+        assert_eq!(radiotap.to_bytes().unwrap(), frame);
+    }
+
+    #[test]
+    fn unknown_field_before_namespace() {
+        let frame = [0, 0, 15, 0, 2, 0, 0, 164, 32, 0, 0, 0, 16, 1, 214];
+
+        // Bit 26 is unassigned, so the fields after it cannot be located
+        let mut iterator = RadiotapIterator::from_bytes(&frame).unwrap().into_iter();
+        assert_eq!(iterator.next().unwrap().unwrap().0, Kind::Flags);
+        match iterator.next().unwrap().unwrap_err() {
+            Error::UnknownFieldBlocksParsing { bit: 26 } => {}
+            e => panic!("Error not UnknownFieldBlocksParsing: {:?}", e),
+        };
+        assert!(iterator.next().is_none());
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert!(radiotap.flags.unwrap().fcs);
+        assert_eq!(radiotap.antenna_signal, None);
+        assert!(radiotap.namespaces.is_empty());
+    }
+
+    #[test]
+    fn present_flags() {
+        let frame = [0, 0, 15, 0, 2, 0, 0, 164, 32, 0, 0, 0, 16, 1, 214];
+
+        let header = RadiotapIterator::from_bytes(&frame).unwrap().header;
+        assert_eq!(header.present.words(), [0xa400_0002, 0x0000_0020]);
+        assert_eq!(header.present.to_string(), "0xa4000002 0x00000020");
+        assert!(header.present.contains(Kind::AntennaSignal));
+        assert!(!header.present.contains(Kind::Rate));
+        assert_eq!(
+            header.present.iter().collect::<Vec<_>>(),
+            [
+                Kind::Flags,
+                Kind::Unknown(26),
+                Kind::RadiotapNamespace,
+                Kind::AntennaSignal
+            ]
+        );
+        assert_eq!(
+            header.present_bits().collect::<Vec<_>>(),
+            [
+                (0, 1, Some(Kind::Flags)),
+                (0, 26, None),
+                (0, 29, Some(Kind::RadiotapNamespace)),
+                (1, 5, Some(Kind::AntennaSignal))
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_lenient() {
+        // The VHT field is cut short by the end of the capture
+        let frame = [0, 0, 14, 0, 0x24, 0, 0x20, 0, 0x0c, 0xd6, 0, 0, 0, 0];
+
+        let (radiotap, errors, rest) = Radiotap::parse_lenient(&frame).unwrap();
+        assert_eq!(radiotap.rate.unwrap().as_mbps(), 6.0);
+        assert_eq!(radiotap.antenna_signal.unwrap().value, -42);
+        assert!(rest.is_empty());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, None);
+        assert_eq!(errors[0].error.category(), ErrorCategory::Skip);
+        assert!(Radiotap::parse(&frame).is_err());
+    }
+
+    #[test]
+    fn parse_strict() {
+        let frame = [0, 0, 11, 0, 0x60, 0, 0, 0, 0xd6, 0xa0, 0xff];
+
+        assert!(Radiotap::parse(&frame).is_ok());
+        match Radiotap::parse_strict(&frame).unwrap_err() {
+            Error::Violations(violations) => assert_eq!(violations.len(), 1),
+            e => panic!("Error not Violations: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn parser_strict_options() {
+        let frame = [1, 0, 9, 0, 4, 0, 0, 0, 12];
+        let strict = RadiotapParser::builder().strict(true);
+        assert!(matches!(
+            strict.clone().done().parse(&frame),
+            Err(Error::UnsupportedVersion)
+        ));
+        let (radiotap, _, _) = strict
+            .clone()
+            .any_version(true)
+            .done()
+            .parse(&frame)
+            .unwrap();
+        assert_eq!(radiotap.rate.unwrap().as_mbps(), 6.0);
+
+        // The limits apply before the trailing byte is reported
+        let frame = [0, 0, 11, 0, 0x60, 0, 0, 0, 0xd6, 0xa0, 0xff];
+        let limits = Limits {
+            fields: 1,
+            ..Limits::default()
+        };
+        assert!(matches!(
+            strict.limits(limits).done().parse(&frame),
+            Err(Error::LimitExceeded("fields"))
+        ));
+    }
+
+    #[test]
+    fn parser_duplicates() {
+        // The L-SIG field is repeated as a TLV record
+        let frame = [
+            0, 0, 20, 0, 0, 0, 0, 0x18, 2, 0, 0x20, 0, 27, 0, 4, 0, 2, 0, 0x40, 0,
+        ];
+
+        let parse = |duplicates| {
+            let parser = RadiotapParser::builder().duplicates(duplicates).done();
+            parser.parse(&frame).map(|(radiotap, _, _)| radiotap.l_sig)
+        };
+        assert_eq!(
+            parse(Duplicates::KeepFirst).unwrap().unwrap().length,
+            Some(2)
+        );
+        assert_eq!(
+            parse(Duplicates::KeepLast).unwrap().unwrap().length,
+            Some(4)
+        );
+        match parse(Duplicates::Reject).unwrap_err() {
+            Error::Violations(violations) => {
+                assert_eq!(violations, [Violation::DuplicateField { kind: Kind::LSig }])
+            }
+            e => panic!("Error not Violations: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn typed() {
+        let frame = [
+            0, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+
+        let iterator = RadiotapIterator::from_bytes(&frame).unwrap();
+        let fields: Vec<_> = iterator.typed().map(|field| field.unwrap()).collect();
+        assert_eq!(
+            fields[0],
+            TypedField::Value(Value::Flags(from_bytes(&[16]).unwrap()))
+        );
+        assert_eq!(
+            fields[1],
+            TypedField::Value(Value::Rate(Rate::from_mbps(1.0).unwrap()))
+        );
+        match fields[6] {
+            TypedField::VendorNamespace(vns, data) => {
+                assert_eq!(vns.oui, Oui([255, 255, 255]));
+                assert_eq!(data, &[222, 173]);
+            }
+            ref field => panic!("Field not VendorNamespace: {:?}", field),
+        }
+        assert_eq!(fields[7], TypedField::RadiotapNamespace);
+    }
+
+    #[test]
+    fn limits() {
+        let frame = [
+            0, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+
+        let parse = |limits| {
+            RadiotapParser::builder()
+                .limits(limits)
+                .done()
+                .parse(&frame)
+        };
+        assert!(parse(Limits::default()).is_ok());
+        for (limits, limit) in [
+            (
+                Limits {
+                    present_words: 3,
+                    ..Default::default()
+                },
+                "present words",
+            ),
+            (
+                Limits {
+                    vendor_length: 1,
+                    ..Default::default()
+                },
+                "vendor length",
+            ),
+            (
+                Limits {
+                    fields: 4,
+                    ..Default::default()
+                },
+                "fields",
+            ),
+        ] {
+            match parse(limits).unwrap_err() {
+                Error::LimitExceeded(l) => assert_eq!(l, limit),
+                e => panic!("Error not LimitExceeded: {:?}", e),
+            }
+        }
+    }
+
+    #[test]
+    fn size_hint() {
+        let frame = [
+            0, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+        let iterator = RadiotapIterator::from_bytes(&frame).unwrap();
+        let mut fields = (&iterator).into_iter();
+        assert_eq!(iterator.header.present.iter().len(), 9);
+        assert_eq!(fields.size_hint(), (1, Some(9)));
+        fields.next();
+        assert_eq!(fields.size_hint(), (1, Some(8)));
+        assert_eq!(fields.by_ref().count(), 8);
+        assert_eq!(fields.size_hint(), (0, Some(0)));
+        assert!(fields.next().is_none());
+
+        // The L-SIG field as a TLV record
+        let frame = [0, 0, 16, 0, 0, 0, 0, 0x10, 27, 0, 4, 0, 0x0b, 0, 0, 0];
+        let iterator = RadiotapIterator::from_bytes(&frame).unwrap();
+        let fields = iterator.into_iter();
+        assert_eq!(fields.size_hint(), (0, Some(3)));
+        assert_eq!(fields.count(), 1);
+    }
+
+    #[test]
+    fn unparse_buffered() {
+        struct Writes(Vec<usize>);
+
+        impl Write for Writes {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.push(buf.len());
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let radiotap = RadiotapBuilder::new()
+            .flags(from_bytes(&[0x10]).unwrap())
+            .channel(from_bytes(&[0x6c, 0x09, 0xa0, 0]).unwrap())
+            .done();
+        let mut writes = Writes(Vec::new());
+        assert_eq!(radiotap.unparse_buffered(&mut writes).unwrap(), 14);
+        assert_eq!(writes.0, [14]);
+        assert_eq!(radiotap.to_bytes().unwrap().capacity(), 14);
+    }
+
+    #[test]
+    fn unparse_into() {
+        let radiotap = RadiotapBuilder::new()
+            .flags(from_bytes(&[0x10]).unwrap())
+            .channel(from_bytes(&[0x6c, 0x09, 0xa0, 0]).unwrap())
+            .done();
+        let mut buf = [0xff; 14];
+        assert_eq!(radiotap.unparse_into(&mut buf).unwrap(), 14);
+        assert_eq!(&buf[..], &radiotap.to_bytes().unwrap()[..]);
+
+        let mut buf = [0xff; 13];
+        match radiotap.unparse_into(&mut buf) {
+            Err(Error::ParseError(e)) => assert_eq!(e.kind(), io::ErrorKind::WriteZero),
+            result => panic!("unexpected result {:?}", result),
+        }
+        assert_eq!(buf, [0xff; 13]);
+    }
+
+    #[test]
+    fn const_metadata() {
+        const BIT: u8 = Kind::LSig.bit();
+        const SIZE: usize = Kind::HEMU.size() + Kind::XChannel.align() as usize;
+        assert_eq!((BIT, SIZE), (27, 16));
+        assert_eq!(Radiotap::MAX_FIXED_HEADER_LEN, 126);
+        assert_eq!(Header::MAX_SIZE, 68);
+
+        let mut buf = [0; Radiotap::MAX_FIXED_HEADER_LEN];
+        let radiotap = RadiotapBuilder::new()
+            .tsft(from_bytes(&[0; 8]).unwrap())
+            .l_sig(from_bytes(&[0; 4]).unwrap())
+            .done();
+        assert_eq!(radiotap.unparse_into(&mut buf).unwrap(), 20);
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary_round_trip() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        // A simple deterministic generator for the raw data
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut data = vec![0; 4096];
+        for _ in 0..200 {
+            for byte in data.iter_mut() {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                *byte = state as u8;
+            }
+            let radiotap = Radiotap::arbitrary(&mut Unstructured::new(&data)).unwrap();
+            // Not every generated value can be written, such as invalid
+            // bandwidths, and values like VHT users without spatial streams
+            // are dropped when parsed, so only the parsed capture must round
+            // trip
+            if let Ok(bytes) = radiotap.to_bytes() {
+                let parsed = Radiotap::from_bytes(&bytes).unwrap();
+                let bytes = parsed.to_bytes().unwrap();
+                assert_eq!(Radiotap::from_bytes(&bytes).unwrap(), parsed);
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        let json = serde_json::to_string(&radiotap).unwrap();
+        assert_eq!(serde_json::from_str::<Radiotap>(&json).unwrap(), radiotap);
+
+        let present: PresentFlags = serde_json::from_str("[2147483648, 6]").unwrap();
+        assert_eq!(present.words(), [0x8000_0000, 6]);
+        assert!(serde_json::from_str::<PresentFlags>(&format!("{:?}", [0; 17])).is_err());
+    }
+
+    #[test]
+    fn get_one() {
+        let frame = [
+            0, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+
+        assert_eq!(Radiotap::get_one(&frame).unwrap(), radiotap.channel);
+        assert_eq!(Radiotap::get_one(&frame).unwrap(), radiotap.antenna_signal);
+        assert_eq!(Radiotap::get_one::<TSFT>(&frame).unwrap(), None);
+
+        // The L-SIG field as a TLV record
+        let frame = [0, 0, 16, 0, 0, 0, 0, 0x10, 27, 0, 4, 0, 0x0b, 0, 0, 0];
+        assert!(Radiotap::get_one::<LSig>(&frame).unwrap().is_some());
+    }
+
+    #[test]
+    fn parse_into() {
+        let frame = [
+            0, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+
+        let mut radiotap = Radiotap::default();
+        radiotap.parse_into(&frame).unwrap();
+        assert_eq!(radiotap, Radiotap::from_bytes(&frame).unwrap());
+
+        let capacity = radiotap.vendor_namespaces.capacity();
+        let rest = radiotap
+            .parse_into(&[0, 0, 9, 0, 4, 0, 0, 0, 12, 1])
+            .unwrap();
+        assert_eq!(rest, [1]);
+        assert_eq!(radiotap.rate.unwrap().as_mbps(), 6.0);
+        assert_eq!(radiotap.flags, None);
+        assert!(radiotap.vendor_namespaces.is_empty());
+        assert!(radiotap.namespaces.is_empty());
+        assert_eq!(radiotap.vendor_namespaces.capacity(), capacity);
+    }
+
+    #[test]
+    fn he() {
+        let frame = [
+            0, 0, 20, 0, 0, 0, 128, 0, 0xa6, 0x46, 0x86, 0x00, 0x2a, 0xa7, 0x05, 0x00, 0x22, 0x02,
+            0x02, 0x80,
+        ];
+
+        let he = Radiotap::from_bytes(&frame).unwrap().he.unwrap();
+        assert_eq!(he.format, Some(HEFormat::MU));
+        assert_eq!(he.bss_color, Some(42));
+        assert_eq!(he.uplink, None);
+        assert_eq!(he.index, Some(7));
+        assert_eq!(he.fec, Some(FEC::LDPC));
+        assert_eq!(he.stbc, Some(true));
+        assert_eq!(he.spatial_reuse, [Some(5), None, None, None]);
+        assert_eq!(he.sta_id, None);
+        assert_eq!(
+            he.bw,
+            Some(HEBandwidth::Bandwidth(Bandwidth::new(4).unwrap()))
+        );
+        assert_eq!(he.gi, Some(HEGuardInterval::GI3200));
+        assert_eq!(he.ltf_size, None);
+        assert_eq!(he.ltf_symbols, Some(4));
+        assert_eq!(he.nsts, Some(2));
+        assert_eq!(he.midamble_periodicity, Some(20));
+        assert_eq!(he.nss(), Some(1));
+        assert_eq!(he.datarate(), Some(306.3));
+
+        let radiotap = RadiotapBuilder::new().he(he).done();
+        let mut bytes = Vec::new();
+        radiotap.unparse(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 20);
+        assert_eq!(Radiotap::from_bytes(&bytes).unwrap().he, Some(he));
+    }
+
+    #[test]
+    fn chains() {
+        let frame = [
+            0, 0, 23, 0, 0x20, 0, 0, 0xa0, 0x20, 0x08, 0, 0xa0, 0x60, 0x08, 0, 0, 0xd0, 0xd2, 0,
+            0xce, 0xa0, 1, 0,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(radiotap.antenna_signal.unwrap().value, -48);
+        assert_eq!(radiotap.antenna_signal.unwrap().dbm(), Dbm(-48.0));
+        let iterator = RadiotapIterator::from_bytes(&frame).unwrap();
+        let chains: Vec<_> = iterator.chains().map(Result::unwrap).collect();
+        assert_eq!(chains, radiotap.chains().collect::<Vec<_>>());
+        assert_eq!(
+            radiotap.chains().collect::<Vec<_>>(),
+            [
+                AntennaChain {
+                    antenna: Some(0),
+                    signal: Some(-46),
+                    noise: None,
+                },
+                AntennaChain {
+                    antenna: Some(1),
+                    signal: Some(-50),
+                    noise: Some(-96),
+                },
+            ]
+        );
+        assert_eq!(radiotap.snr(), Some(Db(46.0)));
+
+        // The trailing padding of the capture is not written back
+        let bytes = radiotap.to_bytes().unwrap();
+        assert_eq!(bytes[4..], frame[4..22]);
+        let parsed = Radiotap::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed.namespaces, radiotap.namespaces);
+        assert!(parsed.chains().eq(radiotap.chains()));
+
+        let mut radiotap = radiotap;
+        radiotap.namespaces.truncate(1);
+        assert_eq!(radiotap.chains().count(), 1);
+    }
+
+    #[test]
+    fn members() {
+        fn kinds<T: RadiotapField>(radiotap: &Radiotap, kinds: &mut Vec<Kind>) {
+            if radiotap.get::<T>().is_some() {
+                kinds.push(T::KIND);
+            }
+        }
+
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        let mut radiotap = Radiotap::from_bytes(&frame).unwrap();
+        let mut present = Vec::new();
+        kinds::<TSFT>(&radiotap, &mut present);
+        kinds::<VHT>(&radiotap, &mut present);
+        kinds::<HE>(&radiotap, &mut present);
+        assert_eq!(present, [Kind::TSFT, Kind::VHT]);
+
+        let tsft = TSFT { value: 1 };
+        let previous = radiotap.set(tsft);
+        assert_eq!(previous, Some(TSFT { value: 0x9a9b_1fb9 }));
+        assert_eq!(radiotap.tsft, Some(tsft));
+    }
+
+    #[test]
+    fn present() {
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        let mut radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert!(radiotap.contains(Kind::VHT));
+
+        radiotap.vht = None;
+        assert!(!radiotap.contains(Kind::VHT));
+        assert!(radiotap.present().all(|kind| kind != Kind::VHT));
+        assert_eq!(radiotap.present().next(), Some(Kind::TSFT));
+
+        let kinds: Vec<_> = radiotap
+            .clone()
+            .into_iter()
+            .map(|value| value.kind())
+            .collect();
+        assert_eq!(kinds, radiotap.present().collect::<Vec<_>>());
+        assert_eq!(
+            radiotap.into_iter().next(),
+            Some(Value::TSFT(TSFT { value: 0x9a9b_1fb9 }))
+        );
+    }
+
+    #[test]
+    fn kinds() {
+        for bit in 0..32 {
+            if let Some(kind) = Kind::from_bit(bit) {
+                assert_eq!(kind.bit(), bit);
+            }
+        }
+        for &kind in Kind::all() {
+            match kind.namespace() {
+                Namespace::Radiotap => assert_eq!(Kind::from_bit(kind.bit()), Some(kind)),
+                namespace => assert_eq!(namespace, Namespace::Tlv),
+            }
+        }
+        assert_eq!(Kind::VendorNamespace(None).namespace(), Namespace::Vendor);
+        assert_eq!(Kind::TLV(Some(5)).namespace(), Namespace::Radiotap);
+    }
+
+    #[test]
+    fn band() {
+        let mut radiotap = Radiotap {
+            channel: Some(Channel::from_bytes(&[0x85, 0x09, 0x80, 0]).unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(radiotap.band(), Some(Band::Band2GHz));
+
+        // An unknown frequency falls back to the flags
+        radiotap.channel = Some(Channel::from_bytes(&[0, 0, 0, 0x01]).unwrap());
+        assert_eq!(radiotap.band(), Some(Band::Band5GHz));
+        radiotap.channel = Some(Channel::from_bytes(&[0, 0, 0, 0]).unwrap());
+        assert_eq!(radiotap.band(), None);
+        assert_eq!(Radiotap::default().band(), None);
+    }
+
+    #[test]
+    fn snr() {
+        let mut radiotap = Radiotap {
+            antenna_signal: Some(AntennaSignal { value: -48 }),
+            antenna_noise: Some(AntennaNoise { value: -90 }),
+            antenna_signal_db: Some(AntennaSignalDb { value: 60 }),
+            antenna_noise_db: Some(AntennaNoiseDb { value: 10 }),
+            ..Default::default()
+        };
+        assert_eq!(radiotap.snr(), Some(Db(42.0)));
+        radiotap.antenna_noise = None;
+        assert_eq!(radiotap.snr(), Some(Db(50.0)));
+        radiotap.antenna_noise_db = None;
+        assert_eq!(radiotap.snr(), None);
+    }
+
+    #[test]
+    fn unknown_unsized_field() {
+        let frame = [0, 0, 14, 0, 2, 0, 0, 128, 1, 0, 0, 0, 16, 1];
+
+        let mut iterator = RadiotapIterator::from_bytes(&frame).unwrap().into_iter();
+        assert_eq!(iterator.next().unwrap().unwrap().0, Kind::Flags);
+        match iterator.next().unwrap().unwrap_err() {
+            Error::UnknownFieldBlocksParsing { bit: 32 } => {}
+            e => panic!("Error not UnknownFieldBlocksParsing: {:?}", e),
+        };
+        assert!(iterator.next().is_none());
+
+        assert!(Radiotap::from_bytes(&frame).unwrap().flags.unwrap().fcs);
+
+        let (radiotap, errors, _) = Radiotap::parse_lenient(&frame).unwrap();
+        assert!(radiotap.flags.unwrap().fcs);
+        assert_eq!(errors[0].kind, Some(Kind::Unknown(32)));
+    }
+
+    #[test]
+    fn vht_unknown_stbc() {
+        // The STBC bit is set, but not known
+        let frame = [
+            0, 0, 20, 0, 0, 0, 0x20, 0, 0, 0, 0x01, 0, 0x71, 0, 0, 0, 0, 0, 0, 0,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        let vht = radiotap.vht.unwrap();
+        assert_eq!(vht.stbc, None);
+        assert_eq!(vht.users[0].unwrap().nsts, 1);
+
+        let parsed = Radiotap::from_bytes(&radiotap.to_bytes().unwrap()).unwrap();
+        assert_eq!(parsed.vht, radiotap.vht);
+    }
+
+    #[test]
+    fn length_shorter_than_header() {
+        // The length ends within the second present word
+        let frame = [0, 0, 8, 0, 0, 0, 0, 0x80, 0, 0, 0, 0];
+
+        assert!(matches!(
+            RadiotapIterator::from_bytes(&frame),
+            Err(Error::InvalidLength)
+        ));
+        assert!(matches!(
+            validate::validate(&frame),
+            Err(Error::InvalidLength)
+        ));
+        assert!(matches!(
+            Radiotap::parse_strict(&frame),
+            Err(Error::InvalidLength)
+        ));
+    }
+
+    #[test]
+    fn layout() {
+        let frame = [
+            0, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+
+        let layout = RadiotapIterator::from_bytes(&frame)
+            .unwrap()
+            .layout()
+            .unwrap();
+        assert_eq!(layout.present_words, 4);
+        assert_eq!(layout.fields.len(), 8);
+        assert_eq!(layout.fields[6].offset, 30);
+        assert_eq!(layout.fields[6].size, 8);
+        assert_eq!(layout.fields[7].kind, Kind::Rate);
+        assert_eq!(layout.padding, 0);
+        assert_eq!(layout.trailing, 0);
+
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+
+        let layout = RadiotapIterator::from_bytes(&frame)
+            .unwrap()
+            .layout()
+            .unwrap();
+        assert_eq!(layout.present_words, 1);
+        assert_eq!(
+            layout.fields[6],
+            FieldLayout {
+                kind: Kind::XChannel,
+                offset: 28,
+                size: 8,
+                padding: 3,
+            }
+        );
+        assert_eq!(layout.padding, 4);
+        assert_eq!(layout.trailing, 0);
+    }
+
+    #[test]
+    fn parse_at() {
+        let frame = [
+            0, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+
+        let layout = RadiotapIterator::from_bytes(&frame)
+            .unwrap()
+            .layout()
+            .unwrap();
+        for field in &layout.fields {
+            let (value, end) = field::parse_at(field.kind, &frame, field.offset).unwrap();
+            assert_eq!(value.kind(), field.kind);
+            assert_eq!(end, field.offset + field.size);
+        }
+
+        let (value, end) = field::parse_at(Kind::Channel, &frame, 21).unwrap();
+        assert_eq!(end, 26);
+        match value {
+            Value::Channel(channel) => assert_eq!(channel.freq, 2462),
+            v => panic!("Value not Channel: {:?}", v),
+        }
+
+        match field::parse_at(Kind::XChannel, &frame, 36).unwrap_err() {
+            Error::IncompleteError => {}
+            e => panic!("Error not IncompleteError: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn fingerprint() {
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        let mut other = radiotap.clone();
+        other.tsft = Some(TSFT { value: 0 });
+        assert_eq!(
+            radiotap.fingerprint().unwrap(),
+            other.fingerprint().unwrap()
+        );
+        assert_ne!(
+            radiotap.fingerprint_excluding(&[]).unwrap(),
+            other.fingerprint_excluding(&[]).unwrap()
+        );
+
+        other.antenna_signal = Some(AntennaSignal { value: -50 });
+        assert_ne!(
+            radiotap.fingerprint().unwrap(),
+            other.fingerprint().unwrap()
+        );
+        assert_eq!(
+            Radiotap::default().fingerprint().unwrap(),
+            0xcbf2_9ce4_8422_2325
+        );
+
+        // Golden values, these must not change between releases
+        assert_eq!(radiotap.fingerprint().unwrap(), 0xe73d_0650_4b53_b744);
+        assert_eq!(
+            radiotap.fingerprint_excluding(&[]).unwrap(),
+            0xd6a3_4ce0_18c2_81f5
+        );
+        let tlvs = [
+            0, 0, 32, 0, 0, 0, 0, 16, 33, 0, 12, 0, 0xde, 0x00, 0x5e, 0x55, 0xa5, 0xa5, 0xa5, 0xa5,
+            0xff, 0xff, 0xff, 0xff, 5, 0, 1, 0, 214, 0, 0, 0,
+        ];
+        let tlvs = Radiotap::from_bytes(&tlvs).unwrap();
+        assert_eq!(tlvs.fingerprint().unwrap(), 0xca1e_7dcb_c6e1_63b0);
+
+        // A value that cannot be written is not hashed as a truncated prefix
+        let mut vht = radiotap.vht.unwrap();
+        vht.bw = Some(Bandwidth {
+            bandwidth: 80,
+            sideband: Some(40),
+            sideband_index: Some(2),
+        });
+        other.vht = Some(vht);
+        assert!(other.fingerprint().is_err());
+    }
+
+    #[test]
+    fn peek_length() {
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0, 0x88, 0x41,
+        ];
+
+        assert_eq!(Radiotap::peek_length(&frame).unwrap(), 56);
+        assert!(matches!(
+            Radiotap::peek_length(&frame[..20]),
+            Err(Error::InvalidLength)
+        ));
+        assert!(matches!(
+            Radiotap::peek_length(&frame[..4]),
+            Err(Error::IncompleteError)
+        ));
+        assert!(matches!(
+            Radiotap::peek_length(&[1, 0, 8, 0, 0, 0, 0, 0]),
+            Err(Error::UnsupportedVersion)
+        ));
+        assert!(matches!(
+            Radiotap::peek_length(&[0, 0, 4, 0, 0, 0, 0, 0]),
+            Err(Error::InvalidLength)
+        ));
+    }
+
+    #[test]
+    fn he_mu() {
+        let frame = [
+            0, 0, 20, 0, 0, 0, 0, 1, 0x73, 0x91, 0x35, 0x00, 192, 0, 0, 0, 113, 0, 0, 0,
+        ];
+
+        let he_mu = Radiotap::from_bytes(&frame).unwrap().he_mu.unwrap();
+        assert_eq!(he_mu.sigb_mcs, Some(3));
+        assert_eq!(he_mu.sigb_dcm, Some(true));
+        assert_eq!(he_mu.sigb_compression, None);
+        assert_eq!(he_mu.sigb_symbols_or_users, Some(4));
+        assert_eq!(he_mu.bw, Some(Bandwidth::new(1).unwrap()));
+        assert_eq!(he_mu.preamble_puncturing, None);
+        assert_eq!(he_mu.ru_channel1, Some([192, 0, 0, 0]));
+        assert_eq!(he_mu.ru_channel2, None);
+        assert_eq!(he_mu.center_ru_channel1, Some(false));
+        assert_eq!(he_mu.center_ru_channel2, None);
+
+        let mut bytes = Vec::new();
+        RadiotapBuilder::new()
+            .he_mu(he_mu)
+            .done()
+            .unparse(&mut bytes)
+            .unwrap();
+        // The RU allocations of content channel 2 are not known
+        assert_eq!(bytes[..16], frame[..16]);
+        assert_eq!(Radiotap::from_bytes(&bytes).unwrap().he_mu, Some(he_mu));
+
+        let allocations: Vec<_> = he_mu.ru_allocations().map(|a| a.unwrap()).collect();
+        assert_eq!(allocations.len(), 1);
+        assert_eq!(allocations[0].0, 1);
+
+        let users = he_mu.users().unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(
+            users[0],
+            HEMUUser {
+                channel: 1,
+                position: 0,
+                size: RUSize::RU242,
+                mu_mimo: false,
+            }
+        );
+
+        let he_mu = HEMU {
+            bw: Some(Bandwidth::new(4).unwrap()),
+            ru_channel1: Some([96, 113, 0, 0]),
+            ru_channel2: Some([200, 114, 0, 0]),
+            center_ru_channel1: Some(true),
+            ..Default::default()
+        };
+        let users = he_mu.users().unwrap();
+        let summary: Vec<_> = users
+            .iter()
+            .map(|user| (user.channel, user.position, user.size, user.mu_mimo))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (1, 0, RUSize::RU106, false),
+                (1, 1, RUSize::RU106, false),
+                (2, 0, RUSize::RU484, false),
+                (1, 2, RUSize::RU26, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn he_mu_other_user() {
+        let frame = [0, 0, 14, 0, 0, 0, 0, 2, 0x2a, 0x50, 0x27, 0x00, 3, 0xef];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        let user = radiotap.he_mu_other_user.unwrap();
+        assert_eq!(user.position, Some(3));
+        assert_eq!(user.sta_id, Some(42));
+        assert_eq!(user.nsts, Some(3));
+        assert_eq!(user.beamformed, Some(true));
+        assert_eq!(user.spatial_config, None);
+        assert_eq!(user.index, Some(7));
+        assert_eq!(user.dcm, Some(false));
+        assert_eq!(user.fec, Some(FEC::LDPC));
+
+        let mut bytes = Vec::new();
+        radiotap.unparse(&mut bytes).unwrap();
+        assert_eq!(bytes, frame);
+    }
+
+    #[test]
+    fn l_sig() {
+        let frame = [0, 0, 12, 0, 0, 0, 0, 8, 3, 0, 0xdd, 0x05];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        let l_sig = radiotap.l_sig.unwrap();
+        assert_eq!(l_sig.length, Some(93));
+        assert_eq!(l_sig.datarate(), Some(36.0));
+
+        let mut bytes = Vec::new();
+        radiotap.unparse(&mut bytes).unwrap();
+        assert_eq!(bytes, frame);
+    }
+
+    #[test]
+    fn tlv() {
+        let frame = [
+            0, 0, 28, 0, 2, 0, 0, 16, 16, 0, 0, 0, 5, 0, 1, 0, 214, 0, 0, 0, 99, 0, 3, 0, 1, 2, 3,
+            0,
+        ];
+
+        let kinds: Vec<_> = RadiotapIterator::from_bytes(&frame)
+            .unwrap()
+            .into_iter()
+            .map(|field| field.unwrap())
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                (Kind::Flags, &frame[8..9]),
+                (Kind::TLV(Some(5)), &frame[16..17]),
+                (Kind::TLV(Some(99)), &frame[24..27]),
+            ]
+        );
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert!(radiotap.flags.unwrap().fcs);
+        assert_eq!(radiotap.antenna_signal.unwrap().value, -42);
+
+        let layout = RadiotapIterator::from_bytes(&frame)
+            .unwrap()
+            .layout()
+            .unwrap();
+        assert_eq!(layout.fields[1].offset, 12);
+        assert_eq!(layout.fields[1].size, 5);
+        assert_eq!(layout.fields[2].padding, 3);
+        assert_eq!(layout.trailing, 1);
+    }
+
+    #[test]
+    fn u_sig() {
+        let frame = [
+            0, 0, 32, 0, 0, 0, 0, 16, 33, 0, 12, 0, 0xde, 0x00, 0x5e, 0x55, 0xa5, 0xa5, 0xa5, 0xa5,
+            0xff, 0xff, 0xff, 0xff, 5, 0, 1, 0, 214, 0, 0, 0,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        let u_sig = radiotap.u_sig.unwrap();
+        assert_eq!(u_sig.phy_version, None);
+        assert_eq!(u_sig.bw, Some(EHTBandwidth::MHz320_1));
+        assert_eq!(u_sig.uplink, Some(true));
+        assert_eq!(u_sig.bss_color, Some(43));
+        assert_eq!(u_sig.txop, Some(42));
+        assert!(!u_sig.bad_crc);
+        assert_eq!(u_sig.validate_ok, Some(true));
+        assert_eq!(u_sig.value, 0xa5a5_a5a5);
+        assert_eq!(u_sig.mask, 0xffff_ffff);
+        assert_eq!(radiotap.antenna_signal.unwrap().value, -42);
+        assert_eq!(Radiotap::get_one::<USig>(&frame).unwrap(), Some(u_sig));
+        assert_eq!(radiotap.into_iter().last(), Some(Value::USig(u_sig)));
+    }
+
+    #[test]
+    fn raw() {
+        let frame = [
+            0, 0, 32, 0, 0, 0, 0, 16, 33, 0, 12, 0, 0xde, 0x00, 0x5e, 0x55, 0xa5, 0xa5, 0xa5, 0xa5,
+            0xff, 0xff, 0xff, 0xff, 5, 0, 1, 0, 214, 0, 0, 0,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert!(radiotap.raw.is_empty());
+
+        let parser = RadiotapParser::builder().raw(true).done();
+        let (mut radiotap, _, _) = parser.parse(&frame).unwrap();
+        assert_eq!(radiotap.get_raw(USig::KIND), Some(&frame[12..24]));
+        assert_eq!(radiotap.get_raw(Kind::AntennaSignal), Some(&[214][..]));
+        assert_eq!(radiotap.get_raw(Kind::TLV(None)), None);
+
+        parser
+            .parse_into(&mut radiotap, &[0, 0, 9, 0, 4, 0, 0, 0, 12])
+            .unwrap();
+        assert_eq!(
+            radiotap.raw,
+            [RawField {
+                kind: Kind::Rate,
+                data: vec![12]
+            }]
+        );
+    }
+
+    #[test]
+    fn eht() {
+        let frame = [
+            0, 0, 56, 0, 0, 0, 0, 16, 34, 0, 44, 0, 22, 0, 194, 0, 152, 18, 0, 0, 163, 0, 89, 0,
+            100, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 32, 0, 0, 0, 0, 0, 0,
+            151, 42, 144, 2,
+        ];
+
+        let eht = Radiotap::from_bytes(&frame).unwrap().eht.unwrap();
+        assert_eq!(eht.spatial_reuse, Some(3));
+        assert_eq!(eht.gi, Some(HEGuardInterval::GI1600));
+        assert_eq!(eht.ltf_size, Some(LTFSize::X2));
+        assert_eq!(eht.ltf_symbols, Some(4));
+        assert_eq!(eht.ldpc_extra, None);
+        assert_eq!(eht.ru_size, Some(3));
+        assert_eq!(eht.ru_index, Some(5));
+        assert_eq!(eht.nss, Some(2));
+        assert_eq!(eht.ru_allocations[0][0], Some(200));
+        assert_eq!(eht.ru_allocations[0][1], None);
+        assert_eq!(eht.ru_allocations[1][0], Some(100));
+        assert_eq!(
+            eht.users,
+            [EHTUser {
+                sta_id: Some(42),
+                index: Some(9),
+                fec: Some(FEC::BCC),
+                nss: Some(2),
+                beamformed: None,
+                spatial_config: None,
+                data_captured: true,
+            }]
+        );
+
+        let mut bytes = Vec::new();
+        Radiotap::from_bytes(&frame)
+            .unwrap()
+            .unparse(&mut bytes)
+            .unwrap();
+        assert_eq!(bytes, frame);
+    }
+
+    #[test]
+    fn vendor_namespaces() {
+        let frame = [
+            0, 0, 22, 0, 2, 0, 0, 0xc0, 1, 0, 0, 0, 16, 0, 0xf6, 0x54, 0x25, 1, 2, 0, 42, 0,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(
+            radiotap.vendor_namespaces,
+            [VendorData {
+                oui: Oui([0xf6, 0x54, 0x25]),
+                sub_namespace: 1,
+                present: vec![1],
+                data: vec![42, 0],
+            }]
+        );
+
+        let mut bytes = Vec::new();
+        radiotap.unparse(&mut bytes).unwrap();
+        assert_eq!(bytes, frame);
+
+        let frame = [
+            0, 0, 24, 0, 0, 0, 0, 16, 30, 0, 11, 0, 0x00, 0x03, 0x7f, 2, 1, 0, 0, 0, 7, 8, 9, 0,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(radiotap.vendor_namespaces[0].sub_namespace, 2);
+        assert_eq!(radiotap.vendor_namespaces[0].data, [7, 8, 9]);
+    }
+
+    #[test]
+    fn multiple_vendor_namespaces() {
+        let frame = [
+            0, 0, 30, 0, 0, 0, 0, 0xc0, 0, 0, 0, 0xc0, 0, 0, 0, 0, 0xf6, 0x54, 0x25, 1, 2, 0, 42,
+            0, 0xf6, 0x54, 0x25, 2, 0, 0,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        let namespaces: Vec<_> = radiotap
+            .vendor_namespaces
+            .iter()
+            .map(|vendor| (vendor.sub_namespace, vendor.data.len()))
+            .collect();
+        assert_eq!(namespaces, [(1, 2), (2, 0)]);
+
+        let mut bytes = Vec::new();
+        radiotap.unparse(&mut bytes).unwrap();
+        assert_eq!(bytes, frame);
+
+        // The skip length of the second namespace is past the end
+        let mut frame = frame;
+        frame[28] = 1;
+        let mut iterator = RadiotapIterator::from_bytes(&frame).unwrap().into_iter();
+        assert!(iterator.next().unwrap().is_ok());
+        assert!(matches!(iterator.next(), Some(Err(Error::IncompleteError))));
+        assert!(iterator.next().is_none());
+        assert!(matches!(
+            Radiotap::from_bytes(&frame),
+            Err(Error::IncompleteError)
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "atheros")]
+    fn undecodable_vendor_namespace() {
+        // The Atheros RX status is too short to decode
+        let frame = [
+            0, 0, 30, 0, 0, 0, 0, 0xc0, 0, 0, 0, 0xc0, 0, 0, 0, 0, 0x00, 0x03, 0x7f, 0, 2, 0, 42,
+            0, 0xf6, 0x54, 0x25, 2, 0, 0,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert!(radiotap.vendor.is_empty());
+        let namespaces: Vec<_> = radiotap
+            .vendor_namespaces
+            .iter()
+            .map(|vendor| {
+                (
+                    vendor.sub_namespace,
+                    vendor.present.clone(),
+                    vendor.data.len(),
+                )
+            })
+            .collect();
+        assert_eq!(namespaces, [(0, vec![0], 2), (2, vec![0], 0)]);
+
+        let (radiotap, errors, _) = Radiotap::parse_lenient(&frame).unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(radiotap.vendor_namespaces.len(), 2);
+    }
+
+    #[test]
+    fn unparse_vendor_namespaces() {
+        let radiotap = RadiotapBuilder::new()
+            .rate(Rate::from_mbps(6.0).unwrap())
+            .vendor_namespace(Oui([0x00, 0x03, 0x7f]), 0, &[1, 2])
+            .vendor_namespace(Oui([0xf6, 0x54, 0x25]), 1, &[9])
+            .done();
+
+        let mut bytes = Vec::new();
+        radiotap.unparse(&mut bytes).unwrap();
+        assert_eq!(
+            bytes,
+            [
+                0, 0, 33, 0, 4, 0, 0, 0xc0, 0, 0, 0, 0xc0, 0, 0, 0, 0, 12, 0, 0x00, 0x03, 0x7f, 0,
+                2, 0, 1, 2, 0xf6, 0x54, 0x25, 1, 1, 0, 9
+            ]
+        );
+
+        let iterator = RadiotapIterator::from_bytes(&bytes).unwrap();
+        assert_eq!(iterator.header, radiotap.header);
+        let data: Vec<_> = iterator.into_iter().map(|field| field.unwrap().1).collect();
+        assert_eq!(data, [&[12][..], &[1, 2], &[9]]);
+
+        // With TLVs the vendor namespaces are TLV records too
+        let radiotap = RadiotapBuilder::new()
+            .u_sig(USig::default())
+            .vendor_namespace(Oui([0x00, 0x03, 0x7f]), 0, &[1, 2])
+            .done();
+        let mut bytes = Vec::new();
+        radiotap.unparse(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 40);
+        assert_eq!(bytes[4..8], [0, 0, 0, 16]);
+        assert_eq!(
+            bytes[24..],
+            [30, 0, 10, 0, 0x00, 0x03, 0x7f, 0, 0, 0, 0, 0, 1, 2, 0, 0]
+        );
+    }
+
+    #[test]
+    fn unparse_tlvs() {
+        let u_sig = USig {
+            bw: Some(EHTBandwidth::MHz160),
+            bss_color: Some(7),
+            ..Default::default()
+        };
+        let eht = EHT {
+            gi: Some(HEGuardInterval::GI800),
+            ltf_size: Some(LTFSize::X4),
+            ru_allocations: [[Some(1), None, None, None, None, None, None, None]; 2],
+            ..Default::default()
+        };
+        let radiotap = RadiotapBuilder::new()
+            .rate(Rate::from_mbps(6.0).unwrap())
+            .u_sig(u_sig)
+            .eht(eht.clone())
+            .done();
+        assert_eq!(radiotap.header.length, 72);
+
+        let mut bytes = Vec::new();
+        assert_eq!(radiotap.unparse(&mut bytes).unwrap(), 72);
+        assert_eq!(bytes[4..8], [4, 0, 0, 16]);
+        // The rate is followed by padding to the first TLV
+        assert_eq!(bytes[8..16], [12, 0, 0, 0, 33, 0, 12, 0]);
+        assert_eq!(bytes[28..32], [34, 0, 40, 0]);
+
+        let parsed = Radiotap::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed.header, radiotap.header);
+        assert_eq!(parsed.u_sig, Some(u_sig));
+        assert_eq!(parsed.eht, Some(eht));
+    }
+
+    #[test]
+    fn error_category() {
+        let frame = [0, 0, 9, 0, 2, 0, 0, 0];
+        let e = Radiotap::from_bytes(&frame).unwrap_err();
+        assert_eq!(e.category(), ErrorCategory::Resync);
+        assert!(e.is_recoverable());
+
+        let frame = [0, 0, 6, 0, 2, 0];
+        let e = Radiotap::from_bytes(&frame).unwrap_err();
+        assert_eq!(e.category(), ErrorCategory::Skip);
+
+        let e = Error::from(std::io::Error::from(std::io::ErrorKind::Other));
+        assert_eq!(e.category(), ErrorCategory::Abort);
+        assert!(!e.is_recoverable());
+    }
+
+    #[test]
+    fn unparse_round_trip() {
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        let mut bytes = Vec::new();
+        assert_eq!(radiotap.unparse(&mut bytes).unwrap(), frame.len());
+        // The reserved VHT flag is not retained
+        assert_eq!(bytes[..46], frame[..46]);
+        assert_eq!(bytes[47..], frame[47..]);
+        assert_eq!(Radiotap::from_bytes(&bytes).unwrap(), radiotap);
+    }
+
+    #[test]
+    fn unparse_builder() {
+        let radiotap = RadiotapBuilder::new()
+            .flags(Flags {
+                cfp: false,
+                preamble: true,
+                wep: false,
+                fragmentation: false,
+                fcs: true,
+                data_pad: false,
+                bad_fcs: false,
+                sgi: false,
+            })
+            .mcs(MCS {
+                bw: Some(Bandwidth::new(1).unwrap()),
+                index: Some(7),
+                gi: Some(GuardInterval::Short),
+                ness: Some(2),
+                ..Default::default()
+            })
+            .timestamp(Timestamp {
+                timestamp: 1234,
+                unit: TimeUnit::Microseconds,
+                position: SamplingPosition::EndMPDU,
+                accuracy: Some(3),
+            })
+            .done();
+
+        let mut bytes = Vec::new();
+        radiotap.unparse(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), radiotap.header.length);
+
+        let parsed = Radiotap::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed.header, radiotap.header);
+        assert_eq!(parsed.flags, radiotap.flags);
+        assert_eq!(parsed.timestamp, radiotap.timestamp);
+        let mcs = parsed.mcs.unwrap();
+        assert_eq!(mcs.ness, Some(2));
+        assert_eq!(mcs.datarate, Some(150.0));
+
+        // MCS 32 is not defined at 20 MHz, the rate is unknown
+        let mcs: MCS = from_bytes(&[0x07, 0x00, 32]).unwrap();
+        assert_eq!(mcs.index, Some(32));
+        assert_eq!(mcs.datarate, None);
+    }
+
+    #[test]
+    fn bad_version() {
+        let frame = [
+            1, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+
+        match Radiotap::from_bytes(&frame).unwrap_err() {
+            Error::UnsupportedVersion => {}
+            e => panic!("Error not UnsupportedVersion: {:?}", e),
+        };
+    }
+
+    #[test]
+    fn any_version() {
+        let frame = [
+            1, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+
+        let (radiotap, rest) = Radiotap::parse_any_version(&frame).unwrap();
+        assert_eq!(radiotap.header.version, 1);
+        assert_eq!(radiotap.rate.unwrap(), Rate::from_mbps(1.0).unwrap());
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn bad_header_length() {
+        let frame = [
+            0, 0, 40, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+
+        match Radiotap::from_bytes(&frame).unwrap_err() {
+            Error::InvalidLength => {}
+            e => panic!("Error not InvalidLength: {:?}", e),
+        };
+    }
+
+    #[test]
+    fn bad_actual_length() {
+        let frame = [
+            0, 0, 39, 0, 47, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+
+        match Radiotap::from_bytes(&frame).unwrap_err() {
+            Error::IncompleteError => {}
+            e => panic!("Error not IncompleteError: {:?}", e),
+        };
+    }
+
+    #[test]
+    fn bad_vendor() {
+        let frame = [
+            0, 0, 34, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255,
+        ];
+
+        match Radiotap::from_bytes(&frame).unwrap_err() {
+            Error::IncompleteError => {}
+            e => panic!("Error not IncompleteError: {:?}", e),
+        };
+    }
+
+    #[test]
+    fn linktype() {
+        // A plain 802.11 data frame, whose frame control looks like a header
+        let frame = [8, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+        let error = Radiotap::parse_with_linktype(LINKTYPE_IEEE802_11, &frame).unwrap_err();
+        assert!(matches!(error, Error::UnsupportedLinkType(105)));
+        assert_eq!(
+            error.to_string(),
+            "unsupported link type 105 (IEEE802_11, plain 802.11 frames without a radiotap \
+             header), expected IEEE802_11_RADIOTAP (127)"
+        );
+        assert!(RadiotapIterator::parse_with_linktype(LINKTYPE_IEEE802_11, &frame).is_err());
+
+        let capture = [0, 0, 9, 0, 4, 0, 0, 0, 12, 0x80, 0];
+        let (radiotap, rest) =
+            Radiotap::parse_with_linktype(LINKTYPE_IEEE802_11_RADIOTAP, &capture).unwrap();
+        assert_eq!(radiotap.rate.unwrap().as_mbps(), 6.0);
+        assert_eq!(rest, [0x80, 0]);
     }
 }
@@ -0,0 +1,80 @@
+//! Live capture of Radiotap frames from a monitor mode interface with
+//! libpcap.
+//!
+//! Enabled with the `live-capture` feature, which links against libpcap
+//! through the [pcap](https://docs.rs/pcap) crate. A
+//! [LiveCapture](struct.LiveCapture.html) checks that the capture has the
+//! `LINKTYPE_IEEE802_11_RADIOTAP` link type and yields the parsed Radiotap
+//! header and the 802.11 frame following it of each captured frame.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use radiotap::live::LiveCapture;
+//!
+//! for frame in LiveCapture::open("wlan0mon").unwrap().take(100) {
+//!     let (radiotap, payload) = frame.unwrap();
+//!     println!("{:?} {} bytes", radiotap.antenna_signal, payload.len());
+//! }
+//! ```
+
+use ::pcap::{Active, Capture, Linktype};
+
+use crate::{is_radiotap_linktype, Error, Radiotap, Result, LINKTYPE_IEEE802_11_RADIOTAP};
+
+/// A live capture of Radiotap frames, see the [module](index.html)
+/// documentation.
+pub struct LiveCapture {
+    capture: Capture<Active>,
+}
+
+impl LiveCapture {
+    /// Opens the given device in monitor mode and selects the Radiotap link
+    /// type.
+    pub fn open(device: &str) -> Result<LiveCapture> {
+        let mut capture = Capture::from_device(device)?
+            .rfmon(true)
+            .immediate_mode(true)
+            .open()?;
+        if !is_radiotap_linktype(capture.get_datalink().0 as u32) {
+            capture.set_datalink(Linktype(LINKTYPE_IEEE802_11_RADIOTAP as i32))?;
+        }
+        LiveCapture::from_capture(capture)
+    }
+
+    /// Wraps an already activated capture, returning an `UnsupportedLinkType`
+    /// error if it does not have the Radiotap link type.
+    pub fn from_capture(capture: Capture<Active>) -> Result<LiveCapture> {
+        let linktype = capture.get_datalink().0 as u32;
+        if !is_radiotap_linktype(linktype) {
+            return Err(Error::UnsupportedLinkType(linktype));
+        }
+        Ok(LiveCapture { capture })
+    }
+
+    /// Returns the underlying capture, for example to set a filter.
+    pub fn capture(&mut self) -> &mut Capture<Active> {
+        &mut self.capture
+    }
+}
+
+impl Iterator for LiveCapture {
+    type Item = Result<(Radiotap, Vec<u8>)>;
+
+    /// Waits for the next frame. A frame whose Radiotap header cannot be
+    /// parsed returns an error and capturing continues with the next one.
+    fn next(&mut self) -> Option<Result<(Radiotap, Vec<u8>)>> {
+        loop {
+            let packet = match self.capture.next_packet() {
+                Ok(packet) => packet,
+                Err(::pcap::Error::TimeoutExpired) => continue,
+                Err(::pcap::Error::NoMorePackets) => return None,
+                Err(e) => return Some(Err(e.into())),
+            };
+            return Some(Radiotap::from_bytes(packet.data).map(|radiotap| {
+                let payload = packet.data[radiotap.header.length..].to_vec();
+                (radiotap, payload)
+            }));
+        }
+    }
+}
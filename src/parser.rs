@@ -0,0 +1,235 @@
+//! A configurable parser for Radiotap captures.
+
+use alloc::vec::Vec;
+
+use crate::{
+    field::MAX_PRESENT_WORDS, validate, Error, FieldError, Radiotap, RadiotapIterator, Result,
+};
+
+/// What to do when a field appears more than once in the first radiotap
+/// namespace, for example both as a present bit and as a TLV record.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Duplicates {
+    /// Keep the value of the first occurrence.
+    KeepFirst,
+    /// Keep the value of the last occurrence.
+    KeepLast,
+    /// Fail with a [DuplicateField](validate/enum.Violation.html#variant.DuplicateField)
+    /// violation.
+    Reject,
+}
+
+/// Caps on the work done for a single capture, to parse captures from
+/// untrusted sources. A capture exceeding a limit fails with
+/// [LimitExceeded](enum.Error.html#variant.LimitExceeded).
+///
+/// The default limits only reject what the format itself cannot express.
+///
+/// ```
+/// use radiotap::{Limits, RadiotapParser};
+///
+/// let parser = RadiotapParser::builder()
+///     .limits(Limits {
+///         present_words: 2,
+///         vendor_length: 64,
+///         fields: 32,
+///     })
+///     .done();
+/// assert!(parser.parse(&[0, 0, 9, 0, 4, 0, 0, 0, 12]).is_ok());
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Limits {
+    /// The maximum number of present words, at most
+    /// [MAX_PRESENT_WORDS](field/constant.MAX_PRESENT_WORDS.html).
+    pub present_words: usize,
+    /// The maximum length of the data of a vendor namespace.
+    pub vendor_length: usize,
+    /// The maximum number of fields, including namespace switches and TLV
+    /// records.
+    pub fields: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits {
+            present_words: MAX_PRESENT_WORDS,
+            vendor_length: usize::from(u16::MAX),
+            fields: usize::MAX,
+        }
+    }
+}
+
+/// Parses Radiotap captures with a set of options, configured with a
+/// [RadiotapParserBuilder](struct.RadiotapParserBuilder.html).
+///
+/// The default parser behaves like [Radiotap::parse](struct.Radiotap.html#method.parse).
+///
+/// ```
+/// use radiotap::{Duplicates, RadiotapParser};
+///
+/// let parser = RadiotapParser::builder()
+///     .lenient(true)
+///     .any_version(true)
+///     .duplicates(Duplicates::KeepFirst)
+///     .done();
+///
+/// let capture = [1, 0, 9, 0, 4, 0, 0, 0, 12, 0x80, 0];
+/// let (radiotap, errors, rest) = parser.parse(&capture).unwrap();
+/// assert_eq!(radiotap.header.version, 1);
+/// assert_eq!(radiotap.rate.unwrap().as_mbps(), 6.0);
+/// assert!(errors.is_empty());
+/// assert_eq!(rest, &[0x80, 0]);
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct RadiotapParser {
+    pub(crate) lenient: bool,
+    pub(crate) strict: bool,
+    pub(crate) any_version: bool,
+    pub(crate) duplicates: Duplicates,
+    pub(crate) vendor: bool,
+    pub(crate) raw: bool,
+    pub(crate) limits: Limits,
+}
+
+impl Default for RadiotapParser {
+    fn default() -> RadiotapParser {
+        RadiotapParser {
+            lenient: false,
+            strict: false,
+            any_version: false,
+            duplicates: Duplicates::KeepLast,
+            vendor: true,
+            raw: false,
+            limits: Limits::default(),
+        }
+    }
+}
+
+impl RadiotapParser {
+    /// Returns a parser with the default options.
+    pub fn new() -> RadiotapParser {
+        RadiotapParser::default()
+    }
+
+    /// Returns a builder starting from the default options.
+    pub fn builder() -> RadiotapParserBuilder {
+        RadiotapParserBuilder::new()
+    }
+
+    /// Returns the parsed [Radiotap](struct.Radiotap.html), the errors of the
+    /// fields that could not be parsed, and the remaining data from an input
+    /// byte array.
+    ///
+    /// The errors are always empty unless the parser is lenient.
+    pub fn parse<'a>(&self, input: &'a [u8]) -> Result<(Radiotap, Vec<FieldError>, &'a [u8])> {
+        let mut radiotap = Radiotap::default();
+        let (errors, rest) = self.parse_into(&mut radiotap, input)?;
+        Ok((radiotap, errors, rest))
+    }
+
+    /// Parses the input into an existing [Radiotap](struct.Radiotap.html),
+    /// see [Radiotap::parse_into](struct.Radiotap.html#method.parse_into),
+    /// returning the errors of the fields that could not be parsed and the
+    /// remaining data.
+    pub fn parse_into<'a>(
+        &self,
+        radiotap: &mut Radiotap,
+        input: &'a [u8],
+    ) -> Result<(Vec<FieldError>, &'a [u8])> {
+        radiotap.clear();
+        let (iterator, rest) = RadiotapIterator::parse_header(input, self.any_version)?;
+        if !self.strict {
+            return Radiotap::parse_fields(radiotap, (iterator, rest), self);
+        }
+
+        // The capture is parsed once, leniently and within the limits, and
+        // the errors of the fields are reported as violations
+        let lenient = RadiotapParser {
+            lenient: true,
+            ..self.clone()
+        };
+        let (errors, rest) = Radiotap::parse_fields(radiotap, (iterator.clone(), rest), &lenient)?;
+        let violations = validate::violations(&iterator, radiotap, &errors)?;
+        if !violations.is_empty() {
+            return Err(Error::Violations(violations));
+        }
+
+        // Only the errors stopping the iteration over the fields are left
+        if self.lenient {
+            return Ok((errors, rest));
+        }
+        match errors.into_iter().find(|error| error.kind.is_none()) {
+            Some(error) => Err(error.error),
+            None => Ok((Vec::new(), rest)),
+        }
+    }
+}
+
+/// Builds a [RadiotapParser](struct.RadiotapParser.html) option by option.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct RadiotapParserBuilder {
+    parser: RadiotapParser,
+}
+
+impl RadiotapParserBuilder {
+    /// Returns a new builder with the default options.
+    pub fn new() -> RadiotapParserBuilder {
+        RadiotapParserBuilder::default()
+    }
+
+    /// Sets whether malformed fields are collected as errors instead of
+    /// failing the whole capture, see
+    /// [Radiotap::parse_lenient](struct.Radiotap.html#method.parse_lenient).
+    pub fn lenient(mut self, lenient: bool) -> RadiotapParserBuilder {
+        self.parser.lenient = lenient;
+        self
+    }
+
+    /// Sets whether captures that do not strictly follow the specification
+    /// are rejected, see [validate](validate/fn.validate.html). The capture is
+    /// only parsed once, with the other options and within the limits.
+    pub fn strict(mut self, strict: bool) -> RadiotapParserBuilder {
+        self.parser.strict = strict;
+        self
+    }
+
+    /// Sets whether any header version is accepted, see
+    /// [Radiotap::parse_any_version](struct.Radiotap.html#method.parse_any_version).
+    pub fn any_version(mut self, any_version: bool) -> RadiotapParserBuilder {
+        self.parser.any_version = any_version;
+        self
+    }
+
+    /// Sets what to do with fields that appear more than once.
+    pub fn duplicates(mut self, duplicates: Duplicates) -> RadiotapParserBuilder {
+        self.parser.duplicates = duplicates;
+        self
+    }
+
+    /// Sets whether vendor namespaces are decoded by the built-in decoders of
+    /// the [vendor](vendor/index.html) module. The raw vendor namespaces are
+    /// always kept.
+    pub fn vendor(mut self, vendor: bool) -> RadiotapParserBuilder {
+        self.parser.vendor = vendor;
+        self
+    }
+
+    /// Sets whether the undecoded bytes of the fields of the first radiotap
+    /// namespace are kept, see
+    /// [Radiotap::get_raw](struct.Radiotap.html#method.get_raw).
+    pub fn raw(mut self, raw: bool) -> RadiotapParserBuilder {
+        self.parser.raw = raw;
+        self
+    }
+
+    /// Sets the limits on the work done for a single capture.
+    pub fn limits(mut self, limits: Limits) -> RadiotapParserBuilder {
+        self.parser.limits = limits;
+        self
+    }
+
+    /// Returns the configured [RadiotapParser](struct.RadiotapParser.html).
+    pub fn done(self) -> RadiotapParser {
+        self.parser
+    }
+}
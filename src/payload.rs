@@ -0,0 +1,282 @@
+//! The 802.11 frame following a Radiotap capture.
+//!
+//! The [Flags](../field/struct.Flags.html) field tells whether the frame ends
+//! with an FCS and whether the frame body is padded to a 32-bit boundary after
+//! the MAC header. A [Payload](struct.Payload.html) takes both into account.
+//!
+//! A [FrameParser](trait.FrameParser.html) hands the frame to another 802.11
+//! parsing crate, without the FCS and padding, see
+//! [Radiotap::parse_with_frame](../struct.Radiotap.html#method.parse_with_frame).
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, vec::Vec};
+
+use crate::{
+    fcs::{self, FcsStatus},
+    field::Flags,
+};
+
+/// Returns the length of the MAC header of an 802.11 frame, or `None` if the
+/// frame is too short or of an unknown type.
+pub fn header_length(frame: &[u8]) -> Option<usize> {
+    let (fc0, fc1) = (*frame.first()?, *frame.get(1)?);
+    let subtype = fc0 >> 4;
+    // The order bit signals an HT control field in management and QoS data
+    // frames
+    let order = fc1 & 0x80 != 0;
+    let length = match (fc0 >> 2) & 0x03 {
+        // Management
+        0 => 24 + if order { 4 } else { 0 },
+        // Control, CTS and ACK frames have a single address
+        1 => match subtype {
+            0xc | 0xd => 10,
+            _ => 16,
+        },
+        // Data, with a fourth address when going from and to the DS
+        2 => {
+            let qos = subtype & 0x08 != 0;
+            let mut length = 24;
+            if fc1 & 0x03 == 0x03 {
+                length += 6;
+            }
+            if qos {
+                length += 2;
+                if order {
+                    length += 4;
+                }
+            }
+            length
+        }
+        _ => return None,
+    };
+    if frame.len() < length {
+        return None;
+    }
+    Some(length)
+}
+
+/// Returns the offset of the frame body in an 802.11 frame, after the MAC
+/// header and, if `data_pad` is set, the padding aligning the body to a
+/// 32-bit boundary. Returns `None` if the MAC header cannot be located.
+///
+/// The `data_pad` flag of the [Flags](../field/struct.Flags.html) field tells
+/// whether the driver inserted the padding.
+///
+/// # Examples
+///
+/// ```
+/// use radiotap::payload::body_offset;
+///
+/// // A QoS data frame has a 26 byte MAC header
+/// let mut frame = vec![0x88, 0x00];
+/// frame.resize(32, 0);
+/// assert_eq!(body_offset(&frame, false), Some(26));
+/// assert_eq!(body_offset(&frame, true), Some(28));
+/// ```
+pub fn body_offset(frame: &[u8], data_pad: bool) -> Option<usize> {
+    let length = header_length(frame)?;
+    if data_pad {
+        Some((length + 3) & !3)
+    } else {
+        Some(length)
+    }
+}
+
+/// The data following a Radiotap capture, usually an 802.11 frame.
+///
+/// # Examples
+///
+/// ```
+/// use radiotap::{fcs::FcsStatus, Radiotap};
+///
+/// // An ACK frame with an FCS
+/// let capture = [
+///     0, 0, 9, 0, 2, 0, 0, 0, 0x10, 0xd4, 0, 0, 0, 2, 4, 6, 8, 10, 12, 0x4b, 0xea, 0x19, 0xd5,
+/// ];
+/// let (_, payload) = Radiotap::parse_with_payload(&capture).unwrap();
+/// assert_eq!(payload.header(), Some(&capture[9..19]));
+/// assert_eq!(payload.fcs(), Some(0xd519_ea4b));
+/// assert_eq!(payload.fcs_valid(), Some(true));
+/// assert_eq!(payload.fcs_status(), FcsStatus::Valid);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Payload<'a> {
+    data: &'a [u8],
+    fcs: bool,
+    data_pad: bool,
+    bad_fcs: bool,
+}
+
+impl<'a> Payload<'a> {
+    /// Returns the payload of the given data following a Radiotap capture
+    /// with the given flags.
+    pub fn new(data: &'a [u8], flags: Option<Flags>) -> Payload<'a> {
+        Payload {
+            data,
+            fcs: flags.is_some_and(|flags| flags.fcs),
+            data_pad: flags.is_some_and(|flags| flags.data_pad),
+            bad_fcs: flags.is_some_and(|flags| flags.bad_fcs),
+        }
+    }
+
+    /// Returns all the data following the Radiotap capture.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns the 802.11 frame, without the FCS.
+    pub fn frame(&self) -> &'a [u8] {
+        if self.fcs {
+            &self.data[..self.data.len().saturating_sub(4)]
+        } else {
+            self.data
+        }
+    }
+
+    /// Returns the MAC header of the 802.11 frame.
+    pub fn header(&self) -> Option<&'a [u8]> {
+        let frame = self.frame();
+        header_length(frame).map(|length| &frame[..length])
+    }
+
+    /// Returns the body of the 802.11 frame, after the MAC header and any
+    /// padding, without the FCS.
+    pub fn body(&self) -> Option<&'a [u8]> {
+        self.frame().get(self.body_offset()?..)
+    }
+
+    /// Returns the offset of the frame body from the start of the data,
+    /// after the MAC header and any padding, see
+    /// [body_offset](fn.body_offset.html).
+    pub fn body_offset(&self) -> Option<usize> {
+        body_offset(self.frame(), self.data_pad)
+    }
+
+    /// Returns the 802.11 frame without the FCS and without the padding
+    /// between the MAC header and the body, which is only copied if there is
+    /// padding to remove.
+    ///
+    /// This is the frame as it was transmitted, what other 802.11 parsers
+    /// expect.
+    #[cfg(feature = "alloc")]
+    pub fn unpadded_frame(&self) -> Cow<'a, [u8]> {
+        let frame = self.frame();
+        let length = match header_length(frame) {
+            Some(length) if self.data_pad => length,
+            _ => return Cow::Borrowed(frame),
+        };
+        let start = ((length + 3) & !3).min(frame.len());
+        if start == length {
+            return Cow::Borrowed(frame);
+        }
+        let mut unpadded = Vec::with_capacity(frame.len() - (start - length));
+        unpadded.extend_from_slice(&frame[..length]);
+        unpadded.extend_from_slice(&frame[start..]);
+        Cow::Owned(unpadded)
+    }
+
+    /// Returns the FCS at the end of the frame, if the flags say there is
+    /// one.
+    pub fn fcs(&self) -> Option<u32> {
+        if !self.fcs || self.data.len() < 4 {
+            return None;
+        }
+        let fcs = &self.data[self.data.len() - 4..];
+        Some(u32::from_le_bytes([fcs[0], fcs[1], fcs[2], fcs[3]]))
+    }
+
+    /// Returns whether the FCS matches the frame, if there is one.
+    pub fn fcs_valid(&self) -> Option<bool> {
+        self.fcs().map(|fcs| fcs::crc32(self.frame()) == fcs)
+    }
+
+    /// Returns whether the frame was received intact, from the FCS and the
+    /// bad FCS flag.
+    pub fn fcs_status(&self) -> FcsStatus {
+        FcsStatus::check(self.data, self.fcs, self.bad_fcs)
+    }
+}
+
+/// A parser of 802.11 frames from another crate, used to decode the frame
+/// following a Radiotap capture.
+///
+/// It is implemented for functions and closures taking the frame, so the
+/// parse function of an 802.11 crate can be passed directly.
+pub trait FrameParser {
+    /// The parsed frame.
+    type Frame;
+    /// The error returned when the frame cannot be parsed.
+    type Error;
+
+    /// Parses an 802.11 frame, without the FCS and the padding after the MAC
+    /// header.
+    fn parse_frame(&self, frame: &[u8]) -> Result<Self::Frame, Self::Error>;
+}
+
+/// The result of a [FrameParser](trait.FrameParser.html).
+pub type FrameResult<P> = Result<<P as FrameParser>::Frame, <P as FrameParser>::Error>;
+
+impl<F, T, E> FrameParser for F
+where
+    F: Fn(&[u8]) -> Result<T, E>,
+{
+    type Frame = T;
+    type Error = E;
+
+    fn parse_frame(&self, frame: &[u8]) -> Result<T, E> {
+        self(frame)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::field::from_bytes;
+
+    #[test]
+    fn padded_body() {
+        // A QoS data frame with padding after its 26 byte header
+        let mut data = vec![0x88, 0x00];
+        data.resize(26, 0);
+        data.extend_from_slice(&[0, 0, 0xaa, 0xbb, 1, 2, 3, 4]);
+
+        let flags = from_bytes(&[0x30]).unwrap();
+        let payload = Payload::new(&data, Some(flags));
+        assert_eq!(payload.header().map(<[u8]>::len), Some(26));
+        assert_eq!(payload.body_offset(), Some(28));
+        assert_eq!(payload.body(), Some(&[0xaa, 0xbb][..]));
+        assert_eq!(payload.fcs(), Some(0x0403_0201));
+
+        let payload = Payload::new(&data, None);
+        assert_eq!(payload.body_offset(), Some(26));
+        assert_eq!(payload.body(), Some(&data[26..]));
+        assert_eq!(payload.fcs(), None);
+    }
+
+    #[test]
+    fn unpadded_frame() {
+        // A QoS data frame with padding after its 26 byte header and an FCS
+        let mut data = vec![0x88, 0x00];
+        data.resize(26, 0);
+        data.extend_from_slice(&[0, 0, 0xaa, 0xbb, 1, 2, 3, 4]);
+
+        let flags = from_bytes(&[0x30]).unwrap();
+        let frame = Payload::new(&data, Some(flags)).unpadded_frame();
+        assert!(matches!(frame, Cow::Owned(_)));
+        assert_eq!(frame[..26], data[..26]);
+        assert_eq!(frame[26..], [0xaa, 0xbb]);
+
+        // A management frame header is already aligned
+        let mut data = vec![0x80, 0x00];
+        data.resize(30, 0);
+        let frame = Payload::new(&data, Some(flags)).unpadded_frame();
+        assert_eq!(frame, Cow::Borrowed(&data[..26]));
+    }
+
+    #[test]
+    fn header_lengths() {
+        assert_eq!(header_length(&[0xd4, 0, 0, 0, 0, 0, 0, 0, 0, 0]), Some(10));
+        assert_eq!(header_length(&[0xb4, 0, 0, 0]), None);
+        assert_eq!(header_length(&[0x0c, 0]), None);
+    }
+}
@@ -0,0 +1,571 @@
+//! Reading of Radiotap captures from pcap and pcapng files.
+//!
+//! Enabled with the `pcap` feature. A [Reader](struct.Reader.html) reads a
+//! legacy pcap file with the
+//! [LINKTYPE_IEEE802_11_RADIOTAP](../constant.LINKTYPE_IEEE802_11_RADIOTAP.html)
+//! link type, or a pcapng file with one or more interfaces of that link type,
+//! and yields the timestamp, parsed Radiotap header, 802.11 frame and capture
+//! interface of each record.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::fs::File;
+//!
+//! use radiotap::pcap::Reader;
+//!
+//! let reader = Reader::new(File::open("capture.pcapng").unwrap()).unwrap();
+//! for record in reader {
+//!     let record = record.unwrap();
+//!     println!(
+//!         "{:?} {:?} {:?}",
+//!         record.timestamp, record.interface.name, record.radiotap.antenna_signal
+//!     );
+//! }
+//! ```
+
+use std::{io::Read, sync::Arc, time::Duration};
+
+use crate::{io, is_radiotap_linktype, Error, Radiotap, Result};
+
+/// The largest record or block accepted, regardless of the snapshot length of
+/// the file.
+const MAX_RECORD_LEN: u32 = 1 << 20;
+
+/// The type of the pcapng section header block, also its magic number.
+const SECTION_HEADER: u32 = 0x0a0d_0d0a;
+/// The type of the pcapng interface description block.
+const INTERFACE_DESCRIPTION: u32 = 1;
+/// The type of the pcapng simple packet block.
+const SIMPLE_PACKET: u32 = 3;
+/// The type of the pcapng enhanced packet block.
+const ENHANCED_PACKET: u32 = 6;
+
+/// A capture interface. A legacy pcap file has a single interface without a
+/// name.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Interface {
+    /// The link type of the records.
+    pub linktype: u32,
+    /// The maximum number of bytes captured of each frame, 0 if unlimited.
+    pub snaplen: u32,
+    /// The name of the interface, from the `if_name` option.
+    pub name: Option<String>,
+    /// The description of the interface, from the `if_description` option.
+    pub description: Option<String>,
+    /// The number of timestamp units per second, from the `if_tsresol`
+    /// option.
+    pub resolution: u64,
+    /// The offset of the timestamps in seconds, from the `if_tsoffset`
+    /// option.
+    pub offset: i64,
+}
+
+impl Interface {
+    /// Returns the timestamp of a record since the Unix epoch.
+    fn timestamp(&self, units: u64) -> Duration {
+        let nanos = u128::from(units) * 1_000_000_000 / u128::from(self.resolution.max(1));
+        let timestamp = Duration::from_secs((nanos / 1_000_000_000) as u64)
+            + Duration::from_nanos((nanos % 1_000_000_000) as u64);
+        let offset = Duration::from_secs(self.offset.unsigned_abs());
+        if self.offset >= 0 {
+            timestamp + offset
+        } else {
+            timestamp.saturating_sub(offset)
+        }
+    }
+}
+
+/// A record of a capture file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    /// The time the frame was captured, since the Unix epoch, zero for the
+    /// simple packet blocks of pcapng files.
+    pub timestamp: Duration,
+    /// The length of the frame when it was captured, including the Radiotap
+    /// header, which is more than the captured data if it was truncated.
+    pub original_length: u32,
+    /// The parsed Radiotap header.
+    pub radiotap: Radiotap,
+    /// The 802.11 frame following the Radiotap header.
+    pub payload: Vec<u8>,
+    /// The interface the frame was captured on.
+    pub interface: Arc<Interface>,
+}
+
+/// The timestamp, original length, interface and data of a record.
+type RawRecord = (Duration, u32, Arc<Interface>, Vec<u8>);
+
+/// The format of a capture file.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Format {
+    Pcap,
+    Pcapng,
+}
+
+/// A reader of the records of a pcap or pcapng file, see the
+/// [module](index.html) documentation.
+#[derive(Debug)]
+pub struct Reader<R> {
+    reader: R,
+    format: Format,
+    big_endian: bool,
+    interfaces: Vec<Arc<Interface>>,
+    done: bool,
+}
+
+impl<R: Read> Reader<R> {
+    /// Reads the file header, returning a reader of the records.
+    ///
+    /// Legacy pcap files with another link type return an
+    /// `UnsupportedLinkType` error. The records of pcapng interfaces with
+    /// another link type are skipped.
+    pub fn new(mut reader: R) -> Result<Reader<R>> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+
+        let mut reader = Reader {
+            reader,
+            format: Format::Pcap,
+            big_endian: false,
+            interfaces: Vec::new(),
+            done: false,
+        };
+        if u32::from_le_bytes(magic) == SECTION_HEADER {
+            reader.format = Format::Pcapng;
+            reader.read_section_header()?;
+            return Ok(reader);
+        }
+
+        let nanoseconds = match magic {
+            [0xd4, 0xc3, 0xb2, 0xa1] => false,
+            [0xa1, 0xb2, 0xc3, 0xd4] => {
+                reader.big_endian = true;
+                false
+            }
+            [0x4d, 0x3c, 0xb2, 0xa1] => true,
+            [0xa1, 0xb2, 0x3c, 0x4d] => {
+                reader.big_endian = true;
+                true
+            }
+            _ => return Err(Error::InvalidFormat),
+        };
+        let mut header = [0; 20];
+        reader.reader.read_exact(&mut header)?;
+        let linktype = reader.u32(&header[16..20]) & 0x0fff_ffff;
+        if !is_radiotap_linktype(linktype) {
+            return Err(Error::UnsupportedLinkType(linktype));
+        }
+        reader.interfaces.push(Arc::new(Interface {
+            linktype,
+            snaplen: reader.u32(&header[12..16]),
+            name: None,
+            description: None,
+            resolution: if nanoseconds {
+                1_000_000_000
+            } else {
+                1_000_000
+            },
+            offset: 0,
+        }));
+        Ok(reader)
+    }
+
+    /// Returns the interfaces described so far in the current section of a
+    /// pcapng file, or the single interface of a legacy pcap file.
+    pub fn interfaces(&self) -> &[Arc<Interface>] {
+        &self.interfaces
+    }
+
+    fn u16(&self, bytes: &[u8]) -> u16 {
+        let bytes = [bytes[0], bytes[1]];
+        if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        }
+    }
+
+    fn u32(&self, bytes: &[u8]) -> u32 {
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        }
+    }
+
+    /// Fills the buffer, returning `false` if the file ended before the first
+    /// byte.
+    fn read_or_end(&mut self, buf: &mut [u8]) -> Result<bool> {
+        let mut read = 0;
+        while read < buf.len() {
+            match self.reader.read(&mut buf[read..]) {
+                Ok(0) if read == 0 => return Ok(false),
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+                Ok(n) => read += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(true)
+    }
+
+    /// Reads the given number of bytes.
+    fn read_data(&mut self, length: u32) -> Result<Vec<u8>> {
+        if length > MAX_RECORD_LEN {
+            return Err(Error::LimitExceeded("record length"));
+        }
+        let mut data = Vec::new();
+        data.try_reserve_exact(length as usize)?;
+        data.resize(length as usize, 0);
+        self.reader.read_exact(&mut data)?;
+        Ok(data)
+    }
+
+    /// Reads the timestamp, original length, interface and data of the next
+    /// record, returning `None` at the end of the file.
+    fn read_record(&mut self) -> Result<Option<RawRecord>> {
+        match self.format {
+            Format::Pcap => self.read_pcap_record(),
+            Format::Pcapng => self.read_pcapng_record(),
+        }
+    }
+
+    fn read_pcap_record(&mut self) -> Result<Option<RawRecord>> {
+        let mut header = [0; 16];
+        if !self.read_or_end(&mut header)? {
+            return Ok(None);
+        }
+
+        let interface = self.interfaces[0].clone();
+        let seconds = u64::from(self.u32(&header[..4]));
+        let fraction = u64::from(self.u32(&header[4..8]));
+        let timestamp = Duration::from_secs(seconds) + interface.timestamp(fraction);
+        let length = self.u32(&header[8..12]);
+        let original_length = self.u32(&header[12..16]);
+        if length > interface.snaplen.max(MAX_RECORD_LEN) {
+            return Err(Error::LimitExceeded("record length"));
+        }
+        let data = self.read_data(length)?;
+        Ok(Some((timestamp, original_length, interface, data)))
+    }
+
+    fn read_pcapng_record(&mut self) -> Result<Option<RawRecord>> {
+        loop {
+            let mut header = [0; 8];
+            if !self.read_or_end(&mut header)? {
+                return Ok(None);
+            }
+            if u32::from_le_bytes([header[0], header[1], header[2], header[3]]) == SECTION_HEADER {
+                self.read_section_header()?;
+                continue;
+            }
+
+            let kind = self.u32(&header[..4]);
+            let length = self.u32(&header[4..8]);
+            if length < 12 || length & 3 != 0 {
+                return Err(Error::InvalidFormat);
+            }
+            // The body and the trailing copy of the length
+            let block = self.read_data(length - 8)?;
+            let body = &block[..block.len() - 4];
+
+            match kind {
+                INTERFACE_DESCRIPTION => self.read_interface(body)?,
+                ENHANCED_PACKET => {
+                    if body.len() < 20 {
+                        return Err(Error::InvalidFormat);
+                    }
+                    let interface = self.interface(self.u32(&body[..4]))?;
+                    let units =
+                        u64::from(self.u32(&body[4..8])) << 32 | u64::from(self.u32(&body[8..12]));
+                    let captured = self.u32(&body[12..16]) as usize;
+                    let original_length = self.u32(&body[16..20]);
+                    let data = body.get(20..20 + captured).ok_or(Error::InvalidFormat)?;
+                    if is_radiotap_linktype(interface.linktype) {
+                        let timestamp = interface.timestamp(units);
+                        return Ok(Some((timestamp, original_length, interface, data.to_vec())));
+                    }
+                }
+                SIMPLE_PACKET => {
+                    if body.len() < 4 {
+                        return Err(Error::InvalidFormat);
+                    }
+                    let interface = self.interface(0)?;
+                    let original_length = self.u32(&body[..4]);
+                    let mut captured = (body.len() - 4).min(original_length as usize);
+                    if interface.snaplen > 0 {
+                        captured = captured.min(interface.snaplen as usize);
+                    }
+                    if is_radiotap_linktype(interface.linktype) {
+                        let data = body[4..4 + captured].to_vec();
+                        return Ok(Some((Duration::ZERO, original_length, interface, data)));
+                    }
+                }
+                // Statistics, name resolution and custom blocks are ignored
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the interface with the given index in the current section.
+    fn interface(&self, index: u32) -> Result<Arc<Interface>> {
+        self.interfaces
+            .get(index as usize)
+            .cloned()
+            .ok_or(Error::InvalidFormat)
+    }
+
+    /// Reads a section header block after its type, which starts a new
+    /// section with its own byte order and interfaces.
+    fn read_section_header(&mut self) -> Result<()> {
+        let mut header = [0; 8];
+        self.reader.read_exact(&mut header)?;
+        self.big_endian = match header[4..] {
+            [0x4d, 0x3c, 0x2b, 0x1a] => false,
+            [0x1a, 0x2b, 0x3c, 0x4d] => true,
+            _ => return Err(Error::InvalidFormat),
+        };
+        let length = self.u32(&header[..4]);
+        if length < 28 || length & 3 != 0 {
+            return Err(Error::InvalidFormat);
+        }
+        // The version, section length, options and trailing length
+        self.read_data(length - 12)?;
+        self.interfaces.clear();
+        Ok(())
+    }
+
+    /// Reads the body of an interface description block.
+    fn read_interface(&mut self, body: &[u8]) -> Result<()> {
+        if body.len() < 8 {
+            return Err(Error::InvalidFormat);
+        }
+        let mut interface = Interface {
+            linktype: u32::from(self.u16(&body[..2])),
+            snaplen: self.u32(&body[4..8]),
+            name: None,
+            description: None,
+            resolution: 1_000_000,
+            offset: 0,
+        };
+
+        let mut options = &body[8..];
+        while options.len() >= 4 {
+            let code = self.u16(&options[..2]);
+            let length = usize::from(self.u16(&options[2..4]));
+            let value = options.get(4..4 + length).ok_or(Error::InvalidFormat)?;
+            match code {
+                0 => break,
+                2 => interface.name = Some(String::from_utf8_lossy(value).into_owned()),
+                3 => interface.description = Some(String::from_utf8_lossy(value).into_owned()),
+                9 if length == 1 => {
+                    let exponent = u32::from(value[0] & 0x7f);
+                    interface.resolution = if value[0] & 0x80 == 0 {
+                        10u64.checked_pow(exponent)
+                    } else {
+                        2u64.checked_pow(exponent)
+                    }
+                    .ok_or(Error::InvalidFormat)?;
+                }
+                14 if length == 8 => {
+                    let (high, low) = (self.u32(&value[..4]), self.u32(&value[4..]));
+                    let (high, low) = if self.big_endian {
+                        (high, low)
+                    } else {
+                        (low, high)
+                    };
+                    interface.offset = (u64::from(high) << 32 | u64::from(low)) as i64;
+                }
+                _ => {}
+            }
+            options = options.get((4 + length + 3) & !3..).unwrap_or(&[]);
+        }
+
+        self.interfaces.try_reserve(1)?;
+        self.interfaces.push(Arc::new(interface));
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for Reader<R> {
+    type Item = Result<Record>;
+
+    /// Returns the next record. A record whose Radiotap header cannot be
+    /// parsed returns an error and reading continues with the next one, an
+    /// error reading the file ends the iteration.
+    fn next(&mut self) -> Option<Result<Record>> {
+        if self.done {
+            return None;
+        }
+        let (timestamp, original_length, interface, mut data) = match self.read_record() {
+            Ok(Some(record)) => record,
+            Ok(None) => {
+                self.done = true;
+                return None;
+            }
+            Err(e) => {
+                self.done = true;
+                return Some(Err(e));
+            }
+        };
+
+        Some(Radiotap::from_bytes(&data).map(|radiotap| {
+            data.drain(..radiotap.header.length);
+            Record {
+                timestamp,
+                original_length,
+                radiotap,
+                payload: data,
+                interface,
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(
+        magic: [u8; 4],
+        big_endian: bool,
+        linktype: u32,
+        records: &[(u32, u32, &[u8])],
+    ) -> Vec<u8> {
+        let u32 = |n: u32| {
+            if big_endian {
+                n.to_be_bytes()
+            } else {
+                n.to_le_bytes()
+            }
+        };
+        let mut file = magic.to_vec();
+        file.extend_from_slice(&[0; 12]);
+        file.extend_from_slice(&u32(65535));
+        file.extend_from_slice(&u32(linktype));
+        for (seconds, fraction, data) in records {
+            file.extend_from_slice(&u32(*seconds));
+            file.extend_from_slice(&u32(*fraction));
+            file.extend_from_slice(&u32(data.len() as u32));
+            file.extend_from_slice(&u32(data.len() as u32 + 4));
+            file.extend_from_slice(data);
+        }
+        file
+    }
+
+    #[test]
+    fn records() {
+        let frame: &[u8] = &[0, 0, 10, 0, 0x24, 0, 0, 0, 12, 0xd6, 0x80, 0];
+        let broken: &[u8] = &[0, 0, 99, 0];
+        let file = file(
+            [0xd4, 0xc3, 0xb2, 0xa1],
+            false,
+            127,
+            &[(1, 500, frame), (2, 0, broken), (3, 0, frame)],
+        );
+
+        let records: Vec<_> = Reader::new(&file[..]).unwrap().collect();
+        assert_eq!(records.len(), 3);
+        let record = records[0].as_ref().unwrap();
+        assert_eq!(record.timestamp, Duration::new(1, 500_000));
+        assert_eq!(record.original_length, 16);
+        assert_eq!(record.radiotap.antenna_signal.unwrap().value, -42);
+        assert_eq!(record.payload, [0x80, 0]);
+        assert!(records[1].is_err());
+        assert!(records[2].is_ok());
+
+        // A truncated record ends the iteration
+        let mut reader = Reader::new(&file[..file.len() - 1]).unwrap();
+        assert!(reader.nth(2).unwrap().is_err());
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn header() {
+        let frame: &[u8] = &[0, 0, 8, 0, 0, 0, 0, 0];
+        let file = file([0xa1, 0xb2, 0x3c, 0x4d], true, 127, &[(1, 5, frame)]);
+        let mut reader = Reader::new(&file[..]).unwrap();
+        assert_eq!(reader.interfaces()[0].snaplen, 65535);
+        assert_eq!(
+            reader.next().unwrap().unwrap().timestamp,
+            Duration::new(1, 5)
+        );
+
+        let file = self::file([0xd4, 0xc3, 0xb2, 0xa1], false, 105, &[]);
+        assert!(matches!(
+            Reader::new(&file[..]),
+            Err(Error::UnsupportedLinkType(105))
+        ));
+        assert!(matches!(
+            Reader::new(&[0; 24][..]),
+            Err(Error::InvalidFormat)
+        ));
+    }
+
+    fn block(kind: u32, body: &[u8]) -> Vec<u8> {
+        let length = 12 + ((body.len() as u32 + 3) & !3);
+        let mut block = kind.to_le_bytes().to_vec();
+        block.extend_from_slice(&length.to_le_bytes());
+        block.extend_from_slice(body);
+        block.resize(length as usize - 4, 0);
+        block.extend_from_slice(&length.to_le_bytes());
+        block
+    }
+
+    fn option(code: u16, value: &[u8]) -> Vec<u8> {
+        let mut option = code.to_le_bytes().to_vec();
+        option.extend_from_slice(&(value.len() as u16).to_le_bytes());
+        option.extend_from_slice(value);
+        option.resize((option.len() + 3) & !3, 0);
+        option
+    }
+
+    fn packet(interface: u32, units: u64, data: &[u8]) -> Vec<u8> {
+        let mut body = interface.to_le_bytes().to_vec();
+        body.extend_from_slice(&((units >> 32) as u32).to_le_bytes());
+        body.extend_from_slice(&(units as u32).to_le_bytes());
+        body.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        body.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        body.extend_from_slice(data);
+        block(ENHANCED_PACKET, &body)
+    }
+
+    #[test]
+    fn pcapng() {
+        let frame: &[u8] = &[0, 0, 10, 0, 0x24, 0, 0, 0, 12, 0xd6, 0x80, 0];
+        let mut file = block(
+            SECTION_HEADER,
+            &[
+                0x4d, 0x3c, 0x2b, 0x1a, 1, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            ],
+        );
+        let mut interface = vec![127, 0, 0, 0, 0, 0, 0, 0];
+        interface.extend(option(2, b"wlan0mon"));
+        interface.extend(option(9, &[9]));
+        interface.extend(option(14, &10i64.to_le_bytes()));
+        interface.extend(option(0, &[]));
+        file.extend(block(INTERFACE_DESCRIPTION, &interface));
+        file.extend(block(INTERFACE_DESCRIPTION, &[1, 0, 0, 0, 0, 0, 0, 0]));
+        file.extend(packet(1, 5, &[0xff; 14]));
+        file.extend(block(5, &[0; 8]));
+        file.extend(packet(0, 1_500_000_000, frame));
+
+        let mut reader = Reader::new(&file[..]).unwrap();
+        let record = reader.next().unwrap().unwrap();
+        assert_eq!(record.timestamp, Duration::new(11, 500_000_000));
+        assert_eq!(record.interface.name.as_deref(), Some("wlan0mon"));
+        assert_eq!(record.interface.resolution, 1_000_000_000);
+        assert_eq!(record.radiotap.antenna_signal.unwrap().value, -42);
+        assert_eq!(record.payload, [0x80, 0]);
+        assert!(reader.next().is_none());
+        assert_eq!(reader.interfaces().len(), 2);
+        assert_eq!(reader.interfaces()[1].linktype, 1);
+
+        // A packet of an undescribed interface
+        file.extend(packet(2, 0, frame));
+        let mut reader = Reader::new(&file[..]).unwrap();
+        assert!(reader.nth(1).unwrap().is_err());
+    }
+}
@@ -0,0 +1,173 @@
+//! Python bindings with [PyO3](https://pyo3.rs).
+//!
+//! Enabled with the `python` feature. The [register](fn.register.html)
+//! function adds a `Radiotap` class to a Python module, whose `from_bytes` and
+//! `parse` static methods parse a capture and whose properties return the
+//! values of the common fields, or `None` if they are not present.
+//! `to_dict()` returns every field with a Wireshark name like
+//! [to_json](../struct.Radiotap.html#method.to_json).
+//!
+//! A Python extension is a `cdylib` crate defining the module:
+//!
+//! ```ignore
+//! use pyo3::prelude::*;
+//!
+//! #[pymodule]
+//! fn radiotap(m: &Bound<'_, PyModule>) -> PyResult<()> {
+//!     ::radiotap::python::register(m)
+//! }
+//! ```
+//!
+//! which is used from Python as:
+//!
+//! ```python
+//! import radiotap
+//!
+//! rt, payload = radiotap.Radiotap.parse(frame)
+//! print(rt.channel_freq, rt.antenna_signal, rt.to_dict())
+//! ```
+
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyBytes};
+
+use crate::{Error, Radiotap};
+
+impl From<Error> for PyErr {
+    fn from(e: Error) -> PyErr {
+        PyValueError::new_err(e.to_string())
+    }
+}
+
+/// Adds the `Radiotap` class to a Python module.
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyRadiotap>()
+}
+
+/// A parsed Radiotap capture, the Python `Radiotap` class.
+#[pyclass(name = "Radiotap", frozen)]
+#[derive(Clone, Debug)]
+pub struct PyRadiotap(pub Radiotap);
+
+#[pymethods]
+impl PyRadiotap {
+    /// Parses a Radiotap capture.
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> PyResult<PyRadiotap> {
+        Ok(PyRadiotap(Radiotap::from_bytes(data)?))
+    }
+
+    /// Parses a Radiotap capture, returning it and the 802.11 frame following
+    /// it.
+    #[staticmethod]
+    fn parse<'py>(py: Python<'py>, data: &[u8]) -> PyResult<(PyRadiotap, Bound<'py, PyBytes>)> {
+        let (radiotap, rest) = Radiotap::parse(data)?;
+        Ok((PyRadiotap(radiotap), PyBytes::new(py, rest)))
+    }
+
+    /// The length of the Radiotap header.
+    #[getter]
+    fn length(&self) -> usize {
+        self.0.header.length
+    }
+
+    /// The TSF timer in microseconds.
+    #[getter]
+    fn tsft(&self) -> Option<u64> {
+        self.0.tsft.map(|tsft| tsft.value)
+    }
+
+    /// The legacy data rate in Mb/s.
+    #[getter]
+    fn rate(&self) -> Option<f32> {
+        self.0.rate.map(|rate| rate.as_mbps())
+    }
+
+    /// The channel frequency in MHz.
+    #[getter]
+    fn channel_freq(&self) -> Option<u16> {
+        self.0.channel.map(|channel| channel.freq)
+    }
+
+    /// The antenna signal in dBm.
+    #[getter]
+    fn antenna_signal(&self) -> Option<i8> {
+        self.0.antenna_signal.map(|signal| signal.value)
+    }
+
+    /// The antenna noise in dBm.
+    #[getter]
+    fn antenna_noise(&self) -> Option<i8> {
+        self.0.antenna_noise.map(|noise| noise.value)
+    }
+
+    /// The antenna index.
+    #[getter]
+    fn antenna(&self) -> Option<u8> {
+        self.0.antenna.map(|antenna| antenna.value)
+    }
+
+    /// The transmit power in dBm.
+    #[getter]
+    fn tx_power(&self) -> Option<i8> {
+        self.0.tx_power.map(|power| power.value)
+    }
+
+    /// The HT MCS index.
+    #[getter]
+    fn mcs_index(&self) -> Option<u8> {
+        self.0.mcs.and_then(|mcs| mcs.index)
+    }
+
+    /// Returns the fields as a dict keyed by their Wireshark names.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        py.import("json")?
+            .call_method1("loads", (self.0.to_json(),))
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Radiotap {}>", self.0.to_line())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn class() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let module = PyModule::new(py, "radiotap").unwrap();
+            register(&module).unwrap();
+            let class = module.getattr("Radiotap").unwrap();
+
+            let capture = PyBytes::new(py, &[0, 0, 10, 0, 0x24, 0, 0, 0, 12, 0xd6, 0x80, 0]);
+            let (radiotap, payload): (Bound<'_, PyAny>, Vec<u8>) = class
+                .call_method1("parse", (capture,))
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(payload, [0x80, 0]);
+            let signal: i8 = radiotap
+                .getattr("antenna_signal")
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(signal, -42);
+            assert!(radiotap.getattr("tsft").unwrap().is_none());
+            let dict = radiotap.call_method0("to_dict").unwrap();
+            let rate: f64 = dict
+                .get_item("radiotap.datarate")
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(rate, 6.0);
+
+            let error = class.call_method1("from_bytes", (PyBytes::new(py, &[1]),));
+            assert!(error.unwrap_err().is_instance_of::<PyValueError>(py));
+        });
+    }
+}
@@ -0,0 +1,244 @@
+//! The on-wire layout of the multi-byte Radiotap fields, for hot paths that
+//! only need a few raw values.
+//!
+//! Each struct is `#[repr(C)]` with little endian integers and no alignment
+//! requirement, so it is a reference into the capture data obtained with a
+//! single length check, instead of reading every member like the decoded
+//! types of the [field](../field/index.html) module. Enabled with the
+//! `zerocopy` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use radiotap::{
+//!     raw::{RawChannel, RawField},
+//!     view::RadiotapView,
+//! };
+//!
+//! let capture = [0, 0, 12, 0, 8, 0, 0, 0, 0x6c, 0x09, 0xa0, 0];
+//! let view = RadiotapView::from_bytes(&capture).unwrap();
+//! let channel: &RawChannel = view.raw().unwrap().unwrap();
+//! assert_eq!(channel.freq.get(), 2412);
+//! assert!(channel.decode().unwrap().flags.ghz2);
+//! ```
+
+use zerocopy::{
+    little_endian::{U16, U32, U64},
+    FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned,
+};
+
+use crate::{
+    field::{
+        AMPDUStatus, Channel, Field, HEMUOtherUser, LSig, PresentField, Timestamp, XChannel, FHSS,
+        HE, HEMU, MCS, TSFT, VHT,
+    },
+    Error, Result,
+};
+
+/// A raw field, the on-wire layout of a decoded field type.
+pub trait RawField: FromBytes + IntoBytes + KnownLayout + Immutable + Unaligned {
+    /// The decoded field type.
+    type Field: PresentField;
+
+    /// Returns the raw field at the start of the data, the data of a field
+    /// yielded by a [RadiotapIterator](../struct.RadiotapIterator.html).
+    fn from_data(data: &[u8]) -> Result<&Self> {
+        Self::ref_from_prefix(data)
+            .map(|(raw, _)| raw)
+            .map_err(|_| Error::IncompleteError)
+    }
+
+    /// Decodes the raw field into its decoded field type.
+    fn decode(&self) -> Result<Self::Field> {
+        Self::Field::from_bytes(self.as_bytes())
+    }
+}
+
+macro_rules! raw_fields {
+    ($($(#[$attr:meta])* $raw:ident => $field:ident { $($(#[$member_attr:meta])* $member:ident: $ty:ty,)* })*) => {
+        $(
+            $(#[$attr])*
+            #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+            #[derive(FromBytes, IntoBytes, KnownLayout, Immutable, Unaligned)]
+            #[repr(C)]
+            pub struct $raw {
+                $(
+                    $(#[$member_attr])*
+                    pub $member: $ty,
+                )*
+            }
+
+            impl RawField for $raw {
+                type Field = $field;
+            }
+        )*
+    };
+}
+
+raw_fields! {
+    /// The raw [TSFT](../field/struct.TSFT.html) field.
+    RawTSFT => TSFT {
+        /// The TSF timer in microseconds.
+        value: U64,
+    }
+
+    /// The raw [Channel](../field/struct.Channel.html) field.
+    RawChannel => Channel {
+        /// The frequency in MHz.
+        freq: U16,
+        /// The channel flags.
+        flags: U16,
+    }
+
+    /// The raw [FHSS](../field/struct.FHSS.html) field.
+    RawFHSS => FHSS {
+        /// The hop set.
+        hopset: u8,
+        /// The hop pattern.
+        pattern: u8,
+    }
+
+    /// The raw [XChannel](../field/struct.XChannel.html) field.
+    RawXChannel => XChannel {
+        /// The channel flags.
+        flags: U32,
+        /// The frequency in MHz.
+        freq: U16,
+        /// The channel number.
+        channel: u8,
+        /// The maximum transmit power in dBm.
+        max_power: u8,
+    }
+
+    /// The raw [MCS](../field/struct.MCS.html) field.
+    RawMCS => MCS {
+        /// The known bits.
+        known: u8,
+        /// The flags.
+        flags: u8,
+        /// The MCS index.
+        mcs: u8,
+    }
+
+    /// The raw [AMPDUStatus](../field/struct.AMPDUStatus.html) field.
+    RawAMPDUStatus => AMPDUStatus {
+        /// The A-MPDU reference number.
+        reference: U32,
+        /// The flags.
+        flags: U16,
+        /// The delimiter CRC value.
+        delimiter_crc: u8,
+        /// Reserved.
+        reserved: u8,
+    }
+
+    /// The raw [VHT](../field/struct.VHT.html) field.
+    RawVHT => VHT {
+        /// The known bits.
+        known: U16,
+        /// The flags.
+        flags: u8,
+        /// The bandwidth encoding.
+        bandwidth: u8,
+        /// The MCS index and number of spatial streams of each user.
+        mcs_nss: [u8; 4],
+        /// The coding of each user.
+        coding: u8,
+        /// The group ID.
+        group_id: u8,
+        /// The partial AID.
+        partial_aid: U16,
+    }
+
+    /// The raw [Timestamp](../field/struct.Timestamp.html) field.
+    RawTimestamp => Timestamp {
+        /// The timestamp.
+        timestamp: U64,
+        /// The accuracy.
+        accuracy: U16,
+        /// The unit in the low nibble and sampling position in the high
+        /// nibble.
+        unit_position: u8,
+        /// The flags.
+        flags: u8,
+    }
+
+    /// The raw [HE](../field/struct.HE.html) field.
+    RawHE => HE {
+        /// The six data words.
+        data: [U16; 6],
+    }
+
+    /// The raw [HEMU](../field/struct.HEMU.html) field.
+    RawHEMU => HEMU {
+        /// The first flags word.
+        flags1: U16,
+        /// The second flags word.
+        flags2: U16,
+        /// The RU channel 1 values.
+        ru_channel1: [u8; 4],
+        /// The RU channel 2 values.
+        ru_channel2: [u8; 4],
+    }
+
+    /// The raw [HEMUOtherUser](../field/struct.HEMUOtherUser.html) field.
+    RawHEMUOtherUser => HEMUOtherUser {
+        /// The first per user word.
+        per_user_1: U16,
+        /// The second per user word.
+        per_user_2: U16,
+        /// The position of the user.
+        position: u8,
+        /// The known bits.
+        known: u8,
+    }
+
+    /// The raw [LSig](../field/struct.LSig.html) field.
+    RawLSig => LSig {
+        /// The first data word.
+        data1: U16,
+        /// The second data word.
+        data2: U16,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::{Kind, PresentField};
+
+    #[test]
+    fn layout() {
+        fn check<T: RawField>() {
+            let kind = <T::Field as PresentField>::KIND;
+            assert_eq!(core::mem::size_of::<T>(), kind.size(), "{:?}", kind);
+        }
+
+        check::<RawTSFT>();
+        check::<RawChannel>();
+        check::<RawFHSS>();
+        check::<RawXChannel>();
+        check::<RawMCS>();
+        check::<RawAMPDUStatus>();
+        check::<RawVHT>();
+        check::<RawTimestamp>();
+        check::<RawHE>();
+        check::<RawHEMU>();
+        check::<RawHEMUOtherUser>();
+        check::<RawLSig>();
+        assert_eq!(Kind::LSig, <RawLSig as RawField>::Field::KIND);
+    }
+
+    #[test]
+    fn decode() {
+        let data = [0x40, 0x01, 0x01, 0, 0x7c, 0x15, 0x64, 0x22, 0xff];
+        let raw = RawXChannel::from_data(&data).unwrap();
+        assert_eq!(raw.freq.get(), 5500);
+        assert_eq!(raw.channel, 100);
+        assert_eq!(raw.decode().unwrap(), XChannel::from_bytes(&data).unwrap());
+        assert!(matches!(
+            RawXChannel::from_data(&data[..7]),
+            Err(Error::IncompleteError)
+        ));
+    }
+}
@@ -0,0 +1,129 @@
+//! A lightweight summary of the values most applications need from a
+//! Radiotap capture.
+
+pub use crate::util::channel::Band;
+
+use crate::{util::channel, Radiotap};
+
+/// The PHY a frame was transmitted or received with.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Phy {
+    /// 802.11b DSSS or CCK.
+    DSSS,
+    /// 802.11a/g OFDM.
+    OFDM,
+    /// 802.11n.
+    HT,
+    /// 802.11ac.
+    VHT,
+    /// 802.11ax.
+    HE,
+}
+
+/// The handful of derived values most applications need from a Radiotap
+/// capture, see [Radiotap::summary](../struct.Radiotap.html#method.summary).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Summary {
+    /// The frequency in MHz.
+    pub freq: Option<u16>,
+    /// The channel number.
+    pub channel: Option<u8>,
+    /// The frequency band.
+    pub band: Option<Band>,
+    /// The RF signal power at the antenna in dBm.
+    pub signal_dbm: Option<i8>,
+    /// The RF noise power at the antenna in dBm.
+    pub noise_dbm: Option<i8>,
+    /// The data rate in Mbps.
+    pub rate_mbps: Option<f32>,
+    /// The PHY used.
+    pub phy: Option<Phy>,
+    /// The number of data retries.
+    pub retries: Option<u8>,
+}
+
+impl Summary {
+    /// Returns the summary of a parsed [Radiotap](../struct.Radiotap.html).
+    pub fn new(radiotap: &Radiotap) -> Summary {
+        let freq = radiotap
+            .xchannel
+            .map(|xchannel| xchannel.freq)
+            .or_else(|| radiotap.channel.map(|channel| channel.freq));
+
+        let channel = radiotap
+            .xchannel
+            .map(|xchannel| xchannel.channel)
+            .or_else(|| freq.and_then(channel::to_channel));
+
+        let (phy, rate_mbps) = if let Some(he) = radiotap.he {
+            (Some(Phy::HE), he.datarate())
+        } else if let Some(vht) = radiotap.vht {
+            let user = vht.users.iter().flatten().next();
+            (Some(Phy::VHT), user.and_then(|user| user.datarate))
+        } else if let Some(mcs) = radiotap.mcs {
+            (Some(Phy::HT), mcs.datarate)
+        } else if let Some(rate) = radiotap.rate {
+            let phy = if is_dsss_rate(rate.as_mbps()) {
+                Phy::DSSS
+            } else {
+                Phy::OFDM
+            };
+            (Some(phy), Some(rate.as_mbps()))
+        } else {
+            (None, None)
+        };
+
+        Summary {
+            freq,
+            channel,
+            band: radiotap.band(),
+            signal_dbm: radiotap.antenna_signal.map(|signal| signal.value),
+            noise_dbm: radiotap.antenna_noise.map(|noise| noise.value),
+            rate_mbps,
+            phy,
+            retries: radiotap.data_retries.map(|retries| retries.value),
+        }
+    }
+}
+
+/// Whether the legacy rate is one of the 802.11b rates.
+pub(crate) fn is_dsss_rate(rate: f32) -> bool {
+    [1.0, 2.0, 5.5, 11.0].contains(&rate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vht_summary() {
+        let capture = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+
+        let summary = Radiotap::from_bytes(&capture).unwrap().summary();
+        assert_eq!(summary.freq, Some(5500));
+        assert_eq!(summary.channel, Some(100));
+        assert_eq!(summary.band, Some(Band::Band5GHz));
+        assert_eq!(summary.signal_dbm, Some(-43));
+        assert_eq!(summary.noise_dbm, Some(-90));
+        assert_eq!(summary.phy, Some(Phy::VHT));
+        assert!(summary.rate_mbps.is_some());
+        assert_eq!(summary.retries, None);
+    }
+
+    #[test]
+    fn he_summary() {
+        let capture = [
+            0, 0, 20, 0, 0, 0, 128, 0, 0xa6, 0x46, 0x86, 0x00, 0x2a, 0xa7, 0x05, 0x00, 0x22, 0x02,
+            0x02, 0x80,
+        ];
+
+        let summary = Radiotap::from_bytes(&capture).unwrap().summary();
+        assert_eq!(summary.phy, Some(Phy::HE));
+        assert_eq!(summary.rate_mbps, Some(306.3));
+    }
+}
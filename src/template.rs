@@ -0,0 +1,164 @@
+//! A pre-serialized Radiotap header whose fields can be changed in place, for
+//! injecting many frames with nearly identical headers.
+
+use alloc::vec::Vec;
+
+use crate::{field::*, Error, Radiotap, RadiotapIterator, Result};
+
+/// A serialized [Radiotap](struct.Radiotap.html) capture whose fields are
+/// patched directly in the bytes, without serializing the whole capture again.
+///
+/// Only the fields set when the template is built can be patched.
+///
+/// ```
+/// use radiotap::{field::*, RadiotapBuilder, Template};
+///
+/// let radiotap = RadiotapBuilder::new()
+///     .rate(Rate::from_mbps(6.0).unwrap())
+///     .tx_power(TxPower { value: 10 })
+///     .done();
+///
+/// let mut template = Template::new(&radiotap).unwrap();
+/// template.patch_rate(Rate::from_mbps(54.0).unwrap()).unwrap();
+/// template.patch_tx_power(TxPower { value: 20 }).unwrap();
+/// assert_eq!(template.as_bytes(), [0, 0, 10, 0, 4, 4, 0, 0, 108, 20]);
+///
+/// assert!(template.patch_antenna(Antenna { value: 1 }).is_err());
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Template {
+    bytes: Vec<u8>,
+    /// The start and end of the data of each field of the first radiotap
+    /// namespace, by present bit.
+    fields: [Option<(usize, usize)>; 28],
+}
+
+impl Template {
+    /// Serializes the capture once and records where each of its fields is.
+    pub fn new(radiotap: &Radiotap) -> Result<Template> {
+        let bytes = radiotap.to_bytes()?;
+
+        let mut fields = [None; 28];
+        let iterator = RadiotapIterator::from_bytes(&bytes)?;
+        for result in &iterator {
+            let (kind, data) = result?;
+            match kind {
+                Kind::RadiotapNamespace => break,
+                kind if kind.bit() < 28 => {
+                    let start = data.as_ptr() as usize - bytes.as_ptr() as usize;
+                    fields[usize::from(kind.bit())] = Some((start, start + data.len()));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Template { bytes, fields })
+    }
+
+    /// Returns the serialized capture.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Overwrites a field of the capture. Fails with an `UnsupportedField`
+    /// error if the field was not set when the template was built, or is
+    /// written as a TLV record, like the U-SIG and EHT fields.
+    pub fn patch<T: PresentField>(&mut self, field: &T) -> Result<()> {
+        let (start, end) = self
+            .fields
+            .get(usize::from(T::KIND.bit()))
+            .copied()
+            .flatten()
+            .ok_or(Error::UnsupportedField)?;
+        let mut data = &mut self.bytes[start..end];
+        field.unparse(&mut data)?;
+        Ok(())
+    }
+
+    /// Overwrites the [Rate](field/struct.Rate.html) field.
+    pub fn patch_rate(&mut self, rate: Rate) -> Result<()> {
+        self.patch(&rate)
+    }
+
+    /// Overwrites the [TxPower](field/struct.TxPower.html) field.
+    pub fn patch_tx_power(&mut self, tx_power: TxPower) -> Result<()> {
+        self.patch(&tx_power)
+    }
+
+    /// Overwrites the [Antenna](field/struct.Antenna.html) field.
+    pub fn patch_antenna(&mut self, antenna: Antenna) -> Result<()> {
+        self.patch(&antenna)
+    }
+
+    /// Overwrites the [TxFlags](field/struct.TxFlags.html) field.
+    pub fn patch_tx_flags(&mut self, tx_flags: TxFlags) -> Result<()> {
+        self.patch(&tx_flags)
+    }
+
+    /// Overwrites the [DataRetries](field/struct.DataRetries.html) field.
+    pub fn patch_data_retries(&mut self, data_retries: DataRetries) -> Result<()> {
+        self.patch(&data_retries)
+    }
+
+    /// Overwrites the [MCS](field/struct.MCS.html) field.
+    pub fn patch_mcs(&mut self, mcs: MCS) -> Result<()> {
+        self.patch(&mcs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RadiotapBuilder;
+
+    #[test]
+    fn patch() {
+        let radiotap = RadiotapBuilder::new()
+            .tsft(TSFT { value: 1 })
+            .rate(Rate::from_mbps(6.0).unwrap())
+            .tx_flags(from_bytes(&[0, 0]).unwrap())
+            .data_retries(DataRetries { value: 0 })
+            .done();
+        let mut template = Template::new(&radiotap).unwrap();
+
+        let tx_flags = from_bytes(&[0x08, 0]).unwrap();
+        template.patch_rate(Rate::from_mbps(1.0).unwrap()).unwrap();
+        template.patch_tx_flags(tx_flags).unwrap();
+        template
+            .patch_data_retries(DataRetries { value: 3 })
+            .unwrap();
+
+        let patched = RadiotapBuilder::new()
+            .tsft(TSFT { value: 1 })
+            .rate(Rate::from_mbps(1.0).unwrap())
+            .tx_flags(tx_flags)
+            .data_retries(DataRetries { value: 3 })
+            .done();
+        let mut bytes = Vec::new();
+        patched.unparse(&mut bytes).unwrap();
+        assert_eq!(template.as_bytes(), &bytes[..]);
+
+        assert!(matches!(
+            template.patch_tx_power(TxPower { value: 1 }),
+            Err(Error::UnsupportedField)
+        ));
+    }
+
+    #[test]
+    fn patch_tlv() {
+        let radiotap = RadiotapBuilder::new()
+            .rate(Rate::from_mbps(6.0).unwrap())
+            .u_sig(USig::default())
+            .done();
+        let mut template = Template::new(&radiotap).unwrap();
+
+        assert!(matches!(
+            template.patch(&USig::default()),
+            Err(Error::UnsupportedField)
+        ));
+        assert!(matches!(
+            template.patch(&EHT::default()),
+            Err(Error::UnsupportedField)
+        ));
+    }
+}
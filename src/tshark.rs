@@ -0,0 +1,382 @@
+//! Import of Radiotap captures from the JSON output of tshark.
+//!
+//! Enabled with the `tshark` feature. The radiotap layer of each packet
+//! written by `tshark -T json` is turned back into a
+//! [Radiotap](../struct.Radiotap.html), for example to replay archived
+//! captures. Values are read from the raw fields, such as `radiotap.flags`
+//! and `radiotap.channel.flags`, which tshark writes next to their decoded
+//! trees. Fields that Wireshark does not decode, such as the HE and EHT
+//! fields and vendor namespaces, cannot be imported.
+//!
+//! # Examples
+//!
+//! ```
+//! let json = r#"[{
+//!     "_source": {"layers": {"radiotap": {
+//!         "radiotap.version": "0",
+//!         "radiotap.flags": "0x00000010",
+//!         "radiotap.channel.freq": "2412",
+//!         "radiotap.channel.flags": "0x000000a0",
+//!         "radiotap.dbm_antsignal": "-42"
+//!     }}}
+//! }]"#;
+//!
+//! let radiotaps = radiotap::tshark::from_str(json).unwrap();
+//! assert_eq!(radiotaps[0].antenna_signal.unwrap().value, -42);
+//! assert!(radiotaps[0].flags.unwrap().fcs);
+//! ```
+
+use std::{collections::BTreeMap, convert::TryFrom};
+
+use serde_json::Value;
+
+use crate::{
+    field::{from_bytes, VHT},
+    Error, Radiotap, RadiotapBuilder, Result,
+};
+
+impl Radiotap {
+    /// Returns the Radiotap capture of a single packet of the JSON output of
+    /// `tshark -T json`, see the [tshark](tshark/index.html) module.
+    ///
+    /// The JSON may be a packet, an array holding a single packet, or just
+    /// its radiotap layer.
+    pub fn from_tshark_json(json: &str) -> Result<Radiotap> {
+        let value = parse(json)?;
+        match value {
+            Value::Array(packets) if packets.len() == 1 => from_value(&packets[0]),
+            Value::Array(_) => Err(invalid("expected a single packet")),
+            value => from_value(&value),
+        }
+    }
+}
+
+/// Returns the Radiotap captures of all packets of the JSON output of
+/// `tshark -T json`.
+pub fn from_str(json: &str) -> Result<Vec<Radiotap>> {
+    match parse(json)? {
+        Value::Array(packets) => {
+            let mut radiotaps = Vec::new();
+            radiotaps.try_reserve(packets.len())?;
+            for packet in &packets {
+                radiotaps.push(from_value(packet)?);
+            }
+            Ok(radiotaps)
+        }
+        value => Ok(vec![from_value(&value)?]),
+    }
+}
+
+/// Returns the Radiotap capture of a packet of the JSON output of
+/// `tshark -T json`, or of just its radiotap layer.
+pub fn from_value(packet: &Value) -> Result<Radiotap> {
+    let layer = match packet
+        .pointer("/_source/layers")
+        .or_else(|| packet.get("layers"))
+    {
+        Some(layers) => layers.get("radiotap"),
+        None => Some(packet),
+    };
+    let layer = match layer {
+        Some(layer) if layer.is_object() => layer,
+        _ => return Err(invalid("the packet has no radiotap layer")),
+    };
+    let mut fields = Fields(BTreeMap::new());
+    fields.flatten(layer);
+    fields.builder().map(RadiotapBuilder::done)
+}
+
+fn parse(json: &str) -> Result<Value> {
+    serde_json::from_str(json).map_err(|e| Error::InvalidTsharkJson(e.to_string()))
+}
+
+fn invalid(message: &str) -> Error {
+    Error::InvalidTsharkJson(message.to_string())
+}
+
+/// The fields of a radiotap layer, by name.
+struct Fields<'a>(BTreeMap<&'a str, &'a Value>);
+
+impl<'a> Fields<'a> {
+    /// Adds the fields of an object and the trees nested in it, keeping the
+    /// first value of a repeated field.
+    fn flatten(&mut self, object: &'a Value) {
+        if let Value::Object(members) = object {
+            for (name, value) in members {
+                if value.is_object() {
+                    self.flatten(value);
+                } else {
+                    self.0.entry(name).or_insert(value);
+                }
+            }
+        }
+    }
+
+    /// Returns the integer value of a field, written by tshark as a decimal or
+    /// hexadecimal string.
+    fn get<T: TryFrom<i128>>(&self, name: &str) -> Result<Option<T>> {
+        let mut value = match self.0.get(name) {
+            Some(value) => *value,
+            None => return Ok(None),
+        };
+        if let Value::Array(values) = value {
+            match values.first() {
+                Some(first) => value = first,
+                None => return Ok(None),
+            }
+        }
+        let integer = match value {
+            Value::String(s) => match s.strip_prefix("0x") {
+                Some(hex) => i128::from_str_radix(hex, 16).ok(),
+                None => s.parse().ok(),
+            },
+            Value::Number(n) => n.as_i64().map(i128::from),
+            Value::Bool(b) => Some(i128::from(*b)),
+            _ => None,
+        };
+        integer
+            .and_then(|integer| T::try_from(integer).ok())
+            .map(Some)
+            .ok_or_else(|| invalid(&format!("{} is not a valid integer", name)))
+    }
+
+    /// Returns the floating point value of a field.
+    fn float(&self, name: &str) -> Result<Option<f64>> {
+        match self.0.get(name) {
+            None => Ok(None),
+            Some(Value::String(s)) => s
+                .parse()
+                .map(Some)
+                .map_err(|_| invalid(&format!("{} is not a valid number", name))),
+            Some(Value::Number(n)) => Ok(n.as_f64()),
+            Some(_) => Err(invalid(&format!("{} is not a valid number", name))),
+        }
+    }
+
+    fn builder(&self) -> Result<RadiotapBuilder> {
+        let mut builder = RadiotapBuilder::new();
+
+        if let Some(mactime) = self.get::<u64>("radiotap.mactime")? {
+            builder = builder.tsft(from_bytes(&mactime.to_le_bytes())?);
+        }
+        if let Some(flags) = self.get::<u8>("radiotap.flags")? {
+            builder = builder.flags(from_bytes(&[flags])?);
+        }
+        if let Some(rate) = self.float("radiotap.datarate")? {
+            // The rate is in 500 kb/s units
+            let rate = u8::try_from((rate * 2.0).round() as i64)
+                .map_err(|_| invalid("radiotap.datarate is out of range"))?;
+            builder = builder.rate(from_bytes(&[rate])?);
+        }
+        if let Some(freq) = self.get::<u16>("radiotap.channel.freq")? {
+            let flags = self.get::<u16>("radiotap.channel.flags")?.unwrap_or(0);
+            let mut data = freq.to_le_bytes().to_vec();
+            data.extend_from_slice(&flags.to_le_bytes());
+            builder = builder.channel(from_bytes(&data)?);
+        }
+        if let Some(hopset) = self.get::<u8>("radiotap.fhss.hopset")? {
+            let pattern = self.get::<u8>("radiotap.fhss.pattern")?.unwrap_or(0);
+            builder = builder.fhss(from_bytes(&[hopset, pattern])?);
+        }
+        if let Some(signal) = self.get::<i8>("radiotap.dbm_antsignal")? {
+            builder = builder.antenna_signal(from_bytes(&signal.to_le_bytes())?);
+        }
+        if let Some(noise) = self.get::<i8>("radiotap.dbm_antnoise")? {
+            builder = builder.antenna_noise(from_bytes(&noise.to_le_bytes())?);
+        }
+        if let Some(quality) = self.get::<u16>("radiotap.quality")? {
+            builder = builder.lock_quality(from_bytes(&quality.to_le_bytes())?);
+        }
+        if let Some(attenuation) = self.get::<u16>("radiotap.txattenuation")? {
+            builder = builder.tx_attenuation(from_bytes(&attenuation.to_le_bytes())?);
+        }
+        if let Some(attenuation) = self.get::<u16>("radiotap.db_txattenuation")? {
+            builder = builder.tx_attenuation_db(from_bytes(&attenuation.to_le_bytes())?);
+        }
+        if let Some(power) = self.get::<i8>("radiotap.dbm_tx_power")? {
+            builder = builder.tx_power(from_bytes(&power.to_le_bytes())?);
+        }
+        if let Some(antenna) = self.get::<u8>("radiotap.antenna")? {
+            builder = builder.antenna(from_bytes(&[antenna])?);
+        }
+        if let Some(signal) = self.get::<u8>("radiotap.db_antsignal")? {
+            builder = builder.antenna_signal_db(from_bytes(&[signal])?);
+        }
+        if let Some(noise) = self.get::<u8>("radiotap.db_antnoise")? {
+            builder = builder.antenna_noise_db(from_bytes(&[noise])?);
+        }
+        if let Some(flags) = self.get::<u16>("radiotap.rxflags")? {
+            builder = builder.rx_flags(from_bytes(&flags.to_le_bytes())?);
+        }
+        if let Some(flags) = self.get::<u16>("radiotap.txflags")? {
+            builder = builder.tx_flags(from_bytes(&flags.to_le_bytes())?);
+        }
+        if let Some(retries) = self.get::<u8>("radiotap.rts_retries")? {
+            builder = builder.rts_retries(from_bytes(&[retries])?);
+        }
+        if let Some(retries) = self.get::<u8>("radiotap.data_retries")? {
+            builder = builder.data_retries(from_bytes(&[retries])?);
+        }
+        if let Some(flags) = self.get::<u32>("radiotap.xchannel.flags")? {
+            let mut data = flags.to_le_bytes().to_vec();
+            data.extend_from_slice(
+                &self
+                    .get::<u16>("radiotap.xchannel.freq")?
+                    .unwrap_or(0)
+                    .to_le_bytes(),
+            );
+            data.push(self.get::<u8>("radiotap.xchannel.channel")?.unwrap_or(0));
+            data.push(self.get::<u8>("radiotap.xchannel.max_power")?.unwrap_or(0));
+            builder = builder.xchannel(from_bytes(&data)?);
+        }
+        if let Some(known) = self.get::<u8>("radiotap.mcs.known")? {
+            let flags = self.get::<u8>("radiotap.mcs.flags")?.unwrap_or(0);
+            let index = self.get::<u8>("radiotap.mcs.index")?.unwrap_or(0);
+            builder = builder.mcs(from_bytes(&[known, flags, index])?);
+        }
+        if let Some(reference) = self.get::<u32>("radiotap.ampdu.reference")? {
+            let mut data = reference.to_le_bytes().to_vec();
+            data.extend_from_slice(
+                &self
+                    .get::<u16>("radiotap.ampdu.flags")?
+                    .unwrap_or(0)
+                    .to_le_bytes(),
+            );
+            data.push(self.get::<u8>("radiotap.ampdu.delim_crc")?.unwrap_or(0));
+            data.push(0);
+            builder = builder.ampdu_status(from_bytes(&data)?);
+        }
+        if let Some(known) = self.get::<u16>("radiotap.vht.known")? {
+            builder = builder.vht(self.vht(known)?);
+        }
+        if let Some(timestamp) = self.get::<u64>("radiotap.timestamp.ts")? {
+            let mut data = timestamp.to_le_bytes().to_vec();
+            data.extend_from_slice(
+                &self
+                    .get::<u16>("radiotap.timestamp.accuracy")?
+                    .unwrap_or(0)
+                    .to_le_bytes(),
+            );
+            let unit = self.get::<u8>("radiotap.timestamp.unit")?.unwrap_or(0);
+            let position = self
+                .get::<u8>("radiotap.timestamp.samplingpos")?
+                .unwrap_or(0);
+            data.push(unit & 0x0f | position << 4);
+            data.push(self.get::<u8>("radiotap.timestamp.flags")?.unwrap_or(0));
+            builder = builder.timestamp(from_bytes(&data)?);
+        }
+
+        Ok(builder)
+    }
+
+    fn vht(&self, known: u16) -> Result<VHT> {
+        let mut data = known.to_le_bytes().to_vec();
+        data.push(self.get::<u8>("radiotap.vht.flags")?.unwrap_or(0));
+        data.push(self.get::<u8>("radiotap.vht.bw")?.unwrap_or(0));
+        let mut coding = 0;
+        for user in 0..4 {
+            let mcs = self
+                .get::<u8>(&format!("radiotap.vht.mcs.{}", user))?
+                .unwrap_or(0);
+            let nss = self
+                .get::<u8>(&format!("radiotap.vht.nss.{}", user))?
+                .unwrap_or(0);
+            data.push(mcs << 4 | nss & 0x0f);
+            if self
+                .get::<u8>(&format!("radiotap.vht.coding.{}", user))?
+                .unwrap_or(0)
+                != 0
+            {
+                coding |= 1 << user;
+            }
+        }
+        data.push(coding);
+        data.push(self.get::<u8>("radiotap.vht.gid")?.unwrap_or(0));
+        data.extend_from_slice(
+            &self
+                .get::<u16>("radiotap.vht.partial_aid")?
+                .unwrap_or(0)
+                .to_le_bytes(),
+        );
+        from_bytes(&data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vht() {
+        let frame = [
+            0, 0, 56, 0, 107, 8, 52, 0, 185, 31, 155, 154, 0, 0, 0, 0, 20, 0, 124, 21, 64, 1, 213,
+            166, 1, 0, 0, 0, 64, 1, 1, 0, 124, 21, 100, 34, 249, 1, 0, 0, 0, 0, 0, 0, 255, 1, 80,
+            4, 115, 0, 0, 0, 1, 63, 0, 0,
+        ];
+        let expected = Radiotap::from_bytes(&frame).unwrap();
+
+        // Abridged from tshark 4.2 output for the same frame
+        let json = r#"{
+            "radiotap.version": "0",
+            "radiotap.pad": "0",
+            "radiotap.length": "56",
+            "radiotap.present_tree": {"radiotap.present.word": "0x0034086b"},
+            "radiotap.mactime": "2593857465",
+            "radiotap.flags": "0x14",
+            "radiotap.flags_tree": {"radiotap.flags.fcs": "1", "radiotap.flags.datapad": "1"},
+            "radiotap.channel.freq": "5500",
+            "radiotap.channel.flags": "0x0140",
+            "radiotap.channel.flags_tree": {"radiotap.channel.flags.5ghz": "1"},
+            "radiotap.dbm_antsignal": ["-43", "-42"],
+            "radiotap.antenna": "1",
+            "radiotap.dbm_antnoise": "-90",
+            "radiotap.xchannel.flags": "0x00010140",
+            "radiotap.xchannel.freq": "5500",
+            "radiotap.xchannel.channel": "100",
+            "radiotap.xchannel.max_power": "34",
+            "radiotap.ampdu.reference": "505",
+            "radiotap.ampdu.flags": "0x0000",
+            "radiotap.ampdu.delim_crc": "0",
+            "radiotap.vht.known": "0x01ff",
+            "radiotap.vht.flags": "0x50",
+            "radiotap.vht.bw": "4",
+            "radiotap.vht.user": {
+                "radiotap.vht.mcs.0": "7",
+                "radiotap.vht.nss.0": "3",
+                "radiotap.vht.coding.0": "1"
+            },
+            "radiotap.vht.gid": "63",
+            "radiotap.vht.partial_aid": "0"
+        }"#;
+        let radiotap = Radiotap::from_tshark_json(json).unwrap();
+        assert_eq!(radiotap.tsft, expected.tsft);
+        assert_eq!(radiotap.flags, expected.flags);
+        assert_eq!(radiotap.channel, expected.channel);
+        assert_eq!(radiotap.antenna_signal, expected.antenna_signal);
+        assert_eq!(radiotap.antenna_noise, expected.antenna_noise);
+        assert_eq!(radiotap.antenna, expected.antenna);
+        assert_eq!(radiotap.xchannel, expected.xchannel);
+        assert_eq!(radiotap.ampdu_status, expected.ampdu_status);
+        assert_eq!(radiotap.vht, expected.vht);
+    }
+
+    #[test]
+    fn packets() {
+        let json = r#"[
+            {"_source": {"layers": {"frame": {}, "radiotap": {"radiotap.datarate": "5.5"}}}},
+            {"_source": {"layers": {"radiotap": {"radiotap.dbm_tx_power": "10"}}}}
+        ]"#;
+        let radiotaps = from_str(json).unwrap();
+        assert_eq!(radiotaps[0].rate.unwrap().as_mbps(), 5.5);
+        assert_eq!(radiotaps[1].tx_power.unwrap().value, 10);
+        assert!(Radiotap::from_tshark_json(json).is_err());
+
+        let json = r#"{"radiotap.dbm_antsignal": "-200"}"#;
+        assert!(matches!(
+            Radiotap::from_tshark_json(json),
+            Err(Error::InvalidTsharkJson(_))
+        ));
+        let json = r#"{"_source": {"layers": {"frame": {}}}}"#;
+        assert!(Radiotap::from_tshark_json(json).is_err());
+    }
+}
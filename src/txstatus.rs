@@ -0,0 +1,155 @@
+//! Pairing of transmitted frames with the TX status captures reported for them
+//! on a monitor interface.
+//!
+//! When injecting frames, most drivers report a TX status capture containing
+//! the transmitted frame and a Radiotap header with the retries used and
+//! whether the frame was acknowledged. A [TxMatcher](struct.TxMatcher.html)
+//! matches these by 802.11 sequence number within a time window.
+
+use alloc::{collections::VecDeque, vec::Vec};
+
+use crate::Radiotap;
+
+/// Returns the 802.11 sequence number of a frame, or `None` if the frame is a
+/// control frame or too short.
+pub fn sequence_number(frame: &[u8]) -> Option<u16> {
+    // Control frames do not carry a sequence control field
+    if frame.len() < 24 || (frame[0] >> 2) & 0x03 == 1 {
+        return None;
+    }
+    Some(u16::from_le_bytes([frame[22], frame[23]]) >> 4)
+}
+
+/// A transmitted frame combined with its TX status capture.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TxRecord<T> {
+    /// The transmitted frame, as passed to
+    /// [transmitted](struct.TxMatcher.html#method.transmitted).
+    pub frame: T,
+    /// The Radiotap header of the TX status capture.
+    pub status: Radiotap,
+    /// The time between transmission and the TX status capture.
+    pub delay: u64,
+}
+
+impl<T> TxRecord<T> {
+    /// Returns whether the frame was acknowledged, if the driver reported it.
+    pub fn acked(&self) -> Option<bool> {
+        self.status.tx_flags.map(|flags| !flags.fail)
+    }
+
+    /// Returns the number of data retries used, if the driver reported it.
+    pub fn retries(&self) -> Option<u8> {
+        self.status.data_retries.map(|retries| retries.value)
+    }
+}
+
+/// Matches transmitted frames with their TX status captures.
+///
+/// Times are in arbitrary but consistent units, usually microseconds.
+#[derive(Clone, Debug)]
+pub struct TxMatcher<T> {
+    pending: VecDeque<(u16, u64, T)>,
+    timeout: u64,
+}
+
+impl<T> TxMatcher<T> {
+    /// Returns a new matcher that gives up on a transmitted frame when no TX
+    /// status is received within `timeout`.
+    pub fn new(timeout: u64) -> TxMatcher<T> {
+        TxMatcher {
+            pending: VecDeque::new(),
+            timeout,
+        }
+    }
+
+    /// Records a transmitted frame with its 802.11 sequence number and the time
+    /// it was sent.
+    pub fn transmitted(&mut self, seq: u16, time: u64, frame: T) {
+        self.pending.push_back((seq, time, frame));
+    }
+
+    /// Matches a TX status capture, consisting of the parsed Radiotap header and
+    /// the 802.11 frame that followed it, against the oldest pending frame with
+    /// the same sequence number sent within the timeout.
+    pub fn status(&mut self, status: Radiotap, frame: &[u8], time: u64) -> Option<TxRecord<T>> {
+        let seq = sequence_number(frame)?;
+        let timeout = self.timeout;
+        let index = self
+            .pending
+            .iter()
+            .position(|&(s, sent, _)| s == seq && sent <= time && time - sent <= timeout)?;
+        let (_, sent, frame) = self.pending.remove(index)?;
+        Some(TxRecord {
+            frame,
+            status,
+            delay: time - sent,
+        })
+    }
+
+    /// Removes and returns the pending frames that were sent more than the
+    /// timeout before `now` without receiving a TX status.
+    pub fn expire(&mut self, now: u64) -> Vec<T> {
+        let timeout = self.timeout;
+        let mut expired = Vec::new();
+        while let Some(&(_, sent, _)) = self.pending.front() {
+            if now.saturating_sub(sent) <= timeout {
+                break;
+            }
+            if let Some((_, _, frame)) = self.pending.pop_front() {
+                expired.push(frame);
+            }
+        }
+        expired
+    }
+
+    /// Returns the number of transmitted frames still waiting for a TX status.
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::field::{DataRetries, TxFlags};
+
+    fn frame(seq: u16) -> Vec<u8> {
+        let mut frame = vec![0x08, 0x00];
+        frame.resize(22, 0);
+        frame.extend_from_slice(&(seq << 4).to_le_bytes());
+        frame
+    }
+
+    #[test]
+    fn matching() {
+        let mut matcher = TxMatcher::new(1000);
+        matcher.transmitted(7, 100, "first");
+        matcher.transmitted(8, 150, "second");
+        matcher.transmitted(9, 200, "third");
+
+        let status = Radiotap {
+            tx_flags: Some(TxFlags {
+                fail: false,
+                cts: false,
+                rts: false,
+                no_ack: false,
+                no_seq: false,
+            }),
+            data_retries: Some(DataRetries { value: 2 }),
+            ..Default::default()
+        };
+
+        let record = matcher.status(status, &frame(8), 400).unwrap();
+        assert_eq!(record.frame, "second");
+        assert_eq!(record.delay, 250);
+        assert_eq!(record.acked(), Some(true));
+        assert_eq!(record.retries(), Some(2));
+
+        assert!(matcher
+            .status(Radiotap::default(), &frame(10), 400)
+            .is_none());
+        assert_eq!(matcher.expire(1150), vec!["first"]);
+        assert_eq!(matcher.pending(), 1);
+    }
+}
@@ -0,0 +1,220 @@
+//! Conversion between frequencies and channel numbers.
+//!
+//! The conversions follow the channelization of each band, so the frequency
+//! of a [Channel](../../field/struct.Channel.html) field can be turned into a
+//! channel number and band:
+//!
+//! ```
+//! use radiotap::util::channel::{self, Band};
+//!
+//! let channel = channel::from_freq(5180).unwrap();
+//! assert_eq!(channel.to_string(), "channel 36, 5 GHz");
+//! assert_eq!(channel::to_freq(1, Band::Band6GHz), Some(5955));
+//! ```
+
+use core::fmt;
+
+/// The frequency band a frame was transmitted or received on.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Band {
+    /// Sub 1 GHz (802.11ah).
+    S1G,
+    /// 2.4 GHz.
+    Band2GHz,
+    /// 5 GHz.
+    Band5GHz,
+    /// 6 GHz.
+    Band6GHz,
+    /// 60 GHz (802.11ad/ay).
+    Band60GHz,
+}
+
+impl Band {
+    /// Returns the band containing the given frequency in MHz.
+    pub fn from_freq(freq: u16) -> Option<Band> {
+        Some(match freq {
+            700..=1000 => Band::S1G,
+            2400..=2500 => Band::Band2GHz,
+            4900..=5900 => Band::Band5GHz,
+            5925..=7125 => Band::Band6GHz,
+            57000..=u16::MAX => Band::Band60GHz,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for Band {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Band::S1G => "sub 1 GHz",
+            Band::Band2GHz => "2.4 GHz",
+            Band::Band5GHz => "5 GHz",
+            Band::Band6GHz => "6 GHz",
+            Band::Band60GHz => "60 GHz",
+        })
+    }
+}
+
+/// A channel number in a band, displayed as `channel 36, 5 GHz`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BandChannel {
+    /// The channel number.
+    pub number: u8,
+    /// The band of the channel.
+    pub band: Band,
+}
+
+impl fmt::Display for BandChannel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "channel {}, {}", self.number, self.band)
+    }
+}
+
+/// Returns the channel number and band of the given frequency in MHz.
+pub fn from_freq(freq: u16) -> Option<BandChannel> {
+    Some(BandChannel {
+        number: to_channel(freq)?,
+        band: Band::from_freq(freq)?,
+    })
+}
+
+/// Returns the channel number of the given frequency in MHz, or `None` if
+/// the frequency is not the center of a channel.
+///
+/// The 4.9 GHz public safety channels are numbered like in the 5 GHz band.
+pub fn to_channel(freq: u16) -> Option<u8> {
+    let (base, spacing) = match freq {
+        2484 => return Some(14),
+        5935 => return Some(2),
+        2412..=2472 => (2407, 5),
+        4910..=4980 => (4000, 5),
+        5005..=5900 => (5000, 5),
+        5955..=7115 => (5950, 5),
+        58320..=u16::MAX => (56160, 2160),
+        _ => return None,
+    };
+    // Frequencies between two channels have no number
+    if !(freq - base).is_multiple_of(spacing) {
+        return None;
+    }
+    Some(((freq - base) / spacing) as u8)
+}
+
+/// Returns the center frequency in MHz of the given channel number in a
+/// band.
+///
+/// 5 GHz channels 182 to 196 are the 4.9 GHz public safety channels, and
+/// channels above 180 are past the end of the band. Only
+/// 60 GHz channels 1 to 4 are returned, as higher channels do not fit the
+/// `u16` frequency of the Radiotap fields. Sub 1 GHz channels depend on the
+/// regulatory domain and are not converted.
+pub fn to_freq(channel: u8, band: Band) -> Option<u16> {
+    let channel = u16::from(channel);
+    Some(match (band, channel) {
+        (Band::Band2GHz, 14) => 2484,
+        (Band::Band2GHz, 1..=13) => 2407 + channel * 5,
+        (Band::Band5GHz, 182..=196) => 4000 + channel * 5,
+        (Band::Band5GHz, 1..=180) => 5000 + channel * 5,
+        (Band::Band6GHz, 2) => 5935,
+        (Band::Band6GHz, 1..=233) => 5950 + channel * 5,
+        (Band::Band60GHz, 1..=4) => 56160 + channel * 2160,
+        _ => return None,
+    })
+}
+
+/// Returns the center frequency in MHz of the channel of the given width
+/// containing the 20 MHz channel at the given frequency in MHz.
+///
+/// The 40, 80, and 160 MHz channels in the 5 and 6 GHz bands are fixed blocks
+/// of 20 MHz channels. In the 2.4 GHz band the secondary channel can be above
+/// or below, so only 20 MHz channels are converted.
+///
+/// # Examples
+///
+/// ```
+/// use radiotap::util::channel::center_freq;
+///
+/// // Channel 44 is in the 80 MHz channel 42
+/// assert_eq!(center_freq(5220, 80), Some(5210));
+/// assert_eq!(center_freq(5745, 40), Some(5755));
+/// assert_eq!(center_freq(2412, 40), None);
+/// ```
+pub fn center_freq(freq: u16, width: u16) -> Option<u16> {
+    if width == 20 {
+        return Some(freq);
+    }
+    if ![40, 80, 160].contains(&width) {
+        return None;
+    }
+    // The lower edge of the first block, channel 36 and 149 in the 5 GHz band
+    // and channel 1 in the 6 GHz band
+    let base = match freq {
+        5170..=5734 => 5170,
+        5735..=5894 => 5735,
+        5945..=7125 => 5945,
+        _ => return None,
+    };
+    Some(base + (freq - base) / width * width + width / 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channels() {
+        assert_eq!(to_channel(2412), Some(1));
+        assert_eq!(to_channel(2484), Some(14));
+        assert_eq!(to_channel(5180), Some(36));
+        assert_eq!(to_channel(5955), Some(1));
+        assert_eq!(to_channel(58320), Some(1));
+        assert_eq!(to_channel(3000), None);
+        assert_eq!(to_channel(5183), None);
+        assert_eq!(to_channel(2407), None);
+        assert_eq!(to_channel(5000), None);
+        assert_eq!(to_channel(5905), None);
+        assert_eq!(to_channel(60000), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let bands = [
+            (Band::Band2GHz, 1..=14),
+            (Band::Band5GHz, 1..=196),
+            (Band::Band6GHz, 1..=233),
+            (Band::Band60GHz, 1..=4),
+        ];
+        for (band, channels) in bands.iter().cloned() {
+            for channel in channels {
+                let freq = match (band, channel) {
+                    (Band::Band5GHz, 181) => {
+                        assert_eq!(to_freq(channel, band), None);
+                        continue;
+                    }
+                    _ => to_freq(channel, band).unwrap(),
+                };
+                let expected = BandChannel {
+                    number: channel,
+                    band,
+                };
+                assert_eq!(from_freq(freq), Some(expected), "{}", freq);
+            }
+        }
+        assert_eq!(to_freq(0, Band::Band2GHz), None);
+        assert_eq!(to_freq(5, Band::S1G), None);
+    }
+
+    #[test]
+    fn center_freqs() {
+        assert_eq!(center_freq(5180, 40), Some(5190));
+        assert_eq!(center_freq(5240, 80), Some(5210));
+        assert_eq!(center_freq(5500, 160), Some(5570));
+        assert_eq!(center_freq(5805, 80), Some(5775));
+        assert_eq!(center_freq(5745, 160), Some(5815));
+        assert_eq!(center_freq(6015, 160), Some(6025));
+        assert_eq!(center_freq(2437, 20), Some(2437));
+        assert_eq!(center_freq(5935, 40), None);
+        assert_eq!(center_freq(5180, 30), None);
+    }
+}
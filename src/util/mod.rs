@@ -0,0 +1,4 @@
+//! Utilities for interpreting the values of Radiotap fields.
+
+pub mod channel;
+pub mod power;
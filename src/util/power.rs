@@ -0,0 +1,158 @@
+//! Typed power units, so absolute power levels in dBm are not mixed up with
+//! relative levels in dB.
+//!
+//! The signal, noise, and transmit power fields return these types, and the
+//! arithmetic between them follows the units, for example the difference of
+//! two absolute levels is a relative level:
+//!
+//! ```
+//! use radiotap::util::power::{Db, Dbm};
+//!
+//! let snr = Dbm(-48.0) - Dbm(-90.0);
+//! assert_eq!(snr, Db(42.0));
+//! assert_eq!(Dbm(-48.0) - Db(3.0), Dbm(-51.0));
+//! assert_eq!(snr.to_string(), "42 dB");
+//! ```
+
+use core::{fmt, ops};
+
+/// An absolute power level in decibels relative to 1 mW.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Dbm(pub f32);
+
+/// A relative power level, or a ratio of powers, in decibels.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Db(pub f32);
+
+/// An absolute power level in milliwatts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Milliwatt(pub f32);
+
+impl From<i8> for Dbm {
+    fn from(value: i8) -> Dbm {
+        Dbm(f32::from(value))
+    }
+}
+
+impl From<u8> for Db {
+    fn from(value: u8) -> Db {
+        Db(f32::from(value))
+    }
+}
+
+impl From<u16> for Db {
+    fn from(value: u16) -> Db {
+        Db(f32::from(value))
+    }
+}
+
+/// Converts the power level to milliwatts, powers can only be added in
+/// milliwatts.
+#[cfg(feature = "std")]
+impl From<Dbm> for Milliwatt {
+    fn from(dbm: Dbm) -> Milliwatt {
+        Milliwatt(10f32.powf(dbm.0 / 10.0))
+    }
+}
+
+/// Converts the power level to dBm, a power of 0 mW is negative infinity.
+#[cfg(feature = "std")]
+impl From<Milliwatt> for Dbm {
+    fn from(mw: Milliwatt) -> Dbm {
+        Dbm(10.0 * mw.0.log10())
+    }
+}
+
+impl ops::Sub for Dbm {
+    type Output = Db;
+
+    fn sub(self, other: Dbm) -> Db {
+        Db(self.0 - other.0)
+    }
+}
+
+impl ops::Add<Db> for Dbm {
+    type Output = Dbm;
+
+    fn add(self, other: Db) -> Dbm {
+        Dbm(self.0 + other.0)
+    }
+}
+
+impl ops::Sub<Db> for Dbm {
+    type Output = Dbm;
+
+    fn sub(self, other: Db) -> Dbm {
+        Dbm(self.0 - other.0)
+    }
+}
+
+impl ops::Add for Db {
+    type Output = Db;
+
+    fn add(self, other: Db) -> Db {
+        Db(self.0 + other.0)
+    }
+}
+
+impl ops::Sub for Db {
+    type Output = Db;
+
+    fn sub(self, other: Db) -> Db {
+        Db(self.0 - other.0)
+    }
+}
+
+impl ops::Neg for Db {
+    type Output = Db;
+
+    fn neg(self) -> Db {
+        Db(-self.0)
+    }
+}
+
+impl ops::Add for Milliwatt {
+    type Output = Milliwatt;
+
+    fn add(self, other: Milliwatt) -> Milliwatt {
+        Milliwatt(self.0 + other.0)
+    }
+}
+
+impl fmt::Display for Dbm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} dBm", self.0)
+    }
+}
+
+impl fmt::Display for Db {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} dB", self.0)
+    }
+}
+
+impl fmt::Display for Milliwatt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} mW", self.0)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Milliwatt::from(Dbm(0.0)), Milliwatt(1.0));
+        assert_eq!(Milliwatt::from(Dbm(20.0)), Milliwatt(100.0));
+        assert_eq!(Dbm::from(Milliwatt(0.001)), Dbm(-30.0));
+
+        // Two equal signals add up to twice the power
+        let sum = Dbm::from(Milliwatt::from(Dbm(-50.0)) + Milliwatt::from(Dbm(-50.0)));
+        assert!(((sum - Dbm(-50.0)).0 - 3.0103).abs() < 0.001);
+
+        assert_eq!(Dbm::from(-70i8), Dbm(-70.0));
+        assert_eq!(Dbm(-70.0).to_string(), "-70 dBm");
+        assert_eq!(-Db(3.0), Db(-3.0));
+    }
+}
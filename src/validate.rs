@@ -0,0 +1,392 @@
+//! Strict validation of Radiotap captures, useful for driver developers
+//! checking the captures they emit.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use crate::{
+    field::{ext::vht_rate, Kind, PresentField, USig, EHT, VHT},
+    Error, FieldError, Radiotap, RadiotapIterator, RadiotapParser, Result,
+};
+
+/// A way in which a Radiotap capture does not follow the specification.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Violation {
+    /// A padding byte inserted to align a field is not zero.
+    NonZeroPadding {
+        /// The offset of the padding byte from the start of the capture.
+        offset: usize,
+    },
+    /// There are bytes after the last field that are still within the length
+    /// specified in the header.
+    TrailingData {
+        /// The offset of the first trailing byte from the start of the capture.
+        offset: usize,
+        /// The number of trailing bytes.
+        length: usize,
+    },
+    /// A field appears more than once in the same radiotap namespace, for
+    /// example both as a present bit and as a TLV record.
+    DuplicateField {
+        /// The type of field.
+        kind: Kind,
+    },
+    /// The value of a field is not valid.
+    InvalidField {
+        /// The type of field.
+        kind: Kind,
+        /// Why the value is not valid.
+        reason: String,
+    },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Violation::NonZeroPadding { offset } => {
+                write!(f, "non-zero padding byte at offset {}", offset)
+            }
+            Violation::TrailingData { offset, length } => {
+                write!(f, "{} trailing bytes at offset {}", length, offset)
+            }
+            Violation::DuplicateField { kind } => write!(f, "duplicate {:?} field", kind),
+            Violation::InvalidField { kind, reason } => {
+                write!(f, "invalid {:?} field: {}", kind, reason)
+            }
+        }
+    }
+}
+
+/// Displays the first of a list of violations, and how many follow it.
+pub(crate) struct ViolationList<'a>(pub(crate) &'a [Violation]);
+
+impl fmt::Display for ViolationList<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            [] => write!(f, "no violations"),
+            [violation] => write!(f, "{}", violation),
+            [violation, rest @ ..] => write!(f, "{} and {} more", violation, rest.len()),
+        }
+    }
+}
+
+/// Returns the ways in which a Radiotap capture does not follow the
+/// specification, in the order they appear in the capture.
+///
+/// An error is returned if the capture cannot be parsed at all, or a field
+/// cannot be located.
+///
+/// # Examples
+///
+/// ```
+/// use radiotap::validate::{validate, Violation};
+///
+/// // The antenna noise is followed by a byte not described by the header
+/// let capture = [0, 0, 11, 0, 0x60, 0, 0, 0, 0xd6, 0xa0, 0xff];
+/// assert_eq!(
+///     validate(&capture).unwrap(),
+///     [Violation::TrailingData {
+///         offset: 10,
+///         length: 1
+///     }]
+/// );
+/// ```
+pub fn validate(input: &[u8]) -> Result<Vec<Violation>> {
+    let (iterator, rest) = RadiotapIterator::parse(input)?;
+    let parser = RadiotapParser::builder().lenient(true).done();
+    let mut radiotap = Radiotap::default();
+    let (errors, _) = Radiotap::parse_fields(&mut radiotap, (iterator.clone(), rest), &parser)?;
+    violations(&iterator, &radiotap, &errors)
+}
+
+/// Returns the violations of a capture that was parsed leniently into
+/// `radiotap`, with the `errors` of the fields that could not be parsed.
+pub(crate) fn violations(
+    iterator: &RadiotapIterator,
+    radiotap: &Radiotap,
+    errors: &[FieldError],
+) -> Result<Vec<Violation>> {
+    let input = iterator.data;
+    let layout = iterator.layout()?;
+    let mut violations = Vec::new();
+
+    // Padding and duplicates are checked in the order of the fields
+    let mut kinds = Vec::new();
+    let mut fields = layout.fields.iter();
+    for result in iterator {
+        let kind = match result {
+            Ok((kind, _)) => kind,
+            Err(_) => break,
+        };
+        let kind = match kind {
+            // A new radiotap namespace may repeat all fields
+            Kind::RadiotapNamespace => {
+                kinds.clear();
+                continue;
+            }
+            Kind::VendorNamespace(_) => Kind::VendorNamespace(None),
+            Kind::TLV(Some(kind)) if kind < 28 => Kind::new(kind as u8)?,
+            kind => kind,
+        };
+
+        if let Some(field) = fields.next() {
+            let start = field.offset - field.padding;
+            if let Some(i) = input[start..field.offset].iter().position(|&b| b != 0) {
+                violations.try_reserve(1)?;
+                violations.push(Violation::NonZeroPadding { offset: start + i });
+            }
+        }
+
+        // Vendor namespaces can be repeated, even with the same OUI
+        if kind != Kind::VendorNamespace(None) && kinds.contains(&kind) {
+            violations.try_reserve(1)?;
+            violations.push(Violation::DuplicateField { kind });
+        }
+        kinds.try_reserve(1)?;
+        kinds.push(kind);
+    }
+
+    // The end of the fields is unknown after a field of unknown size
+    if layout.trailing > 0 && iterator.header.present.iter().all(Kind::is_sized) {
+        violations.try_reserve(1)?;
+        violations.push(Violation::TrailingData {
+            offset: iterator.header.length - layout.trailing,
+            length: layout.trailing,
+        });
+    }
+
+    for error in errors {
+        match (error.kind, &error.error) {
+            // Fields this crate does not know are not violations
+            (_, Error::UnknownFieldBlocksParsing { .. }) | (None, _) => {}
+            (_, Error::Violations(found)) => {
+                violations.try_reserve(found.len())?;
+                violations.extend(found.iter().cloned());
+            }
+            (Some(kind), error) => {
+                violations.try_reserve(1)?;
+                violations.push(Violation::InvalidField {
+                    kind,
+                    reason: error.to_string(),
+                });
+            }
+        }
+    }
+
+    // The data rate of a VHT user is only missing if the combination of MCS
+    // index, bandwidth, and spatial streams is not defined
+    if let Some(VHT {
+        bw: Some(bw),
+        gi: Some(gi),
+        users,
+        ..
+    }) = radiotap.vht
+    {
+        for user in users.iter().flatten() {
+            if vht_rate(user.index, bw, gi, user.nss).is_err() {
+                violations.try_reserve(1)?;
+                violations.push(Violation::InvalidField {
+                    kind: Kind::VHT,
+                    reason: format!(
+                        "MCS {} with {} spatial streams is not defined at {} MHz",
+                        user.index,
+                        user.nss,
+                        bw.width()
+                    ),
+                });
+            }
+        }
+    }
+
+    Ok(violations)
+}
+
+/// Returns the combinations of fields of a [Radiotap](../struct.Radiotap.html)
+/// that contradict each other, which kernels reject in injected frames, see
+/// [RadiotapBuilder::try_done](../struct.RadiotapBuilder.html#method.try_done).
+pub(crate) fn conflicts(radiotap: &Radiotap) -> Result<Vec<Violation>> {
+    let mut violations = Vec::new();
+
+    // A frame is sent with a single PHY
+    let mut phys = [Kind::MCS, Kind::VHT, Kind::HE, EHT::KIND]
+        .iter()
+        .filter(|&&kind| radiotap.contains(kind));
+    if let Some(phy) = phys.next() {
+        for &kind in phys {
+            violations.try_reserve(1)?;
+            violations.push(Violation::InvalidField {
+                kind,
+                reason: format!("set together with the {:?} field", phy),
+            });
+        }
+    }
+
+    // The U-SIG is only sent in EHT PPDUs
+    if radiotap.u_sig.is_some() && radiotap.eht.is_none() {
+        violations.try_reserve(1)?;
+        violations.push(Violation::InvalidField {
+            kind: USig::KIND,
+            reason: "set without the EHT field".to_string(),
+        });
+    }
+
+    if let Some(vht) = &radiotap.vht {
+        if vht.users.iter().all(Option::is_none) {
+            violations.try_reserve(1)?;
+            violations.push(Violation::InvalidField {
+                kind: Kind::VHT,
+                reason: "no user is set".to_string(),
+            });
+        }
+    }
+
+    let bands = [
+        (
+            Kind::Channel,
+            radiotap.channel.map(|c| (c.flags.ghz2, c.flags.ghz5)),
+        ),
+        (
+            Kind::XChannel,
+            radiotap.xchannel.map(|c| (c.flags.ghz2, c.flags.ghz5)),
+        ),
+    ];
+    for &(kind, flags) in bands.iter() {
+        if flags == Some((true, true)) {
+            violations.try_reserve(1)?;
+            violations.push(Violation::InvalidField {
+                kind,
+                reason: "both the 2 GHz and 5 GHz flags are set".to_string(),
+            });
+        }
+    }
+
+    Ok(violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn violations() {
+        // The padding before the VHT field is not zero, and the L-SIG field
+        // is repeated as a TLV record
+        let frame = [
+            0, 0, 36, 0, 0x02, 0, 0x20, 0x18, 0, 0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 27, 0, 4, 0, 0, 0, 0, 0,
+        ];
+
+        assert_eq!(
+            validate(&frame).unwrap(),
+            [
+                Violation::NonZeroPadding { offset: 9 },
+                Violation::DuplicateField { kind: Kind::LSig },
+            ]
+        );
+    }
+
+    #[test]
+    fn violations_display() {
+        let violations = [
+            Violation::NonZeroPadding { offset: 9 },
+            Violation::DuplicateField { kind: Kind::LSig },
+        ];
+
+        let error = Error::Violations(Vec::new());
+        assert_eq!(
+            error.to_string(),
+            "radiotap capture violates the specification: no violations"
+        );
+        let error = Error::Violations(violations[..1].to_vec());
+        assert_eq!(
+            error.to_string(),
+            "radiotap capture violates the specification: non-zero padding byte at offset 9"
+        );
+        let error = Error::Violations(violations.to_vec());
+        assert!(error.to_string().ends_with("offset 9 and 1 more"));
+    }
+
+    #[test]
+    fn valid() {
+        let frame = [
+            0, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+
+        assert!(validate(&frame).unwrap().is_empty());
+    }
+
+    #[test]
+    fn conflicts() {
+        use crate::{
+            field::{Channel, Field, TxPower, MCS},
+            BuildError, RadiotapBuilder,
+        };
+
+        let builder = RadiotapBuilder::new().tx_power(TxPower { value: 10 });
+        assert!(builder.clone().try_done().is_ok());
+
+        // A 2.4 GHz channel also flagged as 5 GHz, with both HT and VHT set
+        let channel = Channel::from_bytes(&[0x85, 0x09, 0x80, 0x01]).unwrap();
+        let builder = builder
+            .channel(channel)
+            .mcs(MCS::default())
+            .vht(VHT::default());
+        let violations = match builder.try_done() {
+            Err(BuildError::Violations(violations)) => violations,
+            result => panic!("not rejected: {:?}", result),
+        };
+        assert_eq!(
+            violations,
+            [
+                Violation::InvalidField {
+                    kind: Kind::VHT,
+                    reason: "set together with the MCS field".to_string(),
+                },
+                Violation::InvalidField {
+                    kind: Kind::VHT,
+                    reason: "no user is set".to_string(),
+                },
+                Violation::InvalidField {
+                    kind: Kind::Channel,
+                    reason: "both the 2 GHz and 5 GHz flags are set".to_string(),
+                },
+            ]
+        );
+
+        let builder = RadiotapBuilder::new().u_sig(USig::default());
+        match builder.clone().try_done() {
+            Err(BuildError::Violations(violations)) => assert_eq!(
+                violations,
+                [Violation::InvalidField {
+                    kind: USig::KIND,
+                    reason: "set without the EHT field".to_string(),
+                }]
+            ),
+            result => panic!("not rejected: {:?}", result),
+        }
+        assert!(builder.eht(EHT::default()).try_done().is_ok());
+    }
+
+    #[test]
+    fn vht_combination() {
+        // MCS 9 with a single spatial stream at 20 MHz
+        let frame = [
+            0, 0, 20, 0, 0, 0, 0x20, 0, 0x44, 0, 0, 0, 0x91, 0, 0, 0, 0, 0, 0, 0,
+        ];
+
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(radiotap.vht.unwrap().users[0].unwrap().datarate, None);
+        assert_eq!(
+            validate(&frame).unwrap(),
+            [Violation::InvalidField {
+                kind: Kind::VHT,
+                reason: "MCS 9 with 1 spatial streams is not defined at 20 MHz".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,274 @@
+//! A zero-copy view of a Radiotap capture that decodes fields on demand.
+//!
+//! Constructing a [RadiotapView](struct.RadiotapView.html) only locates the
+//! fields, which is cheaper than [Radiotap::parse](../struct.Radiotap.html#method.parse)
+//! when only a few fields are read.
+
+#[cfg(feature = "zerocopy")]
+use crate::raw::RawField;
+use crate::{
+    field::{tlv, *},
+    Error, RadiotapIterator, Result,
+};
+
+/// The fields of the first radiotap namespace of a capture, decoded when they
+/// are accessed.
+///
+/// # Examples
+///
+/// ```
+/// use radiotap::view::RadiotapView;
+///
+/// let capture = [0, 0, 10, 0, 0x24, 0, 0, 0, 12, 0xd6, 0x80, 0];
+/// let (view, rest) = RadiotapView::parse(&capture).unwrap();
+/// assert_eq!(view.antenna_signal().unwrap().unwrap().value, -42);
+/// assert_eq!(view.channel().unwrap(), None);
+/// assert_eq!(rest, &[0x80, 0]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct RadiotapView<'a> {
+    header: Header,
+    /// The data of each field defined by a present bit, by bit.
+    fields: [Option<&'a [u8]>; 28],
+    u_sig: Option<&'a [u8]>,
+    eht: Option<&'a [u8]>,
+}
+
+impl<'a> RadiotapView<'a> {
+    /// Returns the view of the Radiotap capture at the start of the input.
+    pub fn from_bytes(input: &'a [u8]) -> Result<RadiotapView<'a>> {
+        Ok(RadiotapView::parse(input)?.0)
+    }
+
+    /// Returns the view of the Radiotap capture at the start of the input, and
+    /// the remaining data.
+    ///
+    /// Like [Radiotap::parse](../struct.Radiotap.html#method.parse), fields
+    /// after a field of unknown size are not available, and a field repeated
+    /// as a TLV record replaces the first one.
+    pub fn parse(input: &'a [u8]) -> Result<(RadiotapView<'a>, &'a [u8])> {
+        let (iterator, rest) = RadiotapIterator::parse(input)?;
+        let mut view = RadiotapView {
+            header: iterator.header.clone(),
+            fields: [None; 28],
+            u_sig: None,
+            eht: None,
+        };
+
+        for result in iterator {
+            let (kind, data) = match result {
+                Ok(field) => field,
+                Err(Error::UnknownFieldBlocksParsing { .. }) => break,
+                Err(e) => return Err(e),
+            };
+            match kind {
+                // Only the first radiotap namespace is kept
+                Kind::RadiotapNamespace => break,
+                Kind::TLV(Some(tlv::U_SIG)) => view.u_sig = Some(data),
+                Kind::TLV(Some(tlv::EHT)) => view.eht = Some(data),
+                Kind::TLV(Some(bit)) if bit < 28 => view.fields[usize::from(bit)] = Some(data),
+                Kind::TLV(_) | Kind::VendorNamespace(_) => {}
+                kind if kind.bit() < 28 => view.fields[usize::from(kind.bit())] = Some(data),
+                _ => {}
+            }
+        }
+
+        Ok((view, rest))
+    }
+
+    /// Returns the header of the capture.
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Returns the raw data of a field defined by a present bit, if it is
+    /// present.
+    pub fn data(&self, kind: Kind) -> Option<&'a [u8]> {
+        match kind {
+            Kind::TLV(_) | Kind::VendorNamespace(_) | Kind::RadiotapNamespace => None,
+            kind => self.fields.get(usize::from(kind.bit())).copied().flatten(),
+        }
+    }
+
+    /// Returns a field defined by a present bit in its on-wire layout, if it
+    /// is present, see the [raw](../raw/index.html) module.
+    #[cfg(feature = "zerocopy")]
+    pub fn raw<T: RawField>(&self) -> Result<Option<&'a T>> {
+        self.data(<T::Field as PresentField>::KIND)
+            .map(T::from_data)
+            .transpose()
+    }
+
+    /// Decodes a field defined by a present bit, if it is present.
+    fn get<T: Field>(&self, kind: Kind) -> Result<Option<T>> {
+        self.data(kind).map(from_bytes).transpose()
+    }
+
+    /// Returns the [TSFT](../field/struct.TSFT.html) field.
+    pub fn tsft(&self) -> Result<Option<TSFT>> {
+        self.get(Kind::TSFT)
+    }
+
+    /// Returns the [Flags](../field/struct.Flags.html) field.
+    pub fn flags(&self) -> Result<Option<Flags>> {
+        self.get(Kind::Flags)
+    }
+
+    /// Returns the [Rate](../field/struct.Rate.html) field.
+    pub fn rate(&self) -> Result<Option<Rate>> {
+        self.get(Kind::Rate)
+    }
+
+    /// Returns the [Channel](../field/struct.Channel.html) field.
+    pub fn channel(&self) -> Result<Option<Channel>> {
+        self.get(Kind::Channel)
+    }
+
+    /// Returns the [FHSS](../field/struct.FHSS.html) field.
+    pub fn fhss(&self) -> Result<Option<FHSS>> {
+        self.get(Kind::FHSS)
+    }
+
+    /// Returns the [AntennaSignal](../field/struct.AntennaSignal.html) field.
+    pub fn antenna_signal(&self) -> Result<Option<AntennaSignal>> {
+        self.get(Kind::AntennaSignal)
+    }
+
+    /// Returns the [AntennaNoise](../field/struct.AntennaNoise.html) field.
+    pub fn antenna_noise(&self) -> Result<Option<AntennaNoise>> {
+        self.get(Kind::AntennaNoise)
+    }
+
+    /// Returns the [LockQuality](../field/struct.LockQuality.html) field.
+    pub fn lock_quality(&self) -> Result<Option<LockQuality>> {
+        self.get(Kind::LockQuality)
+    }
+
+    /// Returns the [TxAttenuation](../field/struct.TxAttenuation.html) field.
+    pub fn tx_attenuation(&self) -> Result<Option<TxAttenuation>> {
+        self.get(Kind::TxAttenuation)
+    }
+
+    /// Returns the [TxAttenuationDb](../field/struct.TxAttenuationDb.html) field.
+    pub fn tx_attenuation_db(&self) -> Result<Option<TxAttenuationDb>> {
+        self.get(Kind::TxAttenuationDb)
+    }
+
+    /// Returns the [TxPower](../field/struct.TxPower.html) field.
+    pub fn tx_power(&self) -> Result<Option<TxPower>> {
+        self.get(Kind::TxPower)
+    }
+
+    /// Returns the [Antenna](../field/struct.Antenna.html) field.
+    pub fn antenna(&self) -> Result<Option<Antenna>> {
+        self.get(Kind::Antenna)
+    }
+
+    /// Returns the [AntennaSignalDb](../field/struct.AntennaSignalDb.html) field.
+    pub fn antenna_signal_db(&self) -> Result<Option<AntennaSignalDb>> {
+        self.get(Kind::AntennaSignalDb)
+    }
+
+    /// Returns the [AntennaNoiseDb](../field/struct.AntennaNoiseDb.html) field.
+    pub fn antenna_noise_db(&self) -> Result<Option<AntennaNoiseDb>> {
+        self.get(Kind::AntennaNoiseDb)
+    }
+
+    /// Returns the [RxFlags](../field/struct.RxFlags.html) field.
+    pub fn rx_flags(&self) -> Result<Option<RxFlags>> {
+        self.get(Kind::RxFlags)
+    }
+
+    /// Returns the [TxFlags](../field/struct.TxFlags.html) field.
+    pub fn tx_flags(&self) -> Result<Option<TxFlags>> {
+        self.get(Kind::TxFlags)
+    }
+
+    /// Returns the [RTSRetries](../field/struct.RTSRetries.html) field.
+    pub fn rts_retries(&self) -> Result<Option<RTSRetries>> {
+        self.get(Kind::RTSRetries)
+    }
+
+    /// Returns the [DataRetries](../field/struct.DataRetries.html) field.
+    pub fn data_retries(&self) -> Result<Option<DataRetries>> {
+        self.get(Kind::DataRetries)
+    }
+
+    /// Returns the [XChannel](../field/struct.XChannel.html) field.
+    pub fn xchannel(&self) -> Result<Option<XChannel>> {
+        self.get(Kind::XChannel)
+    }
+
+    /// Returns the [MCS](../field/struct.MCS.html) field.
+    pub fn mcs(&self) -> Result<Option<MCS>> {
+        self.get(Kind::MCS)
+    }
+
+    /// Returns the [AMPDUStatus](../field/struct.AMPDUStatus.html) field.
+    pub fn ampdu_status(&self) -> Result<Option<AMPDUStatus>> {
+        self.get(Kind::AMPDUStatus)
+    }
+
+    /// Returns the [VHT](../field/struct.VHT.html) field.
+    pub fn vht(&self) -> Result<Option<VHT>> {
+        self.get(Kind::VHT)
+    }
+
+    /// Returns the [Timestamp](../field/struct.Timestamp.html) field.
+    pub fn timestamp(&self) -> Result<Option<Timestamp>> {
+        self.get(Kind::Timestamp)
+    }
+
+    /// Returns the [HE](../field/struct.HE.html) field.
+    pub fn he(&self) -> Result<Option<HE>> {
+        self.get(Kind::HE)
+    }
+
+    /// Returns the [HEMU](../field/struct.HEMU.html) field.
+    pub fn he_mu(&self) -> Result<Option<HEMU>> {
+        self.get(Kind::HEMU)
+    }
+
+    /// Returns the [HEMUOtherUser](../field/struct.HEMUOtherUser.html) field.
+    pub fn he_mu_other_user(&self) -> Result<Option<HEMUOtherUser>> {
+        self.get(Kind::HEMUOtherUser)
+    }
+
+    /// Returns the [LSig](../field/struct.LSig.html) field.
+    pub fn l_sig(&self) -> Result<Option<LSig>> {
+        self.get(Kind::LSig)
+    }
+
+    /// Returns the [USig](../field/struct.USig.html) field.
+    pub fn u_sig(&self) -> Result<Option<USig>> {
+        self.u_sig.map(from_bytes).transpose()
+    }
+
+    /// Returns the [EHT](../field/struct.EHT.html) field.
+    pub fn eht(&self) -> Result<Option<EHT>> {
+        self.eht.map(from_bytes).transpose()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::Radiotap;
+
+    #[test]
+    fn matches_parse() {
+        let frame = [
+            0, 0, 39, 0, 46, 72, 0, 192, 0, 0, 0, 128, 0, 0, 0, 160, 4, 0, 0, 0, 16, 2, 158, 9,
+            160, 0, 227, 5, 0, 0, 255, 255, 255, 255, 2, 0, 222, 173, 4,
+        ];
+
+        let view = RadiotapView::from_bytes(&frame).unwrap();
+        let radiotap = Radiotap::from_bytes(&frame).unwrap();
+        assert_eq!(view.rate().unwrap(), radiotap.rate);
+        assert_eq!(view.channel().unwrap(), radiotap.channel);
+        assert_eq!(view.antenna_signal().unwrap(), radiotap.antenna_signal);
+        assert_eq!(view.rx_flags().unwrap(), radiotap.rx_flags);
+        assert_eq!(view.vht().unwrap(), None);
+        assert_eq!(view.header(), &radiotap.header);
+    }
+}
@@ -0,0 +1,56 @@
+//! JavaScript bindings with [wasm-bindgen](https://rustwasm.github.io/wasm-bindgen/).
+//!
+//! Enabled with the `wasm` feature, which also enables `serde`. The crate
+//! builds for `wasm32-unknown-unknown`, and these functions are exported to
+//! JavaScript so captures can be decoded in the browser, for example by a
+//! pcap analyzer reading files client side.
+//!
+//! [decode](fn.decode.html) returns the parsed capture as a plain JavaScript
+//! object with the structure of the [Radiotap](../struct.Radiotap.html)
+//! struct, absent fields are `undefined`. The 802.11 frame starts at
+//! `header.length`:
+//!
+//! ```js
+//! import { decode } from "radiotap";
+//!
+//! const radiotap = decode(frame);
+//! console.log(radiotap.antenna_signal?.value, frame.subarray(radiotap.header.length));
+//! ```
+//!
+//! Errors are thrown as JavaScript `Error`s with the message of the
+//! [Error](../enum.Error.html).
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::Radiotap;
+
+/// Returns the parsed Radiotap capture as a JavaScript object.
+#[wasm_bindgen]
+pub fn decode(data: &[u8]) -> Result<JsValue, JsError> {
+    let radiotap = Radiotap::from_bytes(data)?;
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+    Ok(radiotap.serialize(&serializer)?)
+}
+
+/// Returns the Radiotap capture as JSON with the Wireshark field names, see
+/// [to_json](../struct.Radiotap.html#method.to_json).
+#[wasm_bindgen(js_name = toJson)]
+pub fn to_json(data: &[u8]) -> Result<String, JsError> {
+    Ok(Radiotap::from_bytes(data)?.to_json())
+}
+
+/// Returns a one line summary of the Radiotap capture, see
+/// [to_line](../struct.Radiotap.html#method.to_line).
+#[wasm_bindgen(js_name = toLine)]
+pub fn to_line(data: &[u8]) -> Result<String, JsError> {
+    Ok(Radiotap::from_bytes(data)?.to_line())
+}
+
+/// Returns the length of the Radiotap header at the start of the data, where
+/// the 802.11 frame starts, see
+/// [peek_length](../struct.Radiotap.html#method.peek_length).
+#[wasm_bindgen(js_name = headerLength)]
+pub fn header_length(data: &[u8]) -> Result<usize, JsError> {
+    Ok(Radiotap::peek_length(data)?)
+}